@@ -1,13 +1,17 @@
-use crate::convertors::wkt_to_svg;
-use crate::geometry_boolean::{geometry_boolean, wkt_boolean};
-use crate::information::get_geometry_type;
-use crate::json_errors;
-use geo_svg_io::geo_svg_reader::svg_to_geometry;
-use geo_svg_io::geo_svg_writer::{ToSvg, ToSvgString};
-use geo_wkt_writer::ToWkt;
+use crate::geometry_boolean;
+use crate::geometry_convert::{geometry_to_svg, geometry_to_svg_path_string};
+use crate::geometry_geojson;
+use crate::geometry_precision::{geometry_with_precision, Precision};
+use geo_booleanop::boolean::Operation;
 use wasm_bindgen::prelude::*;
 
-/** WKT Booleans */
+/*
+ * The coordinates of the result of every non-snapped boolean are rounded as set by
+ * the optional `precision` and `precisionMode` arguments, see `wktToSvg`. An invalid
+ * `precision` or `precisionMode` throws an INVALID_ARGUMENT error.
+ */
+
+/* WKT Booleans */
 
 /// Generates a union from two WKT geometries.
 ///
@@ -15,8 +19,20 @@ use wasm_bindgen::prelude::*;
 /// or if invalid geometries have been submitted.
 ///
 #[wasm_bindgen(js_name = wktPolygonUnion)]
-pub fn wkt_polygon_union(geom1: String, geom2: String) -> Result<String, JsValue> {
-    wkt_polygon_boolean(geom1, geom2, geo_booleanop::boolean::Operation::Union)
+pub fn wkt_polygon_union(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    wkt_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Union,
+        false,
+        precision,
+        precision_mode,
+    )
 }
 
 /// Generates a difference from two WKT geometries.
@@ -25,8 +41,20 @@ pub fn wkt_polygon_union(geom1: String, geom2: String) -> Result<String, JsValue
 /// or if invalid geometries have been submitted.
 ///
 #[wasm_bindgen(js_name = wktPolygonDifference)]
-pub fn wkt_polygon_difference(geom1: String, geom2: String) -> Result<String, JsValue> {
-    wkt_polygon_boolean(geom1, geom2, geo_booleanop::boolean::Operation::Difference)
+pub fn wkt_polygon_difference(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    wkt_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Difference,
+        false,
+        precision,
+        precision_mode,
+    )
 }
 
 /// Generates an intersection from two WKT geometries.
@@ -35,11 +63,19 @@ pub fn wkt_polygon_difference(geom1: String, geom2: String) -> Result<String, Js
 /// or if invalid geometries have been submitted.
 ///
 #[wasm_bindgen(js_name = wktPolygonIntersection)]
-pub fn wkt_polygon_intersection(geom1: String, geom2: String) -> Result<String, JsValue> {
+pub fn wkt_polygon_intersection(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
     wkt_polygon_boolean(
         geom1,
         geom2,
-        geo_booleanop::boolean::Operation::Intersection,
+        Operation::Intersection,
+        false,
+        precision,
+        precision_mode,
     )
 }
 
@@ -49,35 +85,130 @@ pub fn wkt_polygon_intersection(geom1: String, geom2: String) -> Result<String,
 /// or if invalid geometries have been submitted.
 ///
 #[wasm_bindgen(js_name = wktPolygonSymmetricDifference)]
-pub fn wkt_polygon_xor(geom1: String, geom2: String) -> Result<String, JsValue> {
-    wkt_polygon_boolean(geom1, geom2, geo_booleanop::boolean::Operation::Xor)
+pub fn wkt_polygon_xor(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    wkt_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Xor,
+        false,
+        precision,
+        precision_mode,
+    )
 }
 
-/// Perform a boolean operation on the submitted WKT geometries
+/// Generates a union of all the submitted WKT geometries.
 ///
-/// This performs a quick test first to determine if the submitted geometry types are
-/// suitable for the boolean operation (only MULTIPOLYGON and POLYGON are supported)
-fn wkt_polygon_boolean(
+/// The result is always a MULTIPOLYGON. It throws an error naming the index of the
+/// first geometry that is not a valid POLYGON or MULTIPOLYGON.
+///
+#[wasm_bindgen(js_name = wktUnionAll)]
+pub fn wkt_union_all(
+    geoms: Vec<String>,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    let precision = Precision::parse(precision, precision_mode.as_deref())?;
+    Ok(geometry_boolean::wkt_union_all_with_precision(
+        &geoms, precision,
+    )?)
+}
+
+/* Joined WKT Booleans */
+
+/// Generates a union from two WKT geometries, joined into a single polygon.
+///
+/// It throws an error if the union operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = wktPolygonUnionJoined)]
+pub fn wkt_polygon_union_joined(
     geom1: String,
     geom2: String,
-    op: geo_booleanop::boolean::Operation,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
 ) -> Result<String, JsValue> {
-    // Grab the stated type of each input
-    let geom1_type = get_geometry_type(&geom1)?;
-    let geom2_type = get_geometry_type(&geom2)?;
+    wkt_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Union,
+        true,
+        precision,
+        precision_mode,
+    )
+}
 
-    if (geom1_type.eq_ignore_ascii_case("MultiPolygon")
-        || geom1_type.eq_ignore_ascii_case("Polygon"))
-        && (geom2_type.eq_ignore_ascii_case("MultiPolygon")
-            || geom2_type.eq_ignore_ascii_case("Polygon"))
-    {
-        return wkt_boolean(&geom1, &geom2, op);
-    }
+/// Generates a difference from two WKT geometries, joined into a single polygon.
+///
+/// It throws an error if the difference operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = wktPolygonDifferenceJoined)]
+pub fn wkt_polygon_difference_joined(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    wkt_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Difference,
+        true,
+        precision,
+        precision_mode,
+    )
+}
 
-    Err(json_errors::geometry_processing_error::invalid_boolean_geom_pair(&geom1, &geom2))
+/// Generates an intersection from two WKT geometries, joined into a single polygon.
+///
+/// It throws an error if the intersection operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = wktPolygonIntersectionJoined)]
+pub fn wkt_polygon_intersection_joined(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    wkt_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Intersection,
+        true,
+        precision,
+        precision_mode,
+    )
+}
+
+/// Generates a symmetric difference from two WKT geometries, joined into a single polygon.
+///
+/// It throws an error if the symmetric difference operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = wktPolygonSymmetricDifferenceJoined)]
+pub fn wkt_polygon_xor_joined(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    wkt_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Xor,
+        true,
+        precision,
+        precision_mode,
+    )
 }
 
-/** SVG Booleans */
+/* SVG Booleans */
 
 /// Generates a union from two SVG geometries.
 ///
@@ -85,8 +216,20 @@ fn wkt_polygon_boolean(
 /// or if invalid geometries have been submitted.
 ///
 #[wasm_bindgen(js_name = svgPolygonUnion)]
-pub fn svg_polygon_union(geom1: String, geom2: String) -> Result<String, JsValue> {
-    svg_polygon_boolean(&geom1, &geom2, geo_booleanop::boolean::Operation::Union)
+pub fn svg_polygon_union(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    svg_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Union,
+        false,
+        precision,
+        precision_mode,
+    )
 }
 
 /// Generates a difference from two SVG geometries.
@@ -95,11 +238,19 @@ pub fn svg_polygon_union(geom1: String, geom2: String) -> Result<String, JsValue
 /// or if invalid geometries have been submitted.
 ///
 #[wasm_bindgen(js_name = svgPolygonDifference)]
-pub fn svg_polygon_difference(geom1: String, geom2: String) -> Result<String, JsValue> {
+pub fn svg_polygon_difference(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
     svg_polygon_boolean(
-        &geom1,
-        &geom2,
-        geo_booleanop::boolean::Operation::Difference,
+        geom1,
+        geom2,
+        Operation::Difference,
+        false,
+        precision,
+        precision_mode,
     )
 }
 
@@ -109,11 +260,19 @@ pub fn svg_polygon_difference(geom1: String, geom2: String) -> Result<String, Js
 /// or if invalid geometries have been submitted.
 ///
 #[wasm_bindgen(js_name = svgPolygonIntersection)]
-pub fn svg_polygon_intersection(geom1: String, geom2: String) -> Result<String, JsValue> {
+pub fn svg_polygon_intersection(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
     svg_polygon_boolean(
-        &geom1,
-        &geom2,
-        geo_booleanop::boolean::Operation::Intersection,
+        geom1,
+        geom2,
+        Operation::Intersection,
+        false,
+        precision,
+        precision_mode,
     )
 }
 
@@ -123,42 +282,152 @@ pub fn svg_polygon_intersection(geom1: String, geom2: String) -> Result<String,
 /// or if invalid geometries have been submitted.
 ///
 #[wasm_bindgen(js_name = svgPolygonSymmetricDifference)]
-pub fn svg_polygon_xor(geom1: String, geom2: String) -> Result<String, JsValue> {
-    svg_polygon_boolean(&geom1, &geom2, geo_booleanop::boolean::Operation::Xor)
+pub fn svg_polygon_xor(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    svg_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Xor,
+        false,
+        precision,
+        precision_mode,
+    )
 }
 
-/// Perform a boolean operation on the submitted SVG geometries
+/// Generates a union of all the submitted SVG geometries.
 ///
-/// This performs a quick test first to determine if the submitted geometry types are
-/// suitable for the boolean operation (only MULTIPOLYGON and POLYGON are supported)
-fn svg_polygon_boolean(
-    geom1: &String,
-    geom2: &String,
-    op: geo_booleanop::boolean::Operation,
+/// The result is always rendered from a MULTIPOLYGON. It throws an error naming the
+/// index of the first geometry that is not a valid polygon or multi polygon.
+///
+#[wasm_bindgen(js_name = svgUnionAll)]
+pub fn svg_union_all(
+    geoms: Vec<String>,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
 ) -> Result<String, JsValue> {
-    let g1 = match svg_to_geometry(geom1) {
-        Ok(g) => g,
-        Err(e) => return Err(json_errors::svg_error::could_not_parse()),
-    };
-    let g2 = match svg_to_geometry(geom2) {
-        Ok(g) => g,
-        Err(e) => return Err(json_errors::svg_error::could_not_parse()),
-    };
+    let precision = Precision::parse(precision, precision_mode.as_deref())?;
+    Ok(geometry_to_svg(&geometry_with_precision(
+        &geometry_boolean::svg_union_all(&geoms)?.into(),
+        precision,
+    )))
+}
+
+/* Joined SVG Booleans */
 
-    match geometry_boolean(&g1, &g2, op) {
-        Ok(g) => Ok(g.to_svg()),
-        Err(e) => Err(e),
-    }
+/// Generates a union from two SVG geometries, joined into a single polygon.
+///
+/// It throws an error if the union operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = svgPolygonUnionJoined)]
+pub fn svg_polygon_union_joined(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    svg_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Union,
+        true,
+        precision,
+        precision_mode,
+    )
+}
+
+/// Generates a difference from two SVG geometries, joined into a single polygon.
+///
+/// It throws an error if the difference operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = svgPolygonDifferenceJoined)]
+pub fn svg_polygon_difference_joined(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    svg_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Difference,
+        true,
+        precision,
+        precision_mode,
+    )
+}
+
+/// Generates an intersection from two SVG geometries, joined into a single polygon.
+///
+/// It throws an error if the intersection operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = svgPolygonIntersectionJoined)]
+pub fn svg_polygon_intersection_joined(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    svg_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Intersection,
+        true,
+        precision,
+        precision_mode,
+    )
+}
+
+/// Generates a symmetric difference from two SVG geometries, joined into a single polygon.
+///
+/// It throws an error if the symmetric difference operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = svgPolygonSymmetricDifferenceJoined)]
+pub fn svg_polygon_xor_joined(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    svg_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Xor,
+        true,
+        precision,
+        precision_mode,
+    )
 }
 
+/* SVG d-string Booleans */
+
 /// Generates a union from two SVG geometries.
 ///
 /// It throws an error if the union operation on the two geometry types is not supported,
 /// or if invalid geometries have been submitted.
 ///
 #[wasm_bindgen(js_name = svgStringPolygonUnion)]
-pub fn svg_string_polygon_union(geom1: String, geom2: String) -> Result<String, JsValue> {
-    svg_string_polygon_boolean(&geom1, &geom2, geo_booleanop::boolean::Operation::Union)
+pub fn svg_string_polygon_union(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    svg_string_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Union,
+        false,
+        precision,
+        precision_mode,
+    )
 }
 
 /// Generates a difference from two SVG geometries.
@@ -167,11 +436,19 @@ pub fn svg_string_polygon_union(geom1: String, geom2: String) -> Result<String,
 /// or if invalid geometries have been submitted.
 ///
 #[wasm_bindgen(js_name = svgStringPolygonDifference)]
-pub fn svg_string_polygon_difference(geom1: String, geom2: String) -> Result<String, JsValue> {
+pub fn svg_string_polygon_difference(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
     svg_string_polygon_boolean(
-        &geom1,
-        &geom2,
-        geo_booleanop::boolean::Operation::Difference,
+        geom1,
+        geom2,
+        Operation::Difference,
+        false,
+        precision,
+        precision_mode,
     )
 }
 
@@ -181,11 +458,19 @@ pub fn svg_string_polygon_difference(geom1: String, geom2: String) -> Result<Str
 /// or if invalid geometries have been submitted.
 ///
 #[wasm_bindgen(js_name = svgStringPolygonIntersection)]
-pub fn svg_string_polygon_intersection(geom1: String, geom2: String) -> Result<String, JsValue> {
+pub fn svg_string_polygon_intersection(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
     svg_string_polygon_boolean(
-        &geom1,
-        &geom2,
-        geo_booleanop::boolean::Operation::Intersection,
+        geom1,
+        geom2,
+        Operation::Intersection,
+        false,
+        precision,
+        precision_mode,
     )
 }
 
@@ -195,30 +480,563 @@ pub fn svg_string_polygon_intersection(geom1: String, geom2: String) -> Result<S
 /// or if invalid geometries have been submitted.
 ///
 #[wasm_bindgen(js_name = svgStringPolygonSymmetricDifference)]
-pub fn svg_string_polygon_xor(geom1: String, geom2: String) -> Result<String, JsValue> {
-    svg_string_polygon_boolean(&geom1, &geom2, geo_booleanop::boolean::Operation::Xor)
+pub fn svg_string_polygon_xor(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    svg_string_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Xor,
+        false,
+        precision,
+        precision_mode,
+    )
+}
+
+/* Joined SVG d-string Booleans */
+
+/// Generates a union from two SVG geometries, joined into a single polygon.
+///
+/// It throws an error if the union operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = svgStringPolygonUnionJoined)]
+pub fn svg_string_polygon_union_joined(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    svg_string_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Union,
+        true,
+        precision,
+        precision_mode,
+    )
+}
+
+/// Generates a difference from two SVG geometries, joined into a single polygon.
+///
+/// It throws an error if the difference operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = svgStringPolygonDifferenceJoined)]
+pub fn svg_string_polygon_difference_joined(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    svg_string_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Difference,
+        true,
+        precision,
+        precision_mode,
+    )
+}
+
+/// Generates an intersection from two SVG geometries, joined into a single polygon.
+///
+/// It throws an error if the intersection operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = svgStringPolygonIntersectionJoined)]
+pub fn svg_string_polygon_intersection_joined(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    svg_string_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Intersection,
+        true,
+        precision,
+        precision_mode,
+    )
+}
+
+/// Generates a symmetric difference from two SVG geometries, joined into a single polygon.
+///
+/// It throws an error if the symmetric difference operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = svgStringPolygonSymmetricDifferenceJoined)]
+pub fn svg_string_polygon_xor_joined(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    svg_string_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Xor,
+        true,
+        precision,
+        precision_mode,
+    )
 }
 
-/// Perform a boolean operation on the submitted SVG geometries
+/* Snapped WKT Booleans */
+
+/// Generates a union from two WKT geometries, snap rounded to a grid.
+///
+/// Both geometries and the result are snapped to multiples of `gridSize`
+/// (1 by default, the pixel grid), and the result is always valid, so it can be
+/// used in repeated operations without drifting or becoming invalid.
+///
+/// It throws an error if the union operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = wktPolygonUnionSnapped)]
+pub fn wkt_polygon_union_snapped(
+    geom1: String,
+    geom2: String,
+    grid_size: Option<f64>,
+) -> Result<String, JsValue> {
+    Ok(geometry_boolean::wkt_polygon_boolean_snapped(
+        &geom1,
+        &geom2,
+        Operation::Union,
+        grid_size.unwrap_or(1.),
+    )?)
+}
+
+/// Generates a difference from two WKT geometries, snap rounded to a grid.
+///
+/// Both geometries and the result are snapped to multiples of `gridSize`
+/// (1 by default, the pixel grid), and the result is always valid, so it can be
+/// used in repeated operations without drifting or becoming invalid.
 ///
-/// This performs a quick test first to determine if the submitted geometry types are
-/// suitable for the boolean operation (only MULTIPOLYGON and POLYGON are supported)
+/// It throws an error if the difference operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = wktPolygonDifferenceSnapped)]
+pub fn wkt_polygon_difference_snapped(
+    geom1: String,
+    geom2: String,
+    grid_size: Option<f64>,
+) -> Result<String, JsValue> {
+    Ok(geometry_boolean::wkt_polygon_boolean_snapped(
+        &geom1,
+        &geom2,
+        Operation::Difference,
+        grid_size.unwrap_or(1.),
+    )?)
+}
+
+/// Generates a intersection from two WKT geometries, snap rounded to a grid.
+///
+/// Both geometries and the result are snapped to multiples of `gridSize`
+/// (1 by default, the pixel grid), and the result is always valid, so it can be
+/// used in repeated operations without drifting or becoming invalid.
+///
+/// It throws an error if the intersection operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = wktPolygonIntersectionSnapped)]
+pub fn wkt_polygon_intersection_snapped(
+    geom1: String,
+    geom2: String,
+    grid_size: Option<f64>,
+) -> Result<String, JsValue> {
+    Ok(geometry_boolean::wkt_polygon_boolean_snapped(
+        &geom1,
+        &geom2,
+        Operation::Intersection,
+        grid_size.unwrap_or(1.),
+    )?)
+}
+
+/// Generates a symmetric difference from two WKT geometries, snap rounded to a grid.
+///
+/// Both geometries and the result are snapped to multiples of `gridSize`
+/// (1 by default, the pixel grid), and the result is always valid, so it can be
+/// used in repeated operations without drifting or becoming invalid.
+///
+/// It throws an error if the symmetric difference operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = wktPolygonSymmetricDifferenceSnapped)]
+pub fn wkt_polygon_xor_snapped(
+    geom1: String,
+    geom2: String,
+    grid_size: Option<f64>,
+) -> Result<String, JsValue> {
+    Ok(geometry_boolean::wkt_polygon_boolean_snapped(
+        &geom1,
+        &geom2,
+        Operation::Xor,
+        grid_size.unwrap_or(1.),
+    )?)
+}
+
+/* Snapped SVG Booleans */
+
+/// Generates a union from two SVG geometries, snap rounded to a grid
+/// (see `wktPolygonUnionSnapped`).
+///
+/// It throws an error if the union operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = svgPolygonUnionSnapped)]
+pub fn svg_polygon_union_snapped(
+    geom1: String,
+    geom2: String,
+    grid_size: Option<f64>,
+) -> Result<String, JsValue> {
+    Ok(geometry_to_svg(
+        &geometry_boolean::svg_polygon_boolean_snapped(
+            &geom1,
+            &geom2,
+            Operation::Union,
+            grid_size.unwrap_or(1.),
+        )?,
+    ))
+}
+
+/// Generates a difference from two SVG geometries, snap rounded to a grid
+/// (see `wktPolygonDifferenceSnapped`).
+///
+/// It throws an error if the difference operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = svgPolygonDifferenceSnapped)]
+pub fn svg_polygon_difference_snapped(
+    geom1: String,
+    geom2: String,
+    grid_size: Option<f64>,
+) -> Result<String, JsValue> {
+    Ok(geometry_to_svg(
+        &geometry_boolean::svg_polygon_boolean_snapped(
+            &geom1,
+            &geom2,
+            Operation::Difference,
+            grid_size.unwrap_or(1.),
+        )?,
+    ))
+}
+
+/// Generates a intersection from two SVG geometries, snap rounded to a grid
+/// (see `wktPolygonIntersectionSnapped`).
+///
+/// It throws an error if the intersection operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = svgPolygonIntersectionSnapped)]
+pub fn svg_polygon_intersection_snapped(
+    geom1: String,
+    geom2: String,
+    grid_size: Option<f64>,
+) -> Result<String, JsValue> {
+    Ok(geometry_to_svg(
+        &geometry_boolean::svg_polygon_boolean_snapped(
+            &geom1,
+            &geom2,
+            Operation::Intersection,
+            grid_size.unwrap_or(1.),
+        )?,
+    ))
+}
+
+/// Generates a symmetric difference from two SVG geometries, snap rounded to a grid
+/// (see `wktPolygonSymmetricDifferenceSnapped`).
+///
+/// It throws an error if the symmetric difference operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = svgPolygonSymmetricDifferenceSnapped)]
+pub fn svg_polygon_xor_snapped(
+    geom1: String,
+    geom2: String,
+    grid_size: Option<f64>,
+) -> Result<String, JsValue> {
+    Ok(geometry_to_svg(
+        &geometry_boolean::svg_polygon_boolean_snapped(
+            &geom1,
+            &geom2,
+            Operation::Xor,
+            grid_size.unwrap_or(1.),
+        )?,
+    ))
+}
+
+/* GeoJSON Booleans */
+
+/// Generates a union from two GeoJSON geometries or Features.
+///
+/// It throws an error if the union operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = geojsonPolygonUnion)]
+pub fn geojson_polygon_union(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    geojson_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Union,
+        false,
+        precision,
+        precision_mode,
+    )
+}
+
+/// Generates a difference from two GeoJSON geometries or Features.
+///
+/// It throws an error if the difference operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = geojsonPolygonDifference)]
+pub fn geojson_polygon_difference(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    geojson_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Difference,
+        false,
+        precision,
+        precision_mode,
+    )
+}
+
+/// Generates an intersection from two GeoJSON geometries or Features.
+///
+/// It throws an error if the intersection operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = geojsonPolygonIntersection)]
+pub fn geojson_polygon_intersection(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    geojson_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Intersection,
+        false,
+        precision,
+        precision_mode,
+    )
+}
+
+/// Generates a symmetric difference from two GeoJSON geometries or Features.
+///
+/// It throws an error if the symmetric difference operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = geojsonPolygonSymmetricDifference)]
+pub fn geojson_polygon_xor(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    geojson_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Xor,
+        false,
+        precision,
+        precision_mode,
+    )
+}
+
+/// Generates a union of all the submitted GeoJSON geometries or Features.
+///
+/// The result is always a MultiPolygon. It throws an error naming the index of the
+/// first geometry that is not a valid Polygon or MultiPolygon.
+///
+#[wasm_bindgen(js_name = geojsonUnionAll)]
+pub fn geojson_union_all(
+    geoms: Vec<String>,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    let precision = Precision::parse(precision, precision_mode.as_deref())?;
+    Ok(
+        geometry_geojson::geometry_to_geojson(&geometry_with_precision(
+            &geometry_boolean::geojson_union_all(&geoms)?.into(),
+            precision,
+        ))
+        .to_string(),
+    )
+}
+
+/* Joined GeoJSON Booleans */
+
+/// Generates a union from two GeoJSON geometries or Features, joined into a single polygon.
+///
+/// It throws an error if the union operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = geojsonPolygonUnionJoined)]
+pub fn geojson_polygon_union_joined(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    geojson_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Union,
+        true,
+        precision,
+        precision_mode,
+    )
+}
+
+/// Generates a difference from two GeoJSON geometries or Features, joined into a single polygon.
+///
+/// It throws an error if the difference operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = geojsonPolygonDifferenceJoined)]
+pub fn geojson_polygon_difference_joined(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    geojson_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Difference,
+        true,
+        precision,
+        precision_mode,
+    )
+}
+
+/// Generates an intersection from two GeoJSON geometries or Features, joined into a single polygon.
+///
+/// It throws an error if the intersection operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = geojsonPolygonIntersectionJoined)]
+pub fn geojson_polygon_intersection_joined(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    geojson_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Intersection,
+        true,
+        precision,
+        precision_mode,
+    )
+}
+
+/// Generates a symmetric difference from two GeoJSON geometries or Features, joined into a single polygon.
+///
+/// It throws an error if the symmetric difference operation on the two geometry types is not supported,
+/// or if invalid geometries have been submitted.
+///
+#[wasm_bindgen(js_name = geojsonPolygonSymmetricDifferenceJoined)]
+pub fn geojson_polygon_xor_joined(
+    geom1: String,
+    geom2: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    geojson_polygon_boolean(
+        geom1,
+        geom2,
+        Operation::Xor,
+        true,
+        precision,
+        precision_mode,
+    )
+}
+
+/// Parses the precision arguments, then performs a boolean operation on the
+/// submitted WKT geometries
+fn wkt_polygon_boolean(
+    geom1: String,
+    geom2: String,
+    op: Operation,
+    joined: bool,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    let precision = Precision::parse(precision, precision_mode.as_deref())?;
+    Ok(geometry_boolean::wkt_polygon_boolean_with_precision(
+        &geom1, &geom2, op, joined, precision,
+    )?)
+}
+
+/// Parses the precision arguments, then performs a boolean operation on the
+/// submitted SVG geometries and renders the result as an SVG path
+fn svg_polygon_boolean(
+    geom1: String,
+    geom2: String,
+    op: Operation,
+    joined: bool,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    let precision = Precision::parse(precision, precision_mode.as_deref())?;
+    Ok(geometry_to_svg(&geometry_with_precision(
+        &geometry_boolean::svg_polygon_boolean(&geom1, &geom2, op, joined)?,
+        precision,
+    )))
+}
+
+/// Parses the precision arguments, then performs a boolean operation on the
+/// submitted SVG geometries and renders the result as an SVG d-string
 fn svg_string_polygon_boolean(
-    geom1: &String,
-    geom2: &String,
-    op: geo_booleanop::boolean::Operation,
-) -> Result<String, JsValue> {
-    let g1 = match svg_to_geometry(geom1) {
-        Ok(g) => g,
-        Err(e) => return Err(json_errors::svg_error::could_not_parse()),
-    };
-    let g2 = match svg_to_geometry(geom2) {
-        Ok(g) => g,
-        Err(e) => return Err(json_errors::svg_error::could_not_parse()),
-    };
-
-    match geometry_boolean(&g1, &g2, op) {
-        Ok(g) => Ok(g.to_svg_string()),
-        Err(e) => Err(e),
-    }
+    geom1: String,
+    geom2: String,
+    op: Operation,
+    joined: bool,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    let precision = Precision::parse(precision, precision_mode.as_deref())?;
+    Ok(geometry_to_svg_path_string(&geometry_with_precision(
+        &geometry_boolean::svg_polygon_boolean(&geom1, &geom2, op, joined)?,
+        precision,
+    )))
+}
+
+/// Parses the precision arguments, then performs a boolean operation on the
+/// submitted GeoJSON geometries or Features
+fn geojson_polygon_boolean(
+    geom1: String,
+    geom2: String,
+    op: Operation,
+    joined: bool,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    let precision = Precision::parse(precision, precision_mode.as_deref())?;
+    Ok(
+        geometry_geojson::geometry_to_geojson(&geometry_with_precision(
+            &geometry_boolean::geojson_polygon_boolean(&geom1, &geom2, op, joined)?,
+            precision,
+        ))
+        .to_string(),
+    )
 }
@@ -0,0 +1,50 @@
+use crate::geometry_buffer::{self, BufferOptions};
+use wasm_bindgen::prelude::*;
+
+/* Buffers */
+
+/// Grows a WKT (MULTI)POLYGON by a distance, or shrinks it when the distance is negative.
+///
+/// `join` is the style of the corners: "round" (the default), "mitre" or "bevel".
+/// Mitre joins are cut off at `mitreLimit` times the distance from the corner
+/// (5 by default), and round joins use `arcSegments` segments per quarter circle
+/// (8 by default). Holes shrink as the polygon grows, and parts thinner than
+/// twice the distance disappear when shrinking. The result is repaired when needed.
+///
+/// It throws an error if the geometry is not polygonal or not valid, or if the
+/// options are invalid.
+///
+#[wasm_bindgen(js_name = wktBuffer)]
+pub fn wkt_buffer(
+    wkt: String,
+    distance: f64,
+    join: Option<String>,
+    mitre_limit: Option<f64>,
+    arc_segments: Option<u32>,
+) -> Result<String, JsValue> {
+    Ok(geometry_buffer::wkt_buffer(
+        &wkt,
+        distance,
+        &BufferOptions::parse(join.as_deref(), mitre_limit, arc_segments)?,
+    )?)
+}
+
+/// Grows or shrinks an SVG element by a distance (see `wktBuffer`).
+///
+/// It throws an error if the element is not polygonal or not valid, or if the
+/// options are invalid.
+///
+#[wasm_bindgen(js_name = svgBuffer)]
+pub fn svg_buffer(
+    svg: String,
+    distance: f64,
+    join: Option<String>,
+    mitre_limit: Option<f64>,
+    arc_segments: Option<u32>,
+) -> Result<String, JsValue> {
+    Ok(geometry_buffer::svg_buffer(
+        &svg,
+        distance,
+        &BufferOptions::parse(join.as_deref(), mitre_limit, arc_segments)?,
+    )?)
+}
@@ -1,86 +1,298 @@
+use crate::geometry_convert;
+use crate::geometry_convert::svg_reader::SvgReadOptions;
+use crate::geometry_geojson;
+use crate::geometry_precision::Precision;
+use crate::geometry_wkb;
 use crate::json_errors;
-use geo_repair_polygon::repair::Repair;
-use geo_svg_io::geo_svg_reader::svg_to_geometry_collection;
-use geo_svg_io::geo_svg_writer::{ToSvg, ToSvgString};
-use geo_types::Geometry;
-use geo_wkt_writer::ToWkt;
+use serde::Deserialize;
 use wasm_bindgen::prelude::*;
-use wasm_bindgen::JsValue;
-use wkt::Wkt;
+
+/// The options of `svgToWkt` and `svgPathStringToWkt`, given as one object,
+/// e.g., `{ strict: true, tolerance: 0.1, precision: 2 }`. Every option can be left out.
+///
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SvgToWktOptions {
+    strict: Option<bool>,
+    tolerance: Option<f64>,
+    curves: Option<bool>,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+}
+
+impl SvgToWktOptions {
+    /// Reads the options object, which may be undefined or null
+    fn from_js(options: JsValue) -> Result<Self, JsValue> {
+        let options: Option<Self> = serde_wasm_bindgen::from_value(options).map_err(|err| {
+            json_errors::argument_error::invalid_argument("options", &err.to_string())
+        })?;
+        Ok(options.unwrap_or_default())
+    }
+}
 
 /// Convert an SVG element into a WKT representation.
 /// This function can read a <path>, <polygon>. <polyline>,
 /// <rect>, and <line>, all other SVG elements will return
 /// and error.
 ///
+/// The optional `options` object accepts:
+///
+/// * `strict`: when true, unsupported elements, invalid transforms, and
+///   malformed XML return an error instead of being passed over. The
+///   `transform` attributes of the element and of its parent `<g>` groups
+///   are always applied.
+/// * `tolerance`: the largest distance allowed between a curve and the
+///   straight lines that replace it, in output units (by default each curve
+///   becomes 100 points). A tolerance finer than a millionth of the size of a
+///   curve is raised to that, which keeps every curve to a few thousand points.
+/// * `curves`: when true, curves are kept as circular arcs in a CURVEPOLYGON
+///   or COMPOUNDCURVE instead.
+/// * `precision` and `precisionMode`: round the output coordinates, see `wktToSvg`.
+///
+/// An options object that cannot be read throws an INVALID_ARGUMENT error.
+///
 #[wasm_bindgen(js_name = svgToWkt)]
-pub fn svg_to_wkt(svg: String) -> Result<String, JsValue> {
-    let geom = match svg_to_geometry_collection(&svg) {
-        Ok(geom) => geom,
-        Err(_) => return Err(json_errors::svg_error::could_not_parse()),
-    };
+pub fn svg_to_wkt(svg: String, options: JsValue) -> Result<String, JsValue> {
+    svg_to_wkt_with_options(&svg, SvgToWktOptions::from_js(options)?)
+}
 
-    if geom.0.len() == 1 {
-        let single = geom.0[0].clone();
-        return match single {
-            Geometry::MultiPolygon { .. } => match single.into_multi_polygon().unwrap().repair() {
-                Some(wkt) => Ok(wkt.to_wkt()),
-                None => Err(json_errors::geometry_processing_error::irreparable_geom()),
-            },
-            Geometry::Polygon { .. } => match single.into_polygon().unwrap().repair() {
-                Some(wkt) => Ok(wkt.to_wkt()),
-                None => Err(json_errors::geometry_processing_error::irreparable_geom()),
-            },
-            _ => Ok(single.to_wkt()),
-        };
+fn svg_to_wkt_with_options(svg: &str, options: SvgToWktOptions) -> Result<String, JsValue> {
+    let precision = Precision::parse(options.precision, options.precision_mode.as_deref())?;
+    let read_options = SvgReadOptions {
+        strict: options.strict.unwrap_or(false),
+        tolerance: options.tolerance,
+    };
+    if options.curves.unwrap_or(false) {
+        return Ok(geometry_convert::svg_to_curve_wkt(
+            svg,
+            &read_options,
+            precision,
+        )?);
     }
-
-    Err(json_errors::svg_error::could_not_parse())
+    Ok(geometry_convert::svg_to_wkt_with_options(
+        svg,
+        &read_options,
+        precision,
+    )?)
 }
 
 /// Convert an SVG <path> d-string into a WKT representation.
-/// This function can read a <path>, <polygon>. <polyline>,
-/// <rect>, and <line>, all other SVG elements will return
-/// and error.
+///
+/// It takes the same `options` object as `svgToWkt`, where `strict` has
+/// no effect.
 ///
 #[wasm_bindgen(js_name = svgPathStringToWkt)]
-pub fn svg_path_string_to_wkt(d_string: String) -> Result<String, JsValue> {
-    svg_to_wkt(format!("<path d=\"{}\"/>", d_string))
+pub fn svg_path_string_to_wkt(d_string: String, options: JsValue) -> Result<String, JsValue> {
+    let options = SvgToWktOptions::from_js(options)?;
+    let precision = Precision::parse(options.precision, options.precision_mode.as_deref())?;
+    let read_options = SvgReadOptions {
+        tolerance: options.tolerance,
+        ..Default::default()
+    };
+    if options.curves.unwrap_or(false) {
+        return Ok(geometry_convert::svg_path_string_to_curve_wkt(
+            &d_string,
+            &read_options,
+            precision,
+        )?);
+    }
+    Ok(geometry_convert::svg_path_string_to_wkt_with_options(
+        &d_string,
+        &read_options,
+        precision,
+    )?)
 }
 
-/// Converts a WKT geometry into an SVG element.
+/// Convert every shape element of an SVG document into a WKT GEOMETRYCOLLECTION,
+/// in document order. A document without shapes gives "GEOMETRYCOLLECTION EMPTY".
 ///
-#[wasm_bindgen(js_name = wktToSvg)]
-pub fn wkt_to_svg(wkt: String) -> Result<String, JsValue> {
-    let wkt_geom: Wkt<f64> = match Wkt::from_str(&wkt) {
-        Ok(geom) => geom,
-        Err(err) => return Err(JsValue::from_str(err.to_string().as_str())),
+/// See `svgToWkt` for the `strict` and `tolerance` options.
+///
+#[wasm_bindgen(js_name = svgDocumentToWkt)]
+pub fn svg_document_to_wkt(
+    svg: String,
+    strict: Option<bool>,
+    tolerance: Option<f64>,
+) -> Result<String, JsValue> {
+    let options = SvgReadOptions {
+        strict: strict.unwrap_or(false),
+        tolerance,
     };
-    let geom = match wkt::conversion::try_into_geometry(&wkt_geom.items[0]) {
-        Ok(parsed_geom) => parsed_geom,
-        Err(err) => return Err(JsValue::from_str(err.to_string().as_str())),
+    Ok(geometry_convert::svg_document_to_wkt(&svg, &options)?)
+}
+
+/// Convert every shape element of an SVG document into WKT, and return them
+/// as a JSON list with the attributes that identify each element, e.g.,
+/// `[{"class":null,"data":{"artefact":"12"},"element":"path","id":"roi-1","wkt":"POLYGON(...)"}]`.
+///
+/// `id` and `class` are null when the element does not have them, and `data`
+/// holds the `data-*` attributes by their name without the prefix.
+/// See `svgToWkt` for the `strict` and `tolerance` options.
+///
+#[wasm_bindgen(js_name = svgDocumentToWktElements)]
+pub fn svg_document_to_wkt_elements(
+    svg: String,
+    strict: Option<bool>,
+    tolerance: Option<f64>,
+) -> Result<String, JsValue> {
+    let options = SvgReadOptions {
+        strict: strict.unwrap_or(false),
+        tolerance,
     };
+    Ok(geometry_convert::svg_document_to_wkt_elements(
+        &svg, &options,
+    )?)
+}
 
-    Ok(geom.to_svg())
+/// Converts a WKT geometry into an SVG element.
+/// Every member of a GEOMETRYCOLLECTION, or of a WKT string holding several
+/// geometries, becomes its own element on a separate line. Empty geometries
+/// and points give no element.
+///
+/// By default the coordinates are written with full precision. `precision`
+/// rounds them to that many decimal places, or to that many significant digits
+/// when `precisionMode` is "significant". The "grid" mode snaps them to whole
+/// numbers (the pixel grid) instead. In every mode, vertices that end up on the
+/// same position are merged, and rings that collapse are removed.
+///
+#[wasm_bindgen(js_name = wktToSvg)]
+pub fn wkt_to_svg(
+    wkt: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    Ok(geometry_convert::wkt_to_svg_with_precision(
+        &wkt,
+        Precision::parse(precision, precision_mode.as_deref())?,
+    )?)
 }
 
 /// Converts a WKT geometry into an SVG <path> d-string.
 ///
+/// See `wktToSvg` for `precision` and `precisionMode`.
+///
 #[wasm_bindgen(js_name = wktToSvgPathString)]
-pub fn wkt_to_svg_path_string(wkt: String) -> Result<String, JsValue> {
-    let wkt_geom: Wkt<f64> = match Wkt::from_str(&wkt) {
-        Ok(geom) => geom,
-        Err(err) => return Err(JsValue::from_str(err.to_string().as_str())),
-    };
-    let geom = match wkt::conversion::try_into_geometry(&wkt_geom.items[0]) {
-        Ok(parsed_geom) => parsed_geom,
-        Err(err) => return Err(JsValue::from_str(err.to_string().as_str())),
-    };
+pub fn wkt_to_svg_path_string(
+    wkt: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    Ok(geometry_convert::wkt_to_svg_path_string_with_precision(
+        &wkt,
+        Precision::parse(precision, precision_mode.as_deref())?,
+    )?)
+}
+
+/// Converts a GeoJSON geometry, Feature, or FeatureCollection into WKT.
+/// A FeatureCollection is written as a GEOMETRYCOLLECTION.
+///
+#[wasm_bindgen(js_name = geojsonToWkt)]
+pub fn geojson_to_wkt(geojson: String) -> Result<String, JsValue> {
+    Ok(geometry_geojson::geojson_to_wkt(&geojson)?)
+}
 
-    Ok(geom.to_svg_string())
+/// Converts a WKT geometry into a GeoJSON geometry object.
+///
+#[wasm_bindgen(js_name = wktToGeojson)]
+pub fn wkt_to_geojson(wkt: String) -> Result<String, JsValue> {
+    Ok(geometry_geojson::wkt_to_geojson(&wkt)?)
+}
+
+/// Converts a GeoJSON geometry, Feature, or FeatureCollection into SVG.
+/// Each feature becomes a <path> element that carries its `id` and
+/// its `properties` as `data-*` attributes.
+///
+#[wasm_bindgen(js_name = geojsonToSvg)]
+pub fn geojson_to_svg(geojson: String) -> Result<String, JsValue> {
+    Ok(geometry_geojson::geojson_to_svg(&geojson)?)
+}
+
+/// Converts an SVG element into a GeoJSON Feature.
+/// The `id` and `data-*` attributes of the element are kept as
+/// the `id` and `properties` of the feature.
+///
+#[wasm_bindgen(js_name = svgToGeojson)]
+pub fn svg_to_geojson(svg: String) -> Result<String, JsValue> {
+    Ok(geometry_geojson::svg_to_geojson(&svg)?)
+}
+
+/// Converts hex encoded WKB or EWKB into WKT.
+/// Both byte orders and Z/M coordinates are supported (Z and M values are dropped),
+/// and any embedded SRID is ignored.
+///
+#[wasm_bindgen(js_name = wkbToWkt)]
+pub fn wkb_to_wkt(wkb: String) -> Result<String, JsValue> {
+    Ok(geometry_wkb::wkb_to_wkt(&wkb)?)
+}
+
+/// Converts a WKT geometry into hex encoded WKB.
+/// When an `srid` is given, the result is EWKB with the SRID embedded.
+///
+#[wasm_bindgen(js_name = wktToWkb)]
+pub fn wkt_to_wkb(wkt: String, srid: Option<u32>) -> Result<String, JsValue> {
+    Ok(geometry_wkb::wkt_to_wkb(&wkt, srid)?)
+}
+
+/// Converts hex encoded WKB or EWKB into an SVG element.
+///
+#[wasm_bindgen(js_name = wkbToSvg)]
+pub fn wkb_to_svg(wkb: String) -> Result<String, JsValue> {
+    Ok(geometry_wkb::wkb_to_svg(&wkb)?)
+}
+
+/// Converts an SVG element into hex encoded WKB.
+/// When an `srid` is given, the result is EWKB with the SRID embedded.
+///
+#[wasm_bindgen(js_name = svgToWkb)]
+pub fn svg_to_wkb(svg: String, srid: Option<u32>) -> Result<String, JsValue> {
+    Ok(geometry_wkb::svg_to_wkb(&svg, srid)?)
+}
+
+/// Reads the SRID embedded in hex encoded EWKB, if there is one.
+///
+#[wasm_bindgen(js_name = wkbSrid)]
+pub fn wkb_srid(wkb: String) -> Result<Option<u32>, JsValue> {
+    Ok(geometry_wkb::wkb_srid(&wkb)?)
+}
+
+/// Converts WKB or EWKB bytes (a `Uint8Array`) into WKT (see `wkbToWkt`).
+///
+#[wasm_bindgen(js_name = wkbBytesToWkt)]
+pub fn wkb_bytes_to_wkt(wkb: Vec<u8>) -> Result<String, JsValue> {
+    Ok(geometry_wkb::wkb_bytes_to_wkt(&wkb)?)
+}
+
+/// Converts a WKT geometry into WKB bytes (a `Uint8Array`).
+/// When an `srid` is given, the result is EWKB with the SRID embedded.
+///
+#[wasm_bindgen(js_name = wktToWkbBytes)]
+pub fn wkt_to_wkb_bytes(wkt: String, srid: Option<u32>) -> Result<Vec<u8>, JsValue> {
+    Ok(geometry_wkb::wkt_to_wkb_bytes(&wkt, srid)?)
+}
+
+/// Converts WKB or EWKB bytes (a `Uint8Array`) into an SVG element.
+///
+#[wasm_bindgen(js_name = wkbBytesToSvg)]
+pub fn wkb_bytes_to_svg(wkb: Vec<u8>) -> Result<String, JsValue> {
+    Ok(geometry_wkb::wkb_bytes_to_svg(&wkb)?)
+}
+
+/// Converts an SVG element into WKB bytes (a `Uint8Array`).
+/// When an `srid` is given, the result is EWKB with the SRID embedded.
+///
+#[wasm_bindgen(js_name = svgToWkbBytes)]
+pub fn svg_to_wkb_bytes(svg: String, srid: Option<u32>) -> Result<Vec<u8>, JsValue> {
+    Ok(geometry_wkb::svg_to_wkb_bytes(&svg, srid)?)
+}
+
+/// Reads the SRID embedded in EWKB bytes (a `Uint8Array`), if there is one.
+///
+#[wasm_bindgen(js_name = wkbBytesSrid)]
+pub fn wkb_bytes_srid(wkb: Vec<u8>) -> Result<Option<u32>, JsValue> {
+    Ok(geometry_wkb::wkb_bytes_srid(&wkb)?)
 }
 
-/** Tests */
+/* Tests */
 
 #[cfg(test)]
 mod tests {
@@ -89,7 +301,7 @@ mod tests {
     #[test]
     fn can_convert_svg_to_wkt() {
         let svg = r#"<path d="M0 0L10 0L10 10L0 10L0 0M3 3L6 3L6 6L3 6L3 3"/>"#;
-        let wkt = svg_to_wkt(svg.into());
+        let wkt = svg_to_wkt_with_options(svg, SvgToWktOptions::default());
         assert_eq!(
             "POLYGON((0 0,10 0,10 10,0 10,0 0),(3 3,3 6,6 6,6 3,3 3))",
             wkt.ok().unwrap()
@@ -99,7 +311,7 @@ mod tests {
     #[test]
     fn can_convert_wkt_to_svg() {
         let wkt = "POLYGON((0 0,0 10,10 10,10 0,0 0),(3 3,6 3,6 6,3 6,3 3))";
-        let svg = wkt_to_svg(wkt.into());
+        let svg = wkt_to_svg(wkt.into(), None, None);
         assert_eq!(
             r#"<path d="M0 0L0 10L10 10L10 0L0 0M3 3L6 3L6 6L3 6L3 3"/>"#,
             svg.ok().unwrap()
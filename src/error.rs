@@ -0,0 +1,133 @@
+use std::fmt;
+
+/// The errors that can be raised by the geometry tools.
+///
+/// Every error has a stable `code` (see [`GeometryToolsError::code`]), and it keeps
+/// the offending input together with any details needed to explain the failure.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeometryToolsError {
+    /// The WKT string names a geometry type that is unknown or unsupported
+    InvalidWktType { input: String },
+    /// The WKT string could not be parsed
+    WktParse { input: String, reason: String },
+    /// The geometry breaks one or more of the validation rules
+    InvalidGeometry {
+        input: String,
+        failed_rules: Vec<String>,
+    },
+    /// The two geometries cannot be used together in a boolean operation
+    InvalidBooleanGeometryPair {
+        geometry1: String,
+        geometry2: String,
+    },
+    /// One of the geometries submitted for a union cannot be used
+    InvalidUnionInput {
+        index: usize,
+        input: String,
+        reason: String,
+    },
+    /// The geometry is invalid and could not be repaired
+    IrreparableGeometry { input: String },
+    /// The SVG element or d-string could not be parsed
+    SvgParse { input: String, reason: String },
+    /// The GeoJSON string could not be parsed
+    GeoJsonParse { input: String, reason: String },
+    /// The WKB could not be parsed
+    WkbParse { input: String, reason: String },
+    /// An option passed alongside the geometry has an unusable value
+    InvalidArgument { argument: String, reason: String },
+}
+
+impl GeometryToolsError {
+    /// A stable identifier for the kind of error
+    pub fn code(&self) -> &'static str {
+        match self {
+            GeometryToolsError::InvalidWktType { .. } => "INVALID_WKT_TYPE",
+            GeometryToolsError::WktParse { .. } => "WKT_PARSE_ERROR",
+            GeometryToolsError::InvalidGeometry { .. } => "INVALID_GEOMETRY",
+            GeometryToolsError::InvalidBooleanGeometryPair { .. } => {
+                "INVALID_BOOLEAN_GEOMETRY_PAIR"
+            }
+            GeometryToolsError::InvalidUnionInput { .. } => "INVALID_UNION_INPUT",
+            GeometryToolsError::IrreparableGeometry { .. } => "IRREPARABLE_GEOMETRY",
+            GeometryToolsError::SvgParse { .. } => "SVG_PARSE_ERROR",
+            GeometryToolsError::GeoJsonParse { .. } => "GEOJSON_PARSE_ERROR",
+            GeometryToolsError::WkbParse { .. } => "WKB_PARSE_ERROR",
+            GeometryToolsError::InvalidArgument { .. } => "INVALID_ARGUMENT",
+        }
+    }
+
+    /// A human readable description of the error
+    pub fn message(&self) -> String {
+        match self {
+            GeometryToolsError::InvalidWktType { .. } => {
+                "The wkt geometry type is invalid or unsupported".into()
+            }
+            GeometryToolsError::WktParse { .. } => {
+                "The wkt geometry could not be successfully parsed".into()
+            }
+            GeometryToolsError::InvalidGeometry { .. } => {
+                "The submitted shape resulted in an invalid geometry".into()
+            }
+            GeometryToolsError::InvalidBooleanGeometryPair { .. } => {
+                "Cannot perform a boolean operation on the submitted geometries".into()
+            }
+            GeometryToolsError::InvalidUnionInput { index, .. } => {
+                format!("The geometry at index {} cannot be used in a union", index)
+            }
+            GeometryToolsError::IrreparableGeometry { .. } => {
+                "The attempt to repair the geometry failed".into()
+            }
+            GeometryToolsError::SvgParse { .. } => {
+                "The submitted SVG element/d-string could not be parsed".into()
+            }
+            GeometryToolsError::GeoJsonParse { .. } => {
+                "The GeoJSON could not be successfully parsed".into()
+            }
+            GeometryToolsError::WkbParse { .. } => {
+                "The WKB could not be successfully parsed".into()
+            }
+            GeometryToolsError::InvalidArgument { argument, .. } => {
+                format!("The value of the {} argument cannot be used", argument)
+            }
+        }
+    }
+
+    /// The input that caused the error, if there was a single one
+    pub fn input(&self) -> Option<&str> {
+        match self {
+            GeometryToolsError::InvalidWktType { input }
+            | GeometryToolsError::WktParse { input, .. }
+            | GeometryToolsError::InvalidGeometry { input, .. }
+            | GeometryToolsError::InvalidUnionInput { input, .. }
+            | GeometryToolsError::IrreparableGeometry { input }
+            | GeometryToolsError::SvgParse { input, .. }
+            | GeometryToolsError::GeoJsonParse { input, .. }
+            | GeometryToolsError::WkbParse { input, .. } => Some(input),
+            GeometryToolsError::InvalidBooleanGeometryPair { .. }
+            | GeometryToolsError::InvalidArgument { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for GeometryToolsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GeometryToolsError::WktParse { reason, .. }
+            | GeometryToolsError::InvalidUnionInput { reason, .. }
+            | GeometryToolsError::SvgParse { reason, .. }
+            | GeometryToolsError::GeoJsonParse { reason, .. }
+            | GeometryToolsError::WkbParse { reason, .. }
+            | GeometryToolsError::InvalidArgument { reason, .. } => {
+                write!(f, "{}: {}", self.message(), reason)
+            }
+            GeometryToolsError::InvalidGeometry { failed_rules, .. } => {
+                write!(f, "{}: {}", self.message(), failed_rules.join(", "))
+            }
+            _ => write!(f, "{}", self.message()),
+        }
+    }
+}
+
+impl std::error::Error for GeometryToolsError {}
@@ -2,154 +2,657 @@ extern crate geo_booleanop;
 extern crate geo_types;
 extern crate wkt;
 
-use self::geo_types::Polygon;
-use crate::information::type_of;
+use self::geo_types::{LineString, MultiPolygon, Point, Polygon};
+use crate::error::GeometryToolsError;
+use crate::geometry_convert::{
+    geometry_to_wkt, svg_to_geometry, svg_to_geometry_or_collection, wkt_to_geometry,
+};
+use crate::geometry_geojson::geojson_to_geometry;
+use crate::geometry_information::{polygon_is_empty, wkt_geometry_type, WktGeometryKind};
+use crate::geometry_precision::{geometry_with_precision, multi_polygon_with_precision, Precision};
 use crate::json_errors;
-use geo_booleanop::boolean::BooleanOp;
+use geo::algorithm::contains::Contains;
+use geo_booleanop::boolean::{BooleanOp, Operation};
 use geo_repair_polygon::join::Join;
 use geo_types::Geometry;
 use geo_validator::Validate;
 use geo_wkt_writer::ToWkt;
-use wasm_bindgen::JsValue;
-use wkt::Wkt;
 
 /// Apply the operation function to the two geometries if possible
 ///
+/// The result keeps every disconnected piece, so it may be a POLYGON,
+/// a MULTIPOLYGON, or MULTIPOLYGON EMPTY.
+///
 pub fn wkt_boolean(
     geom1: &str,
     geom2: &str,
     operation: geo_booleanop::boolean::Operation,
-) -> Result<String, JsValue> {
-    let wkt_geom1: Wkt<f64> = match Wkt::from_str(geom1) {
-        Ok(g1) => g1,
-        Err(err) => {
-            return Err(json_errors::geometry_processing_error::invalid_geom(
-                &err.to_string(),
-            ))
-        }
-    };
+) -> Result<String, GeometryToolsError> {
+    let (geo_geom1, geo_geom2) = parse_wkt_pair(geom1, geom2)?;
 
-    let geo_geom1 = match wkt::conversion::try_into_geometry(&wkt_geom1.items[0]) {
-        Ok(g1) => g1,
-        Err(err) => {
-            return Err(json_errors::geometry_processing_error::invalid_geom(
-                &err.to_string(),
-            ))
-        }
+    match geometry_boolean(&geo_geom1, &geo_geom2, operation) {
+        Ok(g) => Ok(g.to_wkt()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Apply the operation function to the two geometries if possible and
+/// join the result into a single POLYGON
+///
+pub fn wkt_boolean_joined(
+    geom1: &str,
+    geom2: &str,
+    operation: geo_booleanop::boolean::Operation,
+) -> Result<String, GeometryToolsError> {
+    let (geo_geom1, geo_geom2) = parse_wkt_pair(geom1, geom2)?;
+
+    match geometry_boolean_joined(&geo_geom1, &geo_geom2, operation) {
+        Ok(g) => Ok(g.to_wkt()),
+        Err(e) => Err(e),
+    }
+}
+
+fn parse_wkt_pair(
+    geom1: &str,
+    geom2: &str,
+) -> Result<(Geometry<f64>, Geometry<f64>), GeometryToolsError> {
+    Ok((wkt_to_geometry(geom1)?, wkt_to_geometry(geom2)?))
+}
+
+/// Perform a boolean operation on the submitted WKT geometries
+///
+/// This performs a quick test first to determine if the submitted geometry types are
+/// suitable for the boolean operation (only MULTIPOLYGON, POLYGON, and a
+/// GEOMETRYCOLLECTION of them are supported). An empty operand is allowed.
+/// When `join` is set, the result is always joined into a single POLYGON.
+pub fn wkt_polygon_boolean(
+    geom1: &str,
+    geom2: &str,
+    op: geo_booleanop::boolean::Operation,
+    join: bool,
+) -> Result<String, GeometryToolsError> {
+    wkt_polygon_boolean_with_precision(geom1, geom2, op, join, Precision::Full)
+}
+
+/// Perform a boolean operation on the submitted WKT geometries (see `wkt_polygon_boolean`)
+/// and round the coordinates of the result to the given precision.
+///
+pub fn wkt_polygon_boolean_with_precision(
+    geom1: &str,
+    geom2: &str,
+    op: geo_booleanop::boolean::Operation,
+    join: bool,
+    precision: Precision,
+) -> Result<String, GeometryToolsError> {
+    // Grab the stated type of each input
+    let geom1_type = wkt_geometry_type(geom1)?;
+    let geom2_type = wkt_geometry_type(geom2)?;
+
+    let usable =
+        |kind: WktGeometryKind| kind.is_polygonal() || kind == WktGeometryKind::GeometryCollection;
+    if !usable(geom1_type.kind) || !usable(geom2_type.kind) {
+        return Err(
+            json_errors::geometry_processing_error::invalid_boolean_geom_pair(geom1, geom2),
+        );
+    }
+
+    let (geo_geom1, geo_geom2) = parse_wkt_pair(geom1, geom2)?;
+    let result = if join {
+        geometry_boolean_joined(&geo_geom1, &geo_geom2, op)?.into()
+    } else {
+        geometry_boolean(&geo_geom1, &geo_geom2, op)?
     };
+    Ok(geometry_with_precision(&result, precision).to_wkt())
+}
 
-    let wkt_geom2: Wkt<f64> = match Wkt::from_str(geom2) {
-        Ok(g2) => g2,
-        Err(err) => {
-            return Err(json_errors::geometry_processing_error::invalid_geom(
-                &err.to_string(),
-            ))
+/// Perform a boolean operation on the submitted SVG geometries
+///
+/// Only polygons and multi polygons are supported. When `join` is set, the
+/// result is always joined into a single Polygon.
+pub fn svg_polygon_boolean(
+    geom1: &str,
+    geom2: &str,
+    op: geo_booleanop::boolean::Operation,
+    join: bool,
+) -> Result<Geometry<f64>, GeometryToolsError> {
+    let g1 = svg_to_geometry(geom1)?;
+    let g2 = svg_to_geometry(geom2)?;
+
+    if join {
+        return Ok(geometry_boolean_joined(&g1, &g2, op)?.into());
+    }
+    geometry_boolean(&g1, &g2, op)
+}
+
+/// Perform a boolean operation on the submitted GeoJSON geometries
+///
+/// Each input may be a geometry or a Feature, but only polygons and multi
+/// polygons are supported. When `join` is set, the result is always joined
+/// into a single Polygon.
+pub fn geojson_polygon_boolean(
+    geom1: &str,
+    geom2: &str,
+    op: geo_booleanop::boolean::Operation,
+    join: bool,
+) -> Result<Geometry<f64>, GeometryToolsError> {
+    let g1 = geojson_to_geometry(geom1)?;
+    let g2 = geojson_to_geometry(geom2)?;
+
+    if join {
+        return Ok(geometry_boolean_joined(&g1, &g2, op)?.into());
+    }
+    geometry_boolean(&g1, &g2, op)
+}
+
+/// Apply the operation function to the two geometries.
+///
+/// A result consisting of a single polygon is returned as a Polygon, anything
+/// else (including an empty result) is returned as a MultiPolygon.
+///
+pub fn geometry_boolean(
+    geo_geom1: &Geometry<f64>,
+    geo_geom2: &Geometry<f64>,
+    operation: geo_booleanop::boolean::Operation,
+) -> Result<Geometry<f64>, GeometryToolsError> {
+    let mut result = polygonal_boolean(geo_geom1, geo_geom2, operation)?;
+    if result.0.len() == 1 {
+        return Ok(result.0.remove(0).into());
+    }
+    Ok(result.into())
+}
+
+/// Apply the operation function to the two geometries and join all the
+/// resulting polygons into a single Polygon.
+///
+pub fn geometry_boolean_joined(
+    geo_geom1: &Geometry<f64>,
+    geo_geom2: &Geometry<f64>,
+    operation: geo_booleanop::boolean::Operation,
+) -> Result<Polygon<f64>, GeometryToolsError> {
+    Ok(polygonal_boolean(geo_geom1, geo_geom2, operation)?.join())
+}
+
+fn polygonal_boolean(
+    geo_geom1: &Geometry<f64>,
+    geo_geom2: &Geometry<f64>,
+    operation: geo_booleanop::boolean::Operation,
+) -> Result<MultiPolygon<f64>, GeometryToolsError> {
+    let (g1, g2) = match (polygonal_operand(geo_geom1)?, polygonal_operand(geo_geom2)?) {
+        (Some(g1), Some(g2)) => (g1, g2),
+        _ => {
+            return Err(
+                json_errors::geometry_processing_error::invalid_boolean_geom_pair(
+                    &geometry_to_wkt(geo_geom1),
+                    &geometry_to_wkt(geo_geom2),
+                ),
+            )
         }
     };
-    let geo_geom2 = match wkt::conversion::try_into_geometry(&wkt_geom2.items[0]) {
-        Ok(g2) => g2,
-        Err(err) => {
-            return Err(json_errors::geometry_processing_error::invalid_geom(
-                &err.to_string(),
-            ))
+
+    Ok(apply_operation(g1, g2, operation))
+}
+
+fn apply_operation(
+    g1: MultiPolygon<f64>,
+    g2: MultiPolygon<f64>,
+    operation: geo_booleanop::boolean::Operation,
+) -> MultiPolygon<f64> {
+    // An empty operand decides the result by itself
+    match (g1.0.is_empty(), g2.0.is_empty(), operation) {
+        (false, false, _) => g1.boolean(&g2, operation),
+        (true, _, Operation::Union) | (true, _, Operation::Xor) => g2,
+        (true, _, _) | (false, true, Operation::Intersection) => MultiPolygon(vec![]),
+        (false, true, _) => g1,
+    }
+}
+
+/// Reads a boolean operand as a validated MultiPolygon without empty members.
+///
+/// The polygons of a GEOMETRYCOLLECTION are unioned into one operand. It returns
+/// None when the geometry is not polygonal.
+fn polygonal_operand(
+    geom: &Geometry<f64>,
+) -> Result<Option<MultiPolygon<f64>>, GeometryToolsError> {
+    let mp = match geom {
+        Geometry::Polygon(poly) => MultiPolygon(vec![poly.clone()]),
+        Geometry::MultiPolygon(mp) => mp.clone(),
+        Geometry::GeometryCollection(geoms) => {
+            let mut union = MultiPolygon(vec![]);
+            for member in geoms.0.iter() {
+                let mp = match polygonal_operand(member)? {
+                    Some(mp) => mp,
+                    None => return Ok(None),
+                };
+                union = match (union.0.is_empty(), mp.0.is_empty()) {
+                    (true, _) => mp,
+                    (false, true) => union,
+                    (false, false) => union.union(&mp),
+                };
+            }
+            return Ok(Some(union));
         }
+        _ => return Ok(None),
     };
 
-    match geometry_boolean(&geo_geom1, &geo_geom2, operation) {
-        Ok(g) => Ok(g.to_wkt()),
-        Err(e) => Err(e),
+    let mp = MultiPolygon(
+        mp.0.into_iter()
+            .filter(|poly| !polygon_is_empty(poly))
+            .collect(),
+    );
+    if !mp.validate() {
+        return Err(json_errors::wkt_errors::invalid_geometry(
+            &geometry_to_wkt(geom),
+            &mp.validate_detailed(),
+        ));
     }
+    Ok(Some(mp))
 }
 
-pub fn geometry_boolean(
+/// The most times grid cells are cut away and a geometry is snapped again to make it valid
+const MAX_SNAP_ROUNDS: usize = 8;
+
+/// Apply the operation function to the two WKT geometries with snap rounding
+/// (see `geometry_boolean_snapped`).
+///
+pub fn wkt_polygon_boolean_snapped(
+    geom1: &str,
+    geom2: &str,
+    op: geo_booleanop::boolean::Operation,
+    grid_size: f64,
+) -> Result<String, GeometryToolsError> {
+    let (geo_geom1, geo_geom2) = parse_wkt_pair(geom1, geom2)?;
+    Ok(geometry_boolean_snapped(&geo_geom1, &geo_geom2, op, grid_size)?.to_wkt())
+}
+
+/// Apply the operation function to the two SVG geometries with snap rounding
+/// (see `geometry_boolean_snapped`).
+///
+pub fn svg_polygon_boolean_snapped(
+    geom1: &str,
+    geom2: &str,
+    op: geo_booleanop::boolean::Operation,
+    grid_size: f64,
+) -> Result<Geometry<f64>, GeometryToolsError> {
+    geometry_boolean_snapped(
+        &svg_to_geometry_or_collection(geom1)?,
+        &svg_to_geometry_or_collection(geom2)?,
+        op,
+        grid_size,
+    )
+}
+
+/// Apply the operation function to the two geometries with every coordinate
+/// snap rounded to a grid, e.g., a grid size of 1 keeps pixel masks on the
+/// integer pixel grid.
+///
+/// Both inputs and the result are snapped to the grid. Vertices that end up on
+/// the same position are merged, vertices in the middle of a straight edge or
+/// at the tip of a zero width spike are removed, and rings that collapse are
+/// dropped. Where snapping leaves rings touching or crossing each other or
+/// themselves, the grid cells around that point are cut away before it is
+/// snapped again, with a larger cut each time the rings still touch. When
+/// that does not give a valid geometry, an INVALID_GEOMETRY error is returned.
+///
+/// The result is therefore always valid and already on the grid, so it can be
+/// used in the next operation without drifting, and snapping it again does not
+/// change it. A result consisting of a single polygon is returned as a Polygon,
+/// anything else is returned as a MultiPolygon.
+///
+pub fn geometry_boolean_snapped(
     geo_geom1: &Geometry<f64>,
     geo_geom2: &Geometry<f64>,
     operation: geo_booleanop::boolean::Operation,
-) -> Result<Polygon<f64>, JsValue> {
-    match geo_geom1 {
-        Geometry::MultiPolygon { .. } => {
-            let g1 = geo_geom1.clone().into_multi_polygon().unwrap();
-            if !g1.validate() {
-                return Err(json_errors::wkt_errors::invalid_geometry(
-                    &geo_geom1.to_wkt(),
-                ));
-            }
-            match geo_geom2 {
-                Geometry::MultiPolygon { .. } => {
-                    let g2 = geo_geom2.clone().into_multi_polygon().unwrap();
-                    if !g2.validate() {
-                        return Err(json_errors::wkt_errors::invalid_geometry(
-                            &geo_geom2.to_wkt(),
-                        ));
-                    }
-                    Ok(g1.boolean(&g2, operation).join())
-                }
-                Geometry::Polygon { .. } => {
-                    let g2 = geo_geom2.clone().into_polygon().unwrap();
-                    if !g2.validate() {
-                        return Err(json_errors::wkt_errors::invalid_geometry(
-                            &geo_geom2.to_wkt(),
-                        ));
-                    }
-                    Ok(g1.boolean(&g2, operation).join())
-                }
-                _ => Err(
-                    json_errors::geometry_processing_error::invalid_boolean_geom_pair(
-                        &type_of(&geo_geom1),
-                        &type_of(&geo_geom2),
-                    ),
+    grid_size: f64,
+) -> Result<Geometry<f64>, GeometryToolsError> {
+    if !grid_size.is_finite() || grid_size <= 0. {
+        return Err(json_errors::argument_error::invalid_argument(
+            "gridSize",
+            "The grid size must be a positive number",
+        ));
+    }
+    let (g1, g2) = match (
+        snapped_operand(geo_geom1, grid_size)?,
+        snapped_operand(geo_geom2, grid_size)?,
+    ) {
+        (Some(g1), Some(g2)) => (g1, g2),
+        _ => {
+            return Err(
+                json_errors::geometry_processing_error::invalid_boolean_geom_pair(
+                    &geometry_to_wkt(geo_geom1),
+                    &geometry_to_wkt(geo_geom2),
                 ),
+            )
+        }
+    };
+
+    let result = apply_operation(g1, g2, operation);
+    let mut result = snap_polygons(&result, grid_size, &result.clone().into())?;
+    if result.0.len() == 1 {
+        return Ok(result.0.remove(0).into());
+    }
+    Ok(result.into())
+}
+
+/// Reads a boolean operand as a valid MultiPolygon on the grid, or None when
+/// the geometry is not polygonal. The polygons of a GEOMETRYCOLLECTION are
+/// unioned into one operand.
+fn snapped_operand(
+    geom: &Geometry<f64>,
+    grid_size: f64,
+) -> Result<Option<MultiPolygon<f64>>, GeometryToolsError> {
+    let mp = match geom {
+        Geometry::Polygon(poly) => MultiPolygon(vec![poly.clone()]),
+        Geometry::MultiPolygon(mp) => mp.clone(),
+        Geometry::GeometryCollection(geoms) => {
+            let mut union = MultiPolygon(vec![]);
+            for member in geoms.0.iter() {
+                match snapped_operand(member, grid_size)? {
+                    Some(mp) => union = apply_operation(union, mp, Operation::Union),
+                    None => return Ok(None),
+                };
             }
+            union
         }
-        Geometry::Polygon { .. } => {
-            let g1 = geo_geom1.clone().into_polygon().unwrap();
-            if !g1.validate() {
-                return Err(json_errors::wkt_errors::invalid_geometry(
-                    &geo_geom1.to_wkt(),
-                ));
+        _ => return Ok(None),
+    };
+    Ok(Some(snap_polygons(&mp, grid_size, geom)?))
+}
+
+/// Snaps the polygons to the grid, cutting away grid cells until they are valid.
+/// `source` is the geometry reported when that does not succeed.
+fn snap_polygons(
+    polys: &MultiPolygon<f64>,
+    grid_size: f64,
+    source: &Geometry<f64>,
+) -> Result<MultiPolygon<f64>, GeometryToolsError> {
+    let mut current = polys.clone();
+    let mut cut_away = MultiPolygon(vec![]);
+    for round in 0..MAX_SNAP_ROUNDS {
+        let snapped = remove_straight_vertices(
+            &multi_polygon_with_precision(&reassign_holes(&current), Precision::Grid(grid_size)),
+            grid_size,
+        );
+        let details = snapped.validate_detailed();
+        if details.valid {
+            return Ok(snapped);
+        }
+
+        // Rings that touch or cross each other or themselves are separated by cutting
+        // away the grid cells around that point. The cells are always cut from the
+        // unsnapped result, as the sweep line cannot handle invalid input.
+        let mut touching = details.ring_intersects_other_ring;
+        touching.extend(details.self_intersections);
+        touching.extend(details.point_touching_line);
+        touching.extend(details.repeated_points);
+        touching.retain(|point| point.x.is_finite() && point.y.is_finite());
+        if touching.is_empty() {
+            break;
+        }
+        let size = grid_size * (2f64.powi(round as i32) - 0.5);
+        touching.sort_by(|a, b| (a.x, a.y).partial_cmp(&(b.x, b.y)).unwrap());
+        touching.dedup();
+        // The cells overlap, so they are added one at a time
+        cut_away = touching.iter().fold(cut_away, |cut_away, point| {
+            cut_away.union(&Polygon::new(
+                LineString::from(vec![
+                    (point.x - size, point.y - size),
+                    (point.x + size, point.y - size),
+                    (point.x + size, point.y + size),
+                    (point.x - size, point.y + size),
+                    (point.x - size, point.y - size),
+                ]),
+                vec![],
+            ))
+        });
+        current = polys.difference(&cut_away);
+    }
+    Err(json_errors::wkt_errors::invalid_geometry(
+        &geometry_to_wkt(source),
+        &polys.validate_detailed(),
+    ))
+}
+
+/// Removes the vertices in the middle of a straight edge and at the tip of
+/// a zero width spike, and drops the rings that collapse.
+fn remove_straight_vertices(polys: &MultiPolygon<f64>, grid_size: f64) -> MultiPolygon<f64> {
+    // Distinct grid points that are not on a line span at least one grid cell,
+    // so anything well below that is rounding noise
+    let threshold = grid_size * grid_size / 2.;
+    let clean = |ring: &LineString<f64>| -> Option<LineString<f64>> {
+        let mut coords = ring.0.clone();
+        coords.dedup();
+        if coords.len() > 1 && coords.first() == coords.last() {
+            coords.pop();
+        }
+        let mut index = 0;
+        while coords.len() >= 3 && index < coords.len() {
+            let count = coords.len();
+            let (prev, cur, next) = (
+                coords[(index + count - 1) % count],
+                coords[index],
+                coords[(index + 1) % count],
+            );
+            let cross = (cur.x - prev.x) * (next.y - prev.y) - (cur.y - prev.y) * (next.x - prev.x);
+            if cross.abs() < threshold || prev == next {
+                coords.remove(index);
+                coords.dedup();
+                index = index.saturating_sub(1);
+            } else {
+                index += 1;
             }
-            match geo_geom2 {
-                Geometry::MultiPolygon { .. } => {
-                    let g2 = geo_geom2.clone().into_multi_polygon().unwrap();
-                    if !g2.validate() {
-                        return Err(json_errors::wkt_errors::invalid_geometry(
-                            &geo_geom2.to_wkt(),
-                        ));
-                    }
-                    Ok(g1.boolean(&g2, operation).join())
-                }
-                Geometry::Polygon { .. } => {
-                    let g2 = geo_geom2.clone().into_polygon().unwrap();
-                    if !g2.validate() {
-                        return Err(json_errors::wkt_errors::invalid_geometry(
-                            &geo_geom2.to_wkt(),
-                        ));
-                    }
-                    Ok(g1.boolean(&g2, operation).join())
-                }
-                _ => Err(
-                    json_errors::geometry_processing_error::invalid_boolean_geom_pair(
-                        &type_of(&geo_geom1),
-                        &type_of(&geo_geom2),
+        }
+        if coords.len() < 3 {
+            return None;
+        }
+        coords.push(coords[0]);
+        Some(LineString(coords))
+    };
+
+    MultiPolygon(
+        polys
+            .0
+            .iter()
+            .filter_map(|poly| {
+                Some(Polygon::new(
+                    clean(poly.exterior())?,
+                    poly.interiors().iter().filter_map(clean).collect(),
+                ))
+            })
+            .collect(),
+    )
+}
+
+/// Moves every hole into the shell that contains it.
+///
+/// The sweep line can attach a ring to the wrong polygon, and a ring that no
+/// shell contains encloses filled area, so it becomes a polygon of its own.
+fn reassign_holes(polys: &MultiPolygon<f64>) -> MultiPolygon<f64> {
+    let shells: Vec<Polygon<f64>> = polys
+        .0
+        .iter()
+        .map(|poly| Polygon::new(poly.exterior().clone(), vec![]))
+        .collect();
+    // Rings do not cross, so one vertex or edge midpoint strictly inside or
+    // outside a shell settles on which side the whole ring is
+    let samples = |ring: &LineString<f64>| -> Vec<Point<f64>> {
+        ring.lines()
+            .flat_map(|line| {
+                vec![
+                    Point(line.start),
+                    Point::new(
+                        (line.start.x + line.end.x) / 2.,
+                        (line.start.y + line.end.y) / 2.,
                     ),
-                ),
+                ]
+            })
+            .collect()
+    };
+    let mut holes: Vec<Vec<LineString<f64>>> = vec![vec![]; shells.len()];
+    let mut promoted = vec![];
+    for (index, poly) in polys.0.iter().enumerate() {
+        for ring in poly.interiors() {
+            let points = samples(ring);
+            let inside = |shell: &Polygon<f64>| points.iter().any(|point| shell.contains(point));
+            let outside = points.iter().any(|point| {
+                !shells[index].contains(point) && !shells[index].exterior().contains(point)
+            });
+            if inside(&shells[index]) || !outside {
+                holes[index].push(ring.clone());
+                continue;
+            }
+            match shells.iter().position(inside) {
+                Some(owner) => holes[owner].push(ring.clone()),
+                None => promoted.push(Polygon::new(ring.clone(), vec![])),
             }
         }
-        _ => Err(
-            json_errors::geometry_processing_error::invalid_boolean_geom_pair(
-                &type_of(&geo_geom1),
-                &type_of(&geo_geom2),
-            ),
-        ),
     }
+    MultiPolygon(
+        shells
+            .into_iter()
+            .zip(holes)
+            .map(|(shell, interiors)| Polygon::new(shell.exterior().clone(), interiors))
+            .chain(promoted)
+            .collect(),
+    )
 }
 
-/** Tests */
+/// Union all of the submitted WKT geometries into a single MULTIPOLYGON
+///
+/// Any geometry that cannot be parsed, or is not a valid (MULTI)POLYGON, causes
+/// an error that names its position in the submitted list.
+///
+pub fn wkt_union_all(geoms: &[String]) -> Result<String, GeometryToolsError> {
+    wkt_union_all_with_precision(geoms, Precision::Full)
+}
+
+/// Union all of the submitted WKT geometries into a single MULTIPOLYGON (see `wkt_union_all`)
+/// and round the coordinates of the result to the given precision.
+///
+pub fn wkt_union_all_with_precision(
+    geoms: &[String],
+    precision: Precision,
+) -> Result<String, GeometryToolsError> {
+    let mut geo_geoms = vec![] as Vec<Geometry<f64>>;
+    for (index, geom) in geoms.iter().enumerate() {
+        match wkt_to_geometry(geom) {
+            Ok(g) => geo_geoms.push(g),
+            Err(err) => {
+                return Err(json_errors::geometry_processing_error::invalid_union_input(
+                    index,
+                    geom,
+                    &err.to_string(),
+                ))
+            }
+        };
+    }
+
+    Ok(geometry_with_precision(&geometry_union_all(&geo_geoms)?.into(), precision).to_wkt())
+}
+
+/// Union all of the submitted SVG geometries into a single MultiPolygon
+///
+/// Any geometry that cannot be parsed, or is not a valid polygon, causes
+/// an error that names its position in the submitted list.
+///
+pub fn svg_union_all(geoms: &[String]) -> Result<MultiPolygon<f64>, GeometryToolsError> {
+    let mut geo_geoms = vec![] as Vec<Geometry<f64>>;
+    for (index, geom) in geoms.iter().enumerate() {
+        match svg_to_geometry(geom) {
+            Ok(g) => geo_geoms.push(g),
+            Err(err) => {
+                return Err(json_errors::geometry_processing_error::invalid_union_input(
+                    index,
+                    geom,
+                    &err.to_string(),
+                ))
+            }
+        };
+    }
+
+    geometry_union_all(&geo_geoms)
+}
+
+/// Union all of the submitted GeoJSON geometries into a single MultiPolygon
+///
+/// Any geometry that cannot be parsed, or is not a valid polygon, causes
+/// an error that names its position in the submitted list.
+///
+pub fn geojson_union_all(geoms: &[String]) -> Result<MultiPolygon<f64>, GeometryToolsError> {
+    let mut geo_geoms = vec![] as Vec<Geometry<f64>>;
+    for (index, geom) in geoms.iter().enumerate() {
+        match geojson_to_geometry(geom) {
+            Ok(g) => geo_geoms.push(g),
+            Err(err) => {
+                return Err(json_errors::geometry_processing_error::invalid_union_input(
+                    index,
+                    geom,
+                    &err.to_string(),
+                ))
+            }
+        };
+    }
+
+    geometry_union_all(&geo_geoms)
+}
+
+/// Union all of the submitted geometries into a single MultiPolygon
+///
+/// The union is cascaded: neighbouring pairs are merged first and the partial
+/// results are merged again until one is left, which keeps each individual
+/// boolean operation small.
+///
+pub fn geometry_union_all(
+    geo_geoms: &[Geometry<f64>],
+) -> Result<MultiPolygon<f64>, GeometryToolsError> {
+    let mut layer = vec![] as Vec<MultiPolygon<f64>>;
+    for (index, geom) in geo_geoms.iter().enumerate() {
+        layer.push(union_input(index, geom)?);
+    }
+
+    if layer.is_empty() {
+        return Ok(MultiPolygon(vec![]));
+    }
+
+    while layer.len() > 1 {
+        layer = layer
+            .chunks(2)
+            .map(|pair| match pair {
+                [g1, g2] => g1.union(g2),
+                _ => pair[0].clone(),
+            })
+            .collect();
+    }
+
+    Ok(layer.remove(0))
+}
+
+fn union_input(
+    index: usize,
+    geom: &Geometry<f64>,
+) -> Result<MultiPolygon<f64>, GeometryToolsError> {
+    match polygonal_operand(geom) {
+        Ok(Some(mp)) => Ok(mp),
+        Ok(None) => Err(json_errors::geometry_processing_error::invalid_union_input(
+            index,
+            &geometry_to_wkt(geom),
+            "Only POLYGON and MULTIPOLYGON geometries can be used in a union",
+        )),
+        Err(GeometryToolsError::InvalidGeometry { failed_rules, .. }) => {
+            Err(json_errors::geometry_processing_error::invalid_union_input(
+                index,
+                &geometry_to_wkt(geom),
+                &failed_rules.join(", "),
+            ))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/* Tests */
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use geo_types::{LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon};
 
     #[test]
     fn can_wkt_union_polygons() {
@@ -161,6 +664,102 @@ mod tests {
         assert_eq!(expected, union.unwrap());
     }
 
+    #[test]
+    fn keeps_disconnected_difference_as_multi_polygon() {
+        let poly1 = String::from("POLYGON((0 0,10 0,10 10,0 10,0 0))");
+        let poly2 = String::from("POLYGON((4 -1,6 -1,6 11,4 11,4 -1))");
+        let difference = wkt_boolean(
+            &poly1,
+            &poly2,
+            geo_booleanop::boolean::Operation::Difference,
+        );
+        let expected = "MULTIPOLYGON(((0 0,4 0,4 10,0 10,0 0)),((6 0,10 0,10 10,6 10,6 0)))";
+        assert!(difference.is_ok());
+        assert_eq!(expected, difference.unwrap());
+    }
+
+    #[test]
+    fn returns_empty_geometry_for_disjoint_intersection() {
+        let poly1 = String::from("POLYGON((0 0,1 0,1 1,0 1,0 0))");
+        let poly2 = String::from("POLYGON((5 5,6 5,6 6,5 6,5 5))");
+        let intersection = wkt_boolean(
+            &poly1,
+            &poly2,
+            geo_booleanop::boolean::Operation::Intersection,
+        );
+        assert!(intersection.is_ok());
+        assert_eq!("MULTIPOLYGON EMPTY", intersection.unwrap());
+    }
+
+    #[test]
+    fn can_union_all_polygons() {
+        let polys = vec![
+            String::from("POLYGON((0 0,2 0,2 2,0 2,0 0))"),
+            String::from("POLYGON((1 1,3 1,3 3,1 3,1 1))"),
+            String::from("POLYGON((10 10,12 10,12 12,10 12,10 10))"),
+        ];
+        let union = wkt_union_all(&polys);
+        let expected =
+            "MULTIPOLYGON(((0 0,2 0,2 1,3 1,3 3,1 3,1 2,0 2,0 0)),((10 10,12 10,12 12,10 12,10 10)))";
+        assert!(union.is_ok());
+        assert_eq!(expected, union.unwrap());
+    }
+
+    #[test]
+    fn union_all_of_nothing_is_empty() {
+        let union = wkt_union_all(&[]);
+        assert!(union.is_ok());
+        assert_eq!("MULTIPOLYGON EMPTY", union.unwrap());
+    }
+
+    #[test]
+    fn union_all_names_invalid_input_index() {
+        let polys = vec![
+            String::from("POLYGON((0 0,2 0,2 2,0 2,0 0))"),
+            String::from("LINESTRING(0 0,1 1)"),
+        ];
+        let union = wkt_union_all(&polys);
+        match union {
+            Err(GeometryToolsError::InvalidUnionInput { index, .. }) => assert_eq!(1, index),
+            _ => panic!("expected an invalid union input error"),
+        }
+    }
+
+    #[test]
+    fn rejects_boolean_on_non_polygonal_geometry() {
+        let union = wkt_polygon_boolean(
+            "POLYGON((0 0,2 0,2 2,0 2,0 0))",
+            "LINESTRING(0 0,1 1)",
+            geo_booleanop::boolean::Operation::Union,
+            false,
+        );
+        assert!(union.is_err());
+        assert_eq!("INVALID_BOOLEAN_GEOMETRY_PAIR", union.unwrap_err().code());
+    }
+
+    #[test]
+    fn accepts_uppercase_multi_polygon_and_srid() {
+        let union = wkt_polygon_boolean(
+            "MULTIPOLYGON(((0 0,2 0,2 2,0 2,0 0)))",
+            " SRID=4326;polygon((1 0,3 0,3 2,1 2,1 0))",
+            geo_booleanop::boolean::Operation::Union,
+            false,
+        );
+        assert_eq!(
+            "POLYGON((0 0,1 0,2 0,3 0,3 2,2 2,1 2,0 2,0 0))",
+            union.unwrap()
+        );
+    }
+
+    #[test]
+    fn can_join_disconnected_union() {
+        let poly1 = String::from("POLYGON((0 0,2 0,2 2,0 2,0 0))");
+        let poly2 = String::from("POLYGON((5 5,7 5,7 7,5 7,5 5))");
+        let union = wkt_boolean_joined(&poly1, &poly2, geo_booleanop::boolean::Operation::Union);
+        assert!(union.is_ok());
+        assert!(union.unwrap().starts_with("POLYGON(("));
+    }
+
     // #[test]
     // fn can_not_wkt_union_complex_polygons() {
     //     let poly1 = String::from("POLYGON((0 0,10 0,0 10,10 10,0 0),(3 3,6 3,6 6,3 6,3 3))");
@@ -191,6 +790,165 @@ mod tests {
         assert_eq!(expected, union.unwrap());
     }
 
+    #[test]
+    fn rounds_boolean_results() {
+        let poly1 = "POLYGON((0 0,10.0000001 0,10 10,0 10,0 0))";
+        let poly2 = "POLYGON((5 5,15 5,15 15,5 15,5 5))";
+        assert_eq!(
+            "POLYGON((0 0,10 0,10 5,15 5,15 15,5 15,5 10,0 10,0 0))",
+            wkt_polygon_boolean_with_precision(
+                poly1,
+                poly2,
+                Operation::Union,
+                false,
+                Precision::IntegerGrid
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            "MULTIPOLYGON(((0 0,10 0,10 10,0 10,0 0)))",
+            wkt_union_all_with_precision(
+                &[String::from("POLYGON((0 0,10.004 0,10 10,0 10,0 0))")],
+                Precision::DecimalPlaces(2)
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn snap_rounds_to_the_grid() {
+        assert_eq!(
+            "POLYGON((0 0,10 0,10 10,0 10,0 0))",
+            wkt_polygon_boolean_snapped(
+                "POLYGON((0 0,5.2 0.1,9.9 0,10 10.3,0 9.8,0 0))",
+                "POLYGON((2 2,4 2,4 4,2 2))",
+                Operation::Union,
+                1.
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            "POLYGON((0 0,0.5 0,0.5 0.5,0 0))",
+            wkt_polygon_boolean_snapped(
+                "POLYGON((0 0,0.6 0,0.6 0.6,0 0))",
+                "POLYGON((0.4 0,1 0,1 1,0.4 0))",
+                Operation::Difference,
+                0.5
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            "INVALID_ARGUMENT",
+            wkt_polygon_boolean_snapped(
+                "POLYGON((0 0,1 0,1 1,0 0))",
+                "POLYGON((0 0,1 0,1 1,0 0))",
+                Operation::Union,
+                0.
+            )
+            .unwrap_err()
+            .code()
+        );
+    }
+
+    #[test]
+    fn keeps_repeated_snapped_operations_valid() {
+        let mut mask: Geometry<f64> =
+            wkt_to_geometry("POLYGON((0 0,40 0,40 40,0 40,0 0))").unwrap();
+        for step in 0..30 {
+            let offset = step as f64 * 1.37;
+            let brush = wkt_to_geometry(&format!(
+                "POLYGON(({x} {y},{x2} {y1},{x1} {y2},{x} {y}))",
+                x = offset % 37.,
+                y = (offset * 2.3) % 35.,
+                x1 = offset % 37. + 7.7,
+                x2 = offset % 37. + 9.4,
+                y1 = (offset * 2.3) % 35. + 0.6,
+                y2 = (offset * 2.3) % 35. + 8.1,
+            ))
+            .unwrap();
+            let op = if step % 2 == 0 {
+                Operation::Difference
+            } else {
+                Operation::Union
+            };
+            mask = geometry_boolean_snapped(&mask, &brush, op, 1.).unwrap();
+
+            let polys = match &mask {
+                Geometry::Polygon(poly) => MultiPolygon(vec![poly.clone()]),
+                Geometry::MultiPolygon(polys) => polys.clone(),
+                _ => panic!("The result should be polygonal"),
+            };
+            assert!(polys.validate(), "step {} gave {}", step, mask.to_wkt());
+            assert_eq!(
+                polys,
+                multi_polygon_with_precision(&polys, Precision::IntegerGrid)
+            );
+            assert_eq!(
+                mask,
+                geometry_boolean_snapped(&mask, &MultiPolygon(vec![]).into(), Operation::Union, 1.)
+                    .unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn handles_empty_operands() {
+        let poly = "POLYGON((0 0,1 0,1 1,0 1,0 0))";
+        for empty in [
+            "POLYGON EMPTY",
+            "MULTIPOLYGON EMPTY",
+            "GEOMETRYCOLLECTION EMPTY",
+        ]
+        .iter()
+        {
+            for (op, expected) in [
+                (Operation::Union, poly),
+                (Operation::Intersection, "MULTIPOLYGON EMPTY"),
+                (Operation::Difference, poly),
+                (Operation::Xor, poly),
+            ]
+            .iter()
+            {
+                assert_eq!(
+                    *expected,
+                    wkt_polygon_boolean(poly, empty, *op, false).unwrap()
+                );
+            }
+            assert_eq!(
+                "MULTIPOLYGON EMPTY",
+                wkt_boolean(empty, poly, Operation::Difference).unwrap()
+            );
+            assert_eq!(poly, wkt_boolean(empty, poly, Operation::Union).unwrap());
+        }
+    }
+
+    #[test]
+    fn unions_collection_members_into_one_operand() {
+        let collection =
+            "GEOMETRYCOLLECTION(POLYGON((0 0,2 0,2 2,0 2,0 0)),POLYGON((1 0,3 0,3 2,1 2,1 0)))";
+        assert_eq!(
+            "POLYGON((0 0,1 0,1 2,0 2,0 0))",
+            wkt_polygon_boolean(
+                collection,
+                "POLYGON((1 -1,4 -1,4 3,1 3,1 -1))",
+                Operation::Difference,
+                false
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            "INVALID_BOOLEAN_GEOMETRY_PAIR",
+            wkt_polygon_boolean(
+                "GEOMETRYCOLLECTION(POINT(0 0))",
+                "POLYGON((0 0,1 0,1 1,0 0))",
+                Operation::Union,
+                false
+            )
+            .unwrap_err()
+            .code()
+        );
+    }
+
     // #[test]
     // fn errors_on_malformed_wkt() {
     //     let poly1 = String::from("POLYGON((0 0,10 0,10 10,0 10),2 2,6 2,6 6,2 6,2 2))");
@@ -0,0 +1,721 @@
+mod overlay;
+
+use crate::error::GeometryToolsError;
+use crate::geometry_convert::{
+    geometry_to_svg, geometry_to_wkt, svg_to_geometry_or_collection, wkt_to_geometry,
+};
+use crate::geometry_information::polygon_is_empty;
+use crate::geometry_validate::{geometry_is_valid, repair_geometry};
+use crate::json_errors;
+use geo::algorithm::area::Area;
+use geo::algorithm::intersects::Intersects;
+use geo_types::{Coordinate, Geometry, Line, LineString, MultiPolygon, Polygon};
+use geo_validator::Validate;
+use overlay::positive_region;
+
+/// The mitre limit used when none is given
+pub const DEFAULT_MITRE_LIMIT: f64 = 5.;
+
+/// The number of segments in a quarter circle used when none is given
+pub const DEFAULT_ARC_SEGMENTS: u32 = 8;
+
+/// The largest number of segments in a quarter circle
+pub const MAX_ARC_SEGMENTS: u32 = 256;
+
+/// How the offset edges are joined around a corner
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JoinStyle {
+    /// The corner is rounded off with a circular arc
+    #[default]
+    Round,
+    /// The offset edges are extended until they meet, up to the mitre limit
+    Mitre,
+    /// The corner is cut off with a straight line
+    Bevel,
+}
+
+impl JoinStyle {
+    /// Reads the join style from its name, "round", "mitre" (or "miter"),
+    /// or "bevel" (in any case)
+    pub fn parse(join: &str) -> Result<Self, GeometryToolsError> {
+        match join.to_ascii_lowercase().as_str() {
+            "round" => Ok(JoinStyle::Round),
+            "mitre" | "miter" => Ok(JoinStyle::Mitre),
+            "bevel" => Ok(JoinStyle::Bevel),
+            _ => Err(json_errors::argument_error::invalid_argument(
+                "join",
+                "The join style must be \"round\", \"mitre\", or \"bevel\"",
+            )),
+        }
+    }
+}
+
+/// Options for buffering
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BufferOptions {
+    pub join: JoinStyle,
+    /// The longest distance from a corner to the tip of a mitre join, as a
+    /// multiple of the buffer distance. Longer mitres are cut off at that distance.
+    pub mitre_limit: f64,
+    /// The number of segments used for a quarter circle of a round join
+    pub arc_segments: u32,
+}
+
+impl Default for BufferOptions {
+    fn default() -> Self {
+        BufferOptions {
+            join: JoinStyle::Round,
+            mitre_limit: DEFAULT_MITRE_LIMIT,
+            arc_segments: DEFAULT_ARC_SEGMENTS,
+        }
+    }
+}
+
+impl BufferOptions {
+    /// Reads the options, using the defaults for the ones that are not given
+    pub fn parse(
+        join: Option<&str>,
+        mitre_limit: Option<f64>,
+        arc_segments: Option<u32>,
+    ) -> Result<Self, GeometryToolsError> {
+        let mitre_limit = mitre_limit.unwrap_or(DEFAULT_MITRE_LIMIT);
+        if !mitre_limit.is_finite() || mitre_limit < 1. {
+            return Err(json_errors::argument_error::invalid_argument(
+                "mitreLimit",
+                "The mitre limit must be a number of at least 1",
+            ));
+        }
+        let arc_segments = arc_segments.unwrap_or(DEFAULT_ARC_SEGMENTS);
+        if arc_segments == 0 || arc_segments > MAX_ARC_SEGMENTS {
+            return Err(json_errors::argument_error::invalid_argument(
+                "arcSegments",
+                &format!(
+                    "The number of arc segments must be between 1 and {}",
+                    MAX_ARC_SEGMENTS
+                ),
+            ));
+        }
+        Ok(BufferOptions {
+            join: join.map(JoinStyle::parse).transpose()?.unwrap_or_default(),
+            mitre_limit,
+            arc_segments,
+        })
+    }
+}
+
+/// Grows (positive distance) or shrinks (negative distance) a polygonal Geometry.
+///
+/// Every edge is moved out or in by the distance, and the offset edges are
+/// joined around the corners in the style of the options. Holes shrink as the
+/// polygon grows and grow as it shrinks, and parts that are thinner than twice
+/// the distance disappear when shrinking. The members of a GEOMETRYCOLLECTION
+/// are unioned before they are buffered.
+///
+/// The input must be a valid (MULTI)POLYGON or a collection of them. The result
+/// is repaired when it is not valid, and an INVALID_GEOMETRY error is returned
+/// when that does not succeed. A result consisting of a single polygon is
+/// returned as a Polygon, anything else is returned as a MultiPolygon.
+///
+pub fn buffer_geometry(
+    geom: &Geometry<f64>,
+    distance: f64,
+    options: &BufferOptions,
+) -> Result<Geometry<f64>, GeometryToolsError> {
+    if !distance.is_finite() {
+        return Err(json_errors::argument_error::invalid_argument(
+            "distance",
+            "The distance must be a finite number",
+        ));
+    }
+    let polys = polygonal_input(geom)?
+        .ok_or_else(|| json_errors::wkt_errors::invalid_wkt_type(&geometry_to_wkt(geom)))?;
+    if distance == 0. || polys.0.is_empty() {
+        return Ok(single_or_multi(polys));
+    }
+
+    // Every ring is offset to its right, which is outside of the polygon when
+    // the shells wind counterclockwise and the holes clockwise
+    let paths = oriented_rings(&polys)
+        .iter()
+        .map(|ring| offset_ring(ring, distance, options))
+        .collect::<Vec<Vec<Coordinate<f64>>>>();
+    let buffered = positive_region(&paths);
+
+    // Rings without area are slivers left where offset edges almost meet
+    let min_area = distance * distance * 1e-9;
+    let has_area =
+        |ring: &LineString<f64>| Polygon::new(ring.clone(), vec![]).area().abs() > min_area;
+    let mut buffered = MultiPolygon(
+        buffered
+            .0
+            .iter()
+            .filter(|poly| has_area(poly.exterior()))
+            .map(|poly| {
+                Polygon::new(
+                    remove_collinear_vertices(poly.exterior()),
+                    poly.interiors()
+                        .iter()
+                        .filter(|ring| has_area(ring))
+                        .map(remove_collinear_vertices)
+                        .collect(),
+                )
+            })
+            .collect(),
+    );
+    if !buffer_is_valid(&buffered) {
+        buffered = match repair_geometry(&buffered.clone().into())? {
+            Geometry::Polygon(poly) => MultiPolygon(vec![poly]),
+            Geometry::MultiPolygon(polys) => polys,
+            _ => buffered,
+        };
+        if !buffer_is_valid(&buffered) {
+            return Err(json_errors::wkt_errors::invalid_geometry(
+                &geometry_to_wkt(&buffered.clone().into()),
+                &buffered.validate_detailed(),
+            ));
+        }
+    }
+    Ok(single_or_multi(buffered))
+}
+
+/// Buffers a WKT geometry and writes the result as WKT (see `buffer_geometry`).
+///
+pub fn wkt_buffer(
+    wkt: &str,
+    distance: f64,
+    options: &BufferOptions,
+) -> Result<String, GeometryToolsError> {
+    Ok(geometry_to_wkt(&buffer_geometry(
+        &wkt_to_geometry(wkt)?,
+        distance,
+        options,
+    )?))
+}
+
+/// Buffers an SVG element and writes the result as an SVG element (see `buffer_geometry`).
+///
+pub fn svg_buffer(
+    svg: &str,
+    distance: f64,
+    options: &BufferOptions,
+) -> Result<String, GeometryToolsError> {
+    Ok(geometry_to_svg(&buffer_geometry(
+        &svg_to_geometry_or_collection(svg)?,
+        distance,
+        options,
+    )?))
+}
+
+fn single_or_multi(mut polys: MultiPolygon<f64>) -> Geometry<f64> {
+    if polys.0.len() == 1 {
+        return polys.0.remove(0).into();
+    }
+    polys.into()
+}
+
+/// Reads the valid polygons of a Geometry without its empty polygons, or None
+/// when it is not polygonal. The members of a GEOMETRYCOLLECTION are unioned.
+fn polygonal_input(geom: &Geometry<f64>) -> Result<Option<MultiPolygon<f64>>, GeometryToolsError> {
+    let polys = match geom {
+        Geometry::Polygon(poly) => MultiPolygon(vec![poly.clone()]),
+        Geometry::MultiPolygon(polys) => polys.clone(),
+        Geometry::GeometryCollection(geoms) => {
+            let mut members = vec![];
+            for member in geoms.0.iter() {
+                match polygonal_input(member)? {
+                    Some(polys) => members.extend(polys.0),
+                    None => return Ok(None),
+                }
+            }
+            // The shells wind counterclockwise, so the area that they cover together
+            // is where the winding number is positive
+            return Ok(Some(positive_region(&oriented_rings(&MultiPolygon(
+                members,
+            )))));
+        }
+        _ => return Ok(None),
+    };
+    let polys = MultiPolygon(
+        polys
+            .0
+            .into_iter()
+            .filter(|poly| !polygon_is_empty(poly))
+            .collect(),
+    );
+    if !geometry_is_valid(&polys.clone().into()) {
+        return Err(json_errors::wkt_errors::invalid_geometry(
+            &geometry_to_wkt(geom),
+            &polys.validate_detailed(),
+        ));
+    }
+    Ok(Some(polys))
+}
+
+/// Checks the buffered polygons one by one (they cannot overlap each other).
+///
+/// `geo_validator` intersects the lines through two segments in floating point,
+/// so two segments that lie apart on the same line, such as the pieces of an
+/// offset edge on either side of a notch, can be reported as crossing. When
+/// crossings are all it found, its segment checks are repeated, but only for
+/// segments whose bounding boxes meet.
+fn buffer_is_valid(polys: &MultiPolygon<f64>) -> bool {
+    polys
+        .0
+        .iter()
+        .all(|poly| poly.validate() || only_false_crossings(poly))
+}
+
+fn only_false_crossings(poly: &Polygon<f64>) -> bool {
+    let errors = poly.validate_detailed();
+    if errors.has_less_than_three_points
+        || errors.is_multi_polygon
+        || !errors.unsupported_floating_point_values.is_empty()
+        || !errors.open_rings.is_empty()
+        || !errors.point_touching_line.is_empty()
+        || !errors.repeated_points.is_empty()
+    {
+        return false;
+    }
+    let lines = std::iter::once(poly.exterior())
+        .chain(poly.interiors())
+        .flat_map(|ring| ring.lines())
+        .collect::<Vec<Line<f64>>>();
+    let boxes_meet = |a: &Line<f64>, b: &Line<f64>| {
+        a.start.x.min(a.end.x) <= b.start.x.max(b.end.x)
+            && b.start.x.min(b.end.x) <= a.start.x.max(a.end.x)
+            && a.start.y.min(a.end.y) <= b.start.y.max(b.end.y)
+            && b.start.y.min(b.end.y) <= a.start.y.max(a.end.y)
+    };
+    lines.iter().enumerate().all(|(index, current)| {
+        lines[..index].iter().all(|line| {
+            line.end == current.start
+                || line.start == current.end
+                || !boxes_meet(line, current)
+                || !line.intersects(current)
+        })
+    })
+}
+
+/// The rings of the polygons without their closing points, with the shells
+/// winding counterclockwise and the holes clockwise
+fn oriented_rings(polys: &MultiPolygon<f64>) -> Vec<Vec<Coordinate<f64>>> {
+    let orient = |ring: &LineString<f64>, counterclockwise: bool| {
+        let mut coords = ring.0.clone();
+        coords.dedup();
+        if coords.len() > 1 && coords.first() == coords.last() {
+            coords.pop();
+        }
+        let area: f64 = (0..coords.len())
+            .map(|index| {
+                let (a, b) = (coords[index], coords[(index + 1) % coords.len()]);
+                a.x * b.y - b.x * a.y
+            })
+            .sum();
+        if (area > 0.) != counterclockwise {
+            coords.reverse();
+        }
+        coords
+    };
+    polys
+        .0
+        .iter()
+        .flat_map(|poly| {
+            std::iter::once(orient(poly.exterior(), true))
+                .chain(poly.interiors().iter().map(move |ring| orient(ring, false)))
+        })
+        .filter(|ring| ring.len() > 2)
+        .collect()
+}
+
+/// Moves every edge of the ring to its right by the distance (to its left when
+/// it is negative), and joins the moved edges around the corners.
+///
+/// Where the moved edges overlap, they are joined through the corner itself,
+/// which leaves a small loop that winds the other way and drops out of the
+/// positive region.
+fn offset_ring(
+    ring: &[Coordinate<f64>],
+    distance: f64,
+    options: &BufferOptions,
+) -> Vec<Coordinate<f64>> {
+    let count = ring.len();
+    let normals = (0..count)
+        .map(|index| right_normal(ring[index], ring[(index + 1) % count]))
+        .collect::<Vec<Coordinate<f64>>>();
+
+    let mut path = vec![];
+    for index in 0..count {
+        let corner = ring[index];
+        let (in_normal, out_normal) = (normals[(index + count - 1) % count], normals[index]);
+        let start = offset(corner, in_normal, distance);
+        let end = offset(corner, out_normal, distance);
+        // Turning left opens a gap on the right, and turning right on the left
+        let turn = in_normal.x * out_normal.y - in_normal.y * out_normal.x;
+        let cos = in_normal.x * out_normal.x + in_normal.y * out_normal.y;
+        path.push(start);
+        if turn * distance > 0. || (turn == 0. && cos < 0.) {
+            path.extend(corner_join(
+                corner, in_normal, out_normal, distance, options,
+            ));
+        } else if turn != 0. {
+            path.push(corner);
+        }
+        path.push(end);
+    }
+    path.dedup();
+    path
+}
+
+/// The points between the ends of two moved edges that join them around the
+/// outside of a corner
+fn corner_join(
+    corner: Coordinate<f64>,
+    in_normal: Coordinate<f64>,
+    out_normal: Coordinate<f64>,
+    distance: f64,
+    options: &BufferOptions,
+) -> Vec<Coordinate<f64>> {
+    let cos = in_normal.x * out_normal.x + in_normal.y * out_normal.y;
+    match options.join {
+        JoinStyle::Bevel => vec![],
+        JoinStyle::Round => {
+            let from = in_normal.y.atan2(in_normal.x);
+            let turn = in_normal.x * out_normal.y - in_normal.y * out_normal.x;
+            // The arc turns the same way as the corner, half way round at a reversal
+            let sweep = if turn == 0. {
+                std::f64::consts::PI.copysign(distance)
+            } else {
+                turn.atan2(cos)
+            };
+            let quarter = std::f64::consts::FRAC_PI_2 / options.arc_segments as f64;
+            let steps = (sweep.abs() / quarter).ceil() as usize;
+            (1..steps)
+                .map(|step| {
+                    let angle = from + sweep * step as f64 / steps as f64;
+                    Coordinate {
+                        x: corner.x + distance * angle.cos(),
+                        y: corner.y + distance * angle.sin(),
+                    }
+                })
+                .collect()
+        }
+        JoinStyle::Mitre => {
+            // The tip is 1 / cos(half the turn) times the distance from the corner
+            let cos_half = ((1. + cos) / 2.).sqrt();
+            if cos_half > 0. && 1. / cos_half <= options.mitre_limit {
+                let scale = distance / (1. + cos);
+                return vec![Coordinate {
+                    x: corner.x + (in_normal.x + out_normal.x) * scale,
+                    y: corner.y + (in_normal.y + out_normal.y) * scale,
+                }];
+            }
+            // A longer mitre is cut off square to its middle, at the limit
+            let sin_half = ((1. - cos) / 2.).sqrt();
+            let reach = distance.abs() * (options.mitre_limit - cos_half) / sin_half;
+            let start = offset(corner, in_normal, distance);
+            let end = offset(corner, out_normal, distance);
+            let along = |normal: Coordinate<f64>, sign: f64| {
+                // The edge direction is the normal turned back to the left
+                Coordinate {
+                    x: -normal.y * sign,
+                    y: normal.x * sign,
+                }
+            };
+            vec![
+                offset(start, along(in_normal, 1.), reach),
+                offset(end, along(out_normal, -1.), reach),
+            ]
+        }
+    }
+}
+
+/// The unit normal to the right of the direction from one point to another
+fn right_normal(from: Coordinate<f64>, to: Coordinate<f64>) -> Coordinate<f64> {
+    let (dx, dy) = (to.x - from.x, to.y - from.y);
+    let length = dx.hypot(dy);
+    Coordinate {
+        x: dy / length,
+        y: -dx / length,
+    }
+}
+
+fn offset(point: Coordinate<f64>, dir: Coordinate<f64>, distance: f64) -> Coordinate<f64> {
+    Coordinate {
+        x: point.x + dir.x * distance,
+        y: point.y + dir.y * distance,
+    }
+}
+
+/// Removes the vertices in the middle of a straight edge, which are left
+/// where a moved edge runs into a join
+fn remove_collinear_vertices(ring: &LineString<f64>) -> LineString<f64> {
+    let mut coords = ring.0.clone();
+    coords.dedup();
+    if coords.len() < 5 || coords.first() != coords.last() {
+        return LineString(coords);
+    }
+    coords.pop();
+    let mut index = 0;
+    while coords.len() > 3 && index < coords.len() {
+        let count = coords.len();
+        let (prev, cur, next) = (
+            coords[(index + count - 1) % count],
+            coords[index],
+            coords[(index + 1) % count],
+        );
+        let (ax, ay, bx, by) = (
+            cur.x - prev.x,
+            cur.y - prev.y,
+            next.x - cur.x,
+            next.y - cur.y,
+        );
+        let cross = ax * by - ay * bx;
+        if cross.abs() <= 1e-12 * ax.hypot(ay) * bx.hypot(by) && ax * bx + ay * by > 0. {
+            coords.remove(index);
+        } else {
+            index += 1;
+        }
+    }
+    coords.push(coords[0]);
+    LineString(coords)
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygons(geom: &Geometry<f64>) -> MultiPolygon<f64> {
+        match geom {
+            Geometry::Polygon(poly) => MultiPolygon(vec![poly.clone()]),
+            Geometry::MultiPolygon(polys) => polys.clone(),
+            _ => panic!("expected a polygonal geometry"),
+        }
+    }
+
+    fn buffered_area(wkt: &str, distance: f64, options: &BufferOptions) -> f64 {
+        match buffer_geometry(&wkt_to_geometry(wkt).unwrap(), distance, options).unwrap() {
+            Geometry::Polygon(poly) => poly.area(),
+            Geometry::MultiPolygon(polys) => polys.area(),
+            other => panic!("The buffer should be polygonal, not {:?}", other),
+        }
+    }
+
+    #[test]
+    fn grows_and_shrinks_polygons() {
+        let square = "POLYGON((0 0,10 0,10 10,0 10,0 0))";
+        let mitre = BufferOptions {
+            join: JoinStyle::Mitre,
+            ..Default::default()
+        };
+        assert_eq!(
+            "POLYGON((-1 -1,11 -1,11 11,-1 11,-1 -1))",
+            wkt_buffer(square, 1., &mitre).unwrap()
+        );
+        assert_eq!(
+            "POLYGON((1 1,9 1,9 9,1 9,1 1))",
+            wkt_buffer(square, -1., &mitre).unwrap()
+        );
+        assert_eq!(
+            "<path d=\"M1 1L9 1L9 9L1 9L1 1\"/>",
+            svg_buffer(
+                "<rect x=\"0\" y=\"0\" width=\"10\" height=\"10\"/>",
+                -1.,
+                &mitre
+            )
+            .unwrap()
+        );
+
+        let bevel = BufferOptions {
+            join: JoinStyle::Bevel,
+            ..Default::default()
+        };
+        assert!((buffered_area(square, 1., &bevel) - 142.).abs() < 1e-9);
+        let round = buffered_area(square, 1., &BufferOptions::default());
+        assert!(round > 143. && round < 100. + 40. + std::f64::consts::PI);
+        assert!((buffered_area(square, -1., &BufferOptions::default()) - 64.).abs() < 1e-9);
+
+        // A mitre that is longer than the limit is cut off
+        let sharp = "POLYGON((0 0,10 0,0 1,0 0))";
+        let limited = BufferOptions {
+            join: JoinStyle::Mitre,
+            mitre_limit: 2.,
+            ..Default::default()
+        };
+        let unlimited = BufferOptions {
+            join: JoinStyle::Mitre,
+            mitre_limit: 100.,
+            ..Default::default()
+        };
+        assert!(buffered_area(sharp, 1., &limited) < buffered_area(sharp, 1., &unlimited));
+    }
+
+    #[test]
+    fn shrinks_polygons_with_holes_and_multi_polygons() {
+        let mitre = BufferOptions {
+            join: JoinStyle::Mitre,
+            ..Default::default()
+        };
+        assert_eq!(
+            "POLYGON((1 1,19 1,19 19,1 19,1 1),(6 6,6 14,14 14,14 6,6 6))",
+            wkt_buffer(
+                "POLYGON((0 0,20 0,20 20,0 20,0 0),(7 7,7 13,13 13,13 7,7 7))",
+                -1.,
+                &mitre
+            )
+            .unwrap()
+        );
+        // The small hole closes up while the shell grows
+        assert_eq!(
+            "POLYGON((-1 -1,21 -1,21 21,-1 21,-1 -1))",
+            wkt_buffer(
+                "POLYGON((0 0,20 0,20 20,0 20,0 0),(9 9,9 11,11 11,11 9,9 9))",
+                1.,
+                &mitre
+            )
+            .unwrap()
+        );
+        // The thin member disappears, the other one shrinks
+        assert_eq!(
+            "POLYGON((1 1,9 1,9 9,1 9,1 1))",
+            wkt_buffer(
+                "MULTIPOLYGON(((0 0,10 0,10 10,0 10,0 0)),((20 0,21 0,21 10,20 10,20 0)))",
+                -1.,
+                &mitre
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            "MULTIPOLYGON EMPTY",
+            wkt_buffer("POLYGON((0 0,2 0,2 2,0 2,0 0))", -1.5, &mitre).unwrap()
+        );
+        // Members that grow into each other are merged
+        assert_eq!(
+            "POLYGON((-1 -1,13 -1,13 11,-1 11,-1 -1))",
+            wkt_buffer(
+                "MULTIPOLYGON(((0 0,5 0,5 10,0 10,0 0)),((7 0,12 0,12 10,7 10,7 0)))",
+                1.,
+                &mitre
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn buffers_sharp_stars() {
+        let joins = [JoinStyle::Round, JoinStyle::Mitre, JoinStyle::Bevel];
+        let star = "POLYGON((6.34 0,2.86 1.65,5.88 10.18,0 8.25,-5.59 9.68,-3.72 2.15,-5.08 0,\
+                    -6.7 -3.87,-1.97 -3.42,-0 -3.43,1.99 -3.44,7.64 -4.41,6.34 0))";
+        let area = buffered_area(star, 0., &BufferOptions::default());
+        for join in joins.iter() {
+            let options = BufferOptions {
+                join: *join,
+                ..Default::default()
+            };
+            let shrunk = buffer_geometry(&wkt_to_geometry(star).unwrap(), -0.7, &options).unwrap();
+            assert!(
+                buffer_is_valid(&polygons(&shrunk)),
+                "{:?} gave {:?}",
+                join,
+                shrunk
+            );
+            assert!(buffered_area(star, -0.7, &options) < area);
+        }
+
+        // The bevels of neighbouring spikes cut notches into the offset edges
+        let star = "POLYGON((4.22 0,3.83 0.68,6.99 2.54,3.83 2.21,8.38 7.03,5.11 6.09,3.3 5.72,\
+                    3.91 10.74,1.17 6.61,0 8.08,-1.71 9.7,-3.71 10.21,-5.41 9.38,-6.79 8.09,\
+                    -2.53 2.12,-7.35 4.24,-8.51 3.1,-11.24 1.98,-10.74 0,-3.08 -0.54,\
+                    -11.13 -4.05,-8.18 -4.73,-6.79 -5.7,-2.6 -3.09,-3.91 -6.76,-3.74 -10.27,\
+                    -0.77 -4.36,-0 -8.92,0.66 -3.72,3.26 -8.95,6.09 -10.54,8.05 -9.6,\
+                    9.41 -7.9,2.7 -1.56,8.96 -3.26,11.94 -2.1,4.22 0))";
+        let area = buffered_area(star, 0., &BufferOptions::default());
+        for join in joins.iter() {
+            let options = BufferOptions {
+                join: *join,
+                ..Default::default()
+            };
+            let grown = buffer_geometry(&wkt_to_geometry(star).unwrap(), 4., &options).unwrap();
+            assert!(
+                buffer_is_valid(&polygons(&grown)),
+                "{:?} gave {:?}",
+                join,
+                grown
+            );
+            assert!(buffered_area(star, 4., &options) > area);
+        }
+    }
+
+    #[test]
+    fn accepts_false_crossings_of_buffer_output_only() {
+        // geo_validator reports the pieces of an offset edge on either side of
+        // a bevel notch as crossing each other
+        let star = "POLYGON((4.22 0,3.83 0.68,6.99 2.54,3.83 2.21,8.38 7.03,5.11 6.09,3.3 5.72,\
+                    3.91 10.74,1.17 6.61,0 8.08,-1.71 9.7,-3.71 10.21,-5.41 9.38,-6.79 8.09,\
+                    -2.53 2.12,-7.35 4.24,-8.51 3.1,-11.24 1.98,-10.74 0,-3.08 -0.54,\
+                    -11.13 -4.05,-8.18 -4.73,-6.79 -5.7,-2.6 -3.09,-3.91 -6.76,-3.74 -10.27,\
+                    -0.77 -4.36,-0 -8.92,0.66 -3.72,3.26 -8.95,6.09 -10.54,8.05 -9.6,\
+                    9.41 -7.9,2.7 -1.56,8.96 -3.26,11.94 -2.1,4.22 0))";
+        let options = BufferOptions {
+            join: JoinStyle::Bevel,
+            ..Default::default()
+        };
+        let grown = buffer_geometry(&wkt_to_geometry(star).unwrap(), 4., &options).unwrap();
+        assert!(!geometry_is_valid(&grown));
+        assert!(buffer_is_valid(&polygons(&grown)));
+
+        // Real crossings and touching points are still invalid
+        for wkt in [
+            "POLYGON((0 0,10 10,10 0,0 10,0 0))",
+            "POLYGON((0 0,10 0,10 10,0 10,0 0),(0 0,2 1,1 2,0 0))",
+        ]
+        .iter()
+        {
+            assert!(!buffer_is_valid(&polygons(&wkt_to_geometry(wkt).unwrap())));
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_buffer_input() {
+        let options = BufferOptions::default();
+        assert_eq!(
+            "INVALID_WKT_TYPE",
+            wkt_buffer("LINESTRING(0 0,1 1)", 1., &options)
+                .unwrap_err()
+                .code()
+        );
+        assert_eq!(
+            "INVALID_GEOMETRY",
+            wkt_buffer("POLYGON((0 0,10 10,10 0,0 10,0 0))", 1., &options)
+                .unwrap_err()
+                .code()
+        );
+        assert_eq!(
+            "INVALID_ARGUMENT",
+            wkt_buffer("POLYGON((0 0,1 0,1 1,0 0))", f64::NAN, &options)
+                .unwrap_err()
+                .code()
+        );
+        for (join, mitre_limit, arc_segments) in [
+            (Some("square"), None, None),
+            (None, Some(0.5), None),
+            (None, None, Some(0)),
+        ]
+        .iter()
+        {
+            assert_eq!(
+                "INVALID_ARGUMENT",
+                BufferOptions::parse(*join, *mitre_limit, *arc_segments)
+                    .unwrap_err()
+                    .code()
+            );
+        }
+        assert_eq!(
+            JoinStyle::Mitre,
+            BufferOptions::parse(Some("Miter"), None, None)
+                .unwrap()
+                .join
+        );
+    }
+}
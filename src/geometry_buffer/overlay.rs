@@ -0,0 +1,398 @@
+use geo_types::{Coordinate, LineString, MultiPolygon, Polygon};
+use std::collections::{BTreeMap, HashMap};
+
+type Segment = (Coordinate<f64>, Coordinate<f64>);
+
+/// Resolves closed paths, which may cross each other and themselves, into the
+/// polygons covering every point that the paths wind around counterclockwise
+/// (a positive winding number).
+///
+/// Each path is a list of points, with or without the first one repeated at
+/// the end. The shells of the result wind counterclockwise and the holes
+/// clockwise, and every ring starts at its lowest left point.
+///
+pub(crate) fn positive_region(paths: &[Vec<Coordinate<f64>>]) -> MultiPolygon<f64> {
+    let segments: Vec<Segment> = paths
+        .iter()
+        .flat_map(|path| {
+            (0..path.len())
+                .map(move |index| (path[index], path[(index + 1) % path.len()]))
+                .filter(|(start, end)| start != end)
+        })
+        .collect();
+    let extent = segments
+        .iter()
+        .flat_map(|(start, end)| vec![start.x, start.y, end.x, end.y])
+        .fold(0f64, |extent, value| extent.max(value.abs()));
+    if segments.is_empty() || extent == 0. {
+        return MultiPolygon(vec![]);
+    }
+    // Points closer than this are taken as one node
+    let tolerance = extent * 1e-12;
+
+    let mut nodes = Nodes::new(tolerance);
+    for (start, end) in segments.iter() {
+        nodes.id(*start);
+        nodes.id(*end);
+    }
+    // Every segment becomes a chain of edges from the node of its start to the
+    // node of its end, even when a cut near one of its ends has been merged
+    // into another node. Each edge counts the segments running along it, plus
+    // one for each going from its lower to its higher node and minus one for
+    // each going back.
+    let mut counts = BTreeMap::new();
+    for (segment, mut cuts) in segments.iter().zip(cut_points(&segments, tolerance)) {
+        let (start, end) = *segment;
+        let (dx, dy) = (end.x - start.x, end.y - start.y);
+        cuts.sort_by(|a, b| {
+            ((a.x - start.x) * dx + (a.y - start.y) * dy)
+                .partial_cmp(&((b.x - start.x) * dx + (b.y - start.y) * dy))
+                .unwrap()
+        });
+        let mut ids = vec![nodes.id(start)];
+        ids.extend(cuts.into_iter().map(|cut| nodes.id(cut)));
+        ids.push(nodes.id(end));
+        ids.dedup();
+        for pair in ids.windows(2) {
+            let count = counts
+                .entry((pair[0].min(pair[1]), pair[0].max(pair[1])))
+                .or_insert(0);
+            *count += if pair[0] < pair[1] { 1 } else { -1 };
+        }
+    }
+    let edges = counts
+        .into_iter()
+        .filter(|(_, count)| *count != 0)
+        .collect::<Vec<((usize, usize), i32)>>();
+
+    // Only the edges with the region on exactly one side are kept, directed so
+    // that the region is on their left. Crossing an edge from its right to its
+    // left adds its count to the winding number, so only one side has to be
+    // found, by casting a ray from the middle of the edge past every other edge.
+    let windings = WindingIndex::new(
+        edges
+            .iter()
+            .map(|((a, b), count)| ((nodes.points[*a], nodes.points[*b]), *count))
+            .collect(),
+    );
+    let boundary = edges
+        .iter()
+        .enumerate()
+        .filter_map(|(id, ((a, b), count))| {
+            let (start, end) = (nodes.points[*a], nodes.points[*b]);
+            let beside = windings.winding_beside(id);
+            let (left, right) = if (start.y == end.y && end.x > start.x) || end.y < start.y {
+                (beside, beside - count)
+            } else {
+                (beside + count, beside)
+            };
+            match (left > 0, right > 0) {
+                (true, false) => Some((*a, *b)),
+                (false, true) => Some((*b, *a)),
+                _ => None,
+            }
+        })
+        .collect::<Vec<(usize, usize)>>();
+
+    assemble(trace_rings(&nodes.points, &boundary))
+}
+
+/// Merges the points that are within the tolerance of each other into nodes,
+/// keeping the first point of each node
+struct Nodes {
+    tolerance: f64,
+    points: Vec<Coordinate<f64>>,
+    cells: HashMap<(i64, i64), Vec<usize>>,
+}
+
+impl Nodes {
+    fn new(tolerance: f64) -> Self {
+        Nodes {
+            tolerance,
+            points: vec![],
+            cells: HashMap::new(),
+        }
+    }
+
+    fn id(&mut self, point: Coordinate<f64>) -> usize {
+        let cell = (
+            (point.x / self.tolerance).floor() as i64,
+            (point.y / self.tolerance).floor() as i64,
+        );
+        for x in cell.0 - 1..=cell.0 + 1 {
+            for y in cell.1 - 1..=cell.1 + 1 {
+                for id in self.cells.get(&(x, y)).into_iter().flatten() {
+                    let other = self.points[*id];
+                    if (other.x - point.x).abs() <= self.tolerance
+                        && (other.y - point.y).abs() <= self.tolerance
+                    {
+                        return *id;
+                    }
+                }
+            }
+        }
+        self.points.push(point);
+        self.cells
+            .entry(cell)
+            .or_default()
+            .push(self.points.len() - 1);
+        self.points.len() - 1
+    }
+}
+
+/// Finds where every segment has to be cut: at its ends, where it crosses
+/// another segment, and where the end of another segment lies on it
+fn cut_points(segments: &[Segment], tolerance: f64) -> Vec<Vec<Coordinate<f64>>> {
+    let mut cuts = segments
+        .iter()
+        .map(|(start, end)| vec![*start, *end])
+        .collect::<Vec<Vec<Coordinate<f64>>>>();
+    let min_x = |index: usize| segments[index].0.x.min(segments[index].1.x);
+    let mut order = (0..segments.len()).collect::<Vec<usize>>();
+    order.sort_by(|a, b| min_x(*a).partial_cmp(&min_x(*b)).unwrap());
+
+    for (position, &first) in order.iter().enumerate() {
+        let (p, p2) = segments[first];
+        let max_x = p.x.max(p2.x) + tolerance;
+        let (min_y, max_y) = (p.y.min(p2.y) - tolerance, p.y.max(p2.y) + tolerance);
+        for &second in order[position + 1..].iter() {
+            if min_x(second) > max_x {
+                break;
+            }
+            let (q, q2) = segments[second];
+            if q.y.max(q2.y) < min_y || q.y.min(q2.y) > max_y {
+                continue;
+            }
+            for (point, on) in [(q, first), (q2, first), (p, second), (p2, second)].iter() {
+                let (start, end) = segments[*on];
+                if distance_to_segment(*point, start, end) <= tolerance {
+                    cuts[*on].push(*point);
+                }
+            }
+            let (r, s) = ((p2.x - p.x, p2.y - p.y), (q2.x - q.x, q2.y - q.y));
+            let denominator = r.0 * s.1 - r.1 * s.0;
+            if denominator == 0. {
+                continue;
+            }
+            let (qx, qy) = (q.x - p.x, q.y - p.y);
+            let t = (qx * s.1 - qy * s.0) / denominator;
+            let u = (qx * r.1 - qy * r.0) / denominator;
+            if (0. ..=1.).contains(&t) && (0. ..=1.).contains(&u) {
+                let crossing = Coordinate {
+                    x: p.x + r.0 * t,
+                    y: p.y + r.1 * t,
+                };
+                cuts[first].push(crossing);
+                cuts[second].push(crossing);
+            }
+        }
+    }
+    cuts
+}
+
+fn distance_to_segment(
+    point: Coordinate<f64>,
+    start: Coordinate<f64>,
+    end: Coordinate<f64>,
+) -> f64 {
+    let (dx, dy) = (end.x - start.x, end.y - start.y);
+    let fraction =
+        (((point.x - start.x) * dx + (point.y - start.y) * dy) / (dx * dx + dy * dy)).clamp(0., 1.);
+    (start.x + dx * fraction - point.x).hypot(start.y + dy * fraction - point.y)
+}
+
+/// Finds the winding number beside an edge, with the edges sorted into
+/// horizontal bands so that a ray is only tested against its own band
+struct WindingIndex {
+    edges: Vec<(Segment, i32)>,
+    min_y: f64,
+    band_height: f64,
+    bands: Vec<Vec<usize>>,
+}
+
+impl WindingIndex {
+    fn new(edges: Vec<(Segment, i32)>) -> Self {
+        let min_y = edges
+            .iter()
+            .fold(f64::INFINITY, |min, ((a, b), _)| min.min(a.y).min(b.y));
+        let max_y = edges
+            .iter()
+            .fold(f64::NEG_INFINITY, |max, ((a, b), _)| max.max(a.y).max(b.y));
+        let count = ((edges.len() as f64).sqrt().ceil() as usize).max(1);
+        let band_height = ((max_y - min_y) / count as f64).max(f64::MIN_POSITIVE);
+        let mut index = WindingIndex {
+            edges: vec![],
+            min_y,
+            band_height,
+            bands: vec![vec![]; count],
+        };
+        for (id, ((a, b), _)) in edges.iter().enumerate() {
+            for band in index.band(a.y.min(b.y))..=index.band(a.y.max(b.y)) {
+                index.bands[band].push(id);
+            }
+        }
+        index.edges = edges;
+        index
+    }
+
+    fn band(&self, y: f64) -> usize {
+        (((y - self.min_y) / self.band_height).max(0.) as usize).min(self.bands.len() - 1)
+    }
+
+    /// Casts a ray in the direction of x from the middle of an edge, which
+    /// gives the winding number on the side of the edge towards greater x, or
+    /// towards greater y if the edge is horizontal
+    fn winding_beside(&self, edge: usize) -> i32 {
+        let (a, b) = self.edges[edge].0;
+        let point = Coordinate {
+            x: (a.x + b.x) / 2.,
+            y: (a.y + b.y) / 2.,
+        };
+        let mut winding = 0;
+        for id in self.bands[self.band(point.y)]
+            .iter()
+            .filter(|id| **id != edge)
+        {
+            let ((a, b), count) = self.edges[*id];
+            let side = (b.x - a.x) * (point.y - a.y) - (point.x - a.x) * (b.y - a.y);
+            if a.y <= point.y {
+                if b.y > point.y && side > 0. {
+                    winding += count;
+                }
+            } else if b.y <= point.y && side < 0. {
+                winding -= count;
+            }
+        }
+        winding
+    }
+}
+
+/// Links the directed boundary edges into closed rings. At a node where the
+/// boundary touches itself, each ring takes the sharpest turn to the left, so
+/// rings only touch at their nodes and never cross.
+fn trace_rings(points: &[Coordinate<f64>], edges: &[(usize, usize)]) -> Vec<Vec<Coordinate<f64>>> {
+    let mut outgoing: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (id, (from, _)) in edges.iter().enumerate() {
+        outgoing.entry(*from).or_default().push(id);
+    }
+    let angle = |from: usize, to: usize| {
+        (points[to].y - points[from].y).atan2(points[to].x - points[from].x)
+    };
+
+    let mut used = vec![false; edges.len()];
+    let mut rings = vec![];
+    for first in 0..edges.len() {
+        if used[first] {
+            continue;
+        }
+        let mut ring = vec![];
+        let mut edge = first;
+        loop {
+            used[edge] = true;
+            let (from, to) = edges[edge];
+            ring.push(points[from]);
+            if to == edges[first].0 {
+                rings.push(ring);
+                break;
+            }
+            // The next edge is the first one clockwise from the way back
+            let back = angle(to, from);
+            let next = outgoing
+                .get(&to)
+                .into_iter()
+                .flatten()
+                .filter(|next| !used[**next])
+                .map(|next| {
+                    let turn =
+                        (back - angle(to, edges[*next].1)).rem_euclid(std::f64::consts::PI * 2.);
+                    (
+                        if turn == 0. {
+                            std::f64::consts::PI * 2.
+                        } else {
+                            turn
+                        },
+                        *next,
+                    )
+                })
+                .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+            match next {
+                Some((_, next)) => edge = next,
+                // An open chain is left over from rounding, and is dropped
+                None => break,
+            }
+        }
+    }
+    rings
+}
+
+/// Sorts the rings into shells and holes, and puts every hole in the smallest
+/// shell that contains it
+fn assemble(rings: Vec<Vec<Coordinate<f64>>>) -> MultiPolygon<f64> {
+    let (shells, holes): (Vec<_>, Vec<_>) = rings
+        .into_iter()
+        .filter(|ring| ring.len() > 2)
+        .map(|ring| (signed_area(&ring), ring))
+        .filter(|(area, _)| *area != 0.)
+        .partition(|(area, _)| *area > 0.);
+
+    let mut interiors: Vec<Vec<LineString<f64>>> = vec![vec![]; shells.len()];
+    for (_, hole) in holes {
+        let probe = Coordinate {
+            x: (hole[0].x + hole[1].x) / 2.,
+            y: (hole[0].y + hole[1].y) / 2.,
+        };
+        let owner = shells
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, shell))| ring_contains(shell, probe))
+            .min_by(|a, b| (a.1).0.partial_cmp(&(b.1).0).unwrap())
+            .map(|(id, _)| id);
+        if let Some(owner) = owner {
+            interiors[owner].push(closed_ring(hole));
+        }
+    }
+    MultiPolygon(
+        shells
+            .into_iter()
+            .zip(interiors)
+            .map(|((_, shell), interiors)| Polygon::new(closed_ring(shell), interiors))
+            .collect(),
+    )
+}
+
+fn signed_area(ring: &[Coordinate<f64>]) -> f64 {
+    (0..ring.len())
+        .map(|index| {
+            let (a, b) = (ring[index], ring[(index + 1) % ring.len()]);
+            a.x * b.y - b.x * a.y
+        })
+        .sum::<f64>()
+        / 2.
+}
+
+fn ring_contains(ring: &[Coordinate<f64>], point: Coordinate<f64>) -> bool {
+    let mut inside = false;
+    for index in 0..ring.len() {
+        let (a, b) = (ring[index], ring[(index + 1) % ring.len()]);
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x < a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x)
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Starts the ring at its lowest left point and repeats that point at the end
+fn closed_ring(mut ring: Vec<Coordinate<f64>>) -> LineString<f64> {
+    let first = (0..ring.len())
+        .min_by(|a, b| {
+            (ring[*a].x, ring[*a].y)
+                .partial_cmp(&(ring[*b].x, ring[*b].y))
+                .unwrap()
+        })
+        .unwrap_or(0);
+    ring.rotate_left(first);
+    ring.push(ring[0]);
+    LineString(ring)
+}
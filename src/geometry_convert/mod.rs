@@ -0,0 +1,717 @@
+use crate::error::GeometryToolsError;
+use crate::geometry_information::split_srid;
+use crate::geometry_precision::{geometry_with_precision, Precision};
+use crate::json_errors;
+use geo_repair_polygon::repair::Repair;
+use geo_svg_io::geo_svg_writer::{ToSvg, ToSvgString};
+use geo_types::{Geometry, GeometryCollection, LineString, MultiPoint};
+use geo_wkt_writer::ToWkt;
+use svg_reader::{SvgDocumentElement, SvgReadOptions};
+use wkt::Wkt;
+
+pub mod svg_path;
+pub mod svg_reader;
+
+/// Parses a WKT string into a Geometry.
+/// An EWKT `SRID=...;` prefix is accepted and ignored.
+///
+/// A string holding several geometries, separated by whitespace, commas or
+/// semicolons, is read as a GeometryCollection of all of them. `POINT EMPTY`
+/// has no Point equivalent and is read as an empty MultiPoint, at the top
+/// level as well as inside a GEOMETRYCOLLECTION, so it is written back as
+/// `MULTIPOINT EMPTY`.
+///
+pub fn wkt_to_geometry(wkt: &str) -> Result<Geometry<f64>, GeometryToolsError> {
+    let (_, body) = split_srid(wkt)?;
+    let items = split_wkt_items(body);
+    if items.is_empty() {
+        return Err(json_errors::wkt_errors::wkt_cannot_be_parsed(
+            wkt,
+            "The wkt string is empty",
+        ));
+    }
+
+    let mut geoms = items
+        .iter()
+        .map(|item| wkt_item_to_geometry(wkt, item))
+        .collect::<Result<Vec<Geometry<f64>>, GeometryToolsError>>()?;
+    if geoms.len() == 1 {
+        return Ok(geoms.remove(0));
+    }
+    Ok(Geometry::GeometryCollection(GeometryCollection(geoms)))
+}
+
+/// Splits a WKT body into its top level geometries.
+/// A geometry ends with the parenthesis that closes its coordinates, or with
+/// the EMPTY keyword.
+fn split_wkt_items(body: &str) -> Vec<&str> {
+    let mut items = vec![];
+    let mut start = None as Option<usize>;
+    let mut depth = 0;
+    for (index, c) in body.char_indices() {
+        match c {
+            '(' => {
+                start.get_or_insert(index);
+                depth += 1;
+            }
+            ')' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    if let Some(item_start) = start.take() {
+                        items.push(&body[item_start..=index]);
+                    }
+                }
+            }
+            _ if depth == 0 && (c.is_whitespace() || c == ',' || c == ';') => {
+                if let Some(item_start) = start {
+                    let is_empty = body[item_start..index]
+                        .split_whitespace()
+                        .last()
+                        .is_some_and(|word| word.eq_ignore_ascii_case("EMPTY"));
+                    if is_empty {
+                        items.push(&body[item_start..index]);
+                        start = None;
+                    }
+                }
+            }
+            _ if c.is_whitespace() => {}
+            _ => {
+                start.get_or_insert(index);
+            }
+        }
+    }
+    if let Some(item_start) = start {
+        items.push(&body[item_start..]);
+    }
+    items
+}
+
+fn wkt_item_to_geometry(wkt: &str, item: &str) -> Result<Geometry<f64>, GeometryToolsError> {
+    let wkt_geom: Wkt<f64> = match Wkt::from_str(item) {
+        Ok(geom) => geom,
+        Err(err) => return Err(json_errors::wkt_errors::wkt_cannot_be_parsed(wkt, err)),
+    };
+    let item = match wkt_geom.items.first() {
+        Some(item) => item,
+        None => {
+            return Err(json_errors::wkt_errors::wkt_cannot_be_parsed(
+                wkt,
+                "The wkt string is empty",
+            ))
+        }
+    };
+    wkt_geometry_to_geometry(item)
+        .map_err(|reason| json_errors::wkt_errors::wkt_cannot_be_parsed(wkt, &reason))
+}
+
+/// Converts a parsed WKT geometry, reading empty points (also those nested
+/// in collections) as empty MultiPoints.
+fn wkt_geometry_to_geometry(item: &wkt::Geometry<f64>) -> Result<Geometry<f64>, String> {
+    match item {
+        wkt::Geometry::Point(wkt::types::Point(None)) => {
+            Ok(Geometry::MultiPoint(MultiPoint(vec![])))
+        }
+        wkt::Geometry::GeometryCollection(collection) => {
+            Ok(Geometry::GeometryCollection(GeometryCollection(
+                collection
+                    .0
+                    .iter()
+                    .map(wkt_geometry_to_geometry)
+                    .collect::<Result<Vec<Geometry<f64>>, String>>()?,
+            )))
+        }
+        _ => wkt::conversion::try_into_geometry(item).map_err(|err| err.to_string()),
+    }
+}
+
+/// Writes a Geometry as WKT.
+///
+/// Unlike `ToWkt` for a Geometry, this also writes the members of a
+/// GEOMETRYCOLLECTION, MULTIPOINTs, and Lines (as a LINESTRING). Empty
+/// members of multi geometries, and empty interior rings, are written as EMPTY.
+///
+pub fn geometry_to_wkt(geom: &Geometry<f64>) -> String {
+    match geom {
+        Geometry::GeometryCollection(collection) => {
+            if collection.0.is_empty() {
+                return "GEOMETRYCOLLECTION EMPTY".into();
+            }
+            format!(
+                "GEOMETRYCOLLECTION({})",
+                collection
+                    .0
+                    .iter()
+                    .map(geometry_to_wkt)
+                    .collect::<Vec<String>>()
+                    .join(",")
+            )
+        }
+        Geometry::MultiPoint(points) => points.to_wkt(),
+        Geometry::Line(line) => LineString(vec![line.start, line.end]).to_wkt(),
+        Geometry::Polygon(poly)
+            if !poly.exterior().0.is_empty()
+                && poly.interiors().iter().any(|ring| ring.0.is_empty()) =>
+        {
+            wkt_with_members(
+                "POLYGON",
+                std::iter::once(poly.exterior())
+                    .chain(poly.interiors())
+                    .map(|ring| Geometry::LineString(ring.clone())),
+            )
+        }
+        Geometry::MultiLineString(lines) if lines.0.iter().any(|line| line.0.is_empty()) => {
+            wkt_with_members(
+                "MULTILINESTRING",
+                lines
+                    .0
+                    .iter()
+                    .map(|line| Geometry::LineString(line.clone())),
+            )
+        }
+        Geometry::MultiPolygon(polys)
+            if polys.0.iter().any(|poly| poly.exterior().0.is_empty()) =>
+        {
+            wkt_with_members(
+                "MULTIPOLYGON",
+                polys.0.iter().map(|poly| Geometry::Polygon(poly.clone())),
+            )
+        }
+        _ => geom.to_wkt(),
+    }
+}
+
+/// Writes a multi geometry (or polygon) from its members, without their own
+/// type names, so that an empty member is written as EMPTY.
+fn wkt_with_members(name: &str, members: impl Iterator<Item = Geometry<f64>>) -> String {
+    format!(
+        "{}({})",
+        name,
+        members
+            .map(|member| {
+                let wkt = geometry_to_wkt(&member);
+                wkt.trim_start_matches(char::is_alphabetic)
+                    .trim_start()
+                    .to_string()
+            })
+            .collect::<Vec<String>>()
+            .join(",")
+    )
+}
+
+/// Parses an SVG element into a GeometryCollection.
+/// This function can read a <path>, <polygon>. <polyline>,
+/// <rect>, and <line>, all other SVG elements will return
+/// an error.
+///
+/// The `transform` attributes of the element and of its parent `<g>` groups
+/// are applied, see `svg_reader::read_svg`.
+///
+pub fn svg_to_geometry_collection(
+    svg: &str,
+) -> Result<GeometryCollection<f64>, GeometryToolsError> {
+    svg_reader::read_svg(svg, &SvgReadOptions::default())
+}
+
+/// Parses an SVG element into a Geometry, which is a GeometryCollection
+/// only when the element holds more than one geometry.
+///
+pub fn svg_to_geometry_or_collection(svg: &str) -> Result<Geometry<f64>, GeometryToolsError> {
+    let mut geom = svg_to_geometry_collection(svg)?;
+    if geom.0.len() == 1 {
+        return Ok(geom.0.remove(0));
+    }
+    Ok(Geometry::GeometryCollection(geom))
+}
+
+/// Parses an SVG element into a single Geometry.
+/// It returns an error if the SVG element can only be represented
+/// by a GeometryCollection.
+///
+pub fn svg_to_geometry(svg: &str) -> Result<Geometry<f64>, GeometryToolsError> {
+    let mut geom = svg_to_geometry_collection(svg)?;
+    if geom.0.len() == 1 {
+        return Ok(geom.0.remove(0));
+    }
+    Err(json_errors::svg_error::could_not_parse(
+        svg,
+        "The SVG could only be parsed as a GEOMETRYCOLLECTION",
+    ))
+}
+
+/// Parses an SVG element into a single Geometry.
+/// Polygons and multi polygons are repaired on the way, and an
+/// SVG that yields more than one geometry returns an error.
+///
+pub fn svg_to_repaired_geometry(svg: &str) -> Result<Geometry<f64>, GeometryToolsError> {
+    svg_to_repaired_geometry_with_options(svg, &SvgReadOptions::default())
+}
+
+/// Parses an SVG element into a single repaired Geometry (see `svg_to_repaired_geometry`),
+/// reading it with the given options.
+///
+pub fn svg_to_repaired_geometry_with_options(
+    svg: &str,
+    options: &SvgReadOptions,
+) -> Result<Geometry<f64>, GeometryToolsError> {
+    let mut geom = svg_reader::read_svg(svg, options)?;
+    if geom.0.len() == 1 {
+        return repair_svg_geometry(svg, geom.0.remove(0));
+    }
+
+    Err(json_errors::svg_error::could_not_parse(
+        svg,
+        "The SVG contains more than one geometry",
+    ))
+}
+
+/// Repairs the polygons and multi polygons read from an SVG
+fn repair_svg_geometry(
+    svg: &str,
+    geom: Geometry<f64>,
+) -> Result<Geometry<f64>, GeometryToolsError> {
+    let repaired = match geom {
+        Geometry::MultiPolygon(polys) => polys.repair().map(Geometry::from),
+        Geometry::Polygon(poly) => poly.repair().map(Geometry::from),
+        geom => Some(geom),
+    };
+    repaired.ok_or_else(|| json_errors::geometry_processing_error::irreparable_geom(svg))
+}
+
+/// Reads every shape element of an SVG document (see `svg_reader::read_svg_document`).
+/// Polygons and multi polygons are repaired on the way.
+///
+pub fn svg_document_to_elements(
+    svg: &str,
+    options: &SvgReadOptions,
+) -> Result<Vec<SvgDocumentElement>, GeometryToolsError> {
+    svg_reader::read_svg_document(svg, options)?
+        .into_iter()
+        .map(|element| {
+            Ok(SvgDocumentElement {
+                geometry: repair_svg_geometry(svg, element.geometry)?,
+                ..element
+            })
+        })
+        .collect()
+}
+
+/// Reads every shape element of an SVG document into a GeometryCollection,
+/// which is empty when the document has no shapes.
+/// Polygons and multi polygons are repaired on the way.
+///
+pub fn svg_document_to_geometry(
+    svg: &str,
+    options: &SvgReadOptions,
+) -> Result<GeometryCollection<f64>, GeometryToolsError> {
+    Ok(GeometryCollection(
+        svg_document_to_elements(svg, options)?
+            .into_iter()
+            .map(|element| element.geometry)
+            .collect(),
+    ))
+}
+
+/// Convert every shape element of an SVG document into a WKT GEOMETRYCOLLECTION.
+///
+pub fn svg_document_to_wkt(
+    svg: &str,
+    options: &SvgReadOptions,
+) -> Result<String, GeometryToolsError> {
+    Ok(geometry_to_wkt(&Geometry::GeometryCollection(
+        svg_document_to_geometry(svg, options)?,
+    )))
+}
+
+/// Convert every shape element of an SVG document into WKT, listed as JSON
+/// together with the attributes that identify each element, e.g.,
+/// `[{"class":null,"data":{"artefact":"12"},"element":"path","id":"roi-1","wkt":"POLYGON(...)"}]`.
+///
+/// `id` and `class` are null when the element does not have them, and `data`
+/// holds the `data-*` attributes by their name without the prefix.
+///
+pub fn svg_document_to_wkt_elements(
+    svg: &str,
+    options: &SvgReadOptions,
+) -> Result<String, GeometryToolsError> {
+    let elements = svg_document_to_elements(svg, options)?
+        .iter()
+        .map(|element| {
+            serde_json::json!({
+                "element": element.element,
+                "id": element.id,
+                "class": element.class,
+                "data": element
+                    .data
+                    .iter()
+                    .map(|(key, value)| (key.clone(), serde_json::Value::String(value.clone())))
+                    .collect::<serde_json::Map<String, serde_json::Value>>(),
+                "wkt": geometry_to_wkt(&element.geometry),
+            })
+        })
+        .collect::<Vec<serde_json::Value>>();
+    Ok(serde_json::Value::Array(elements).to_string())
+}
+
+/// Convert an SVG element into a WKT representation.
+/// Polygons and multi polygons are repaired on the way.
+///
+pub fn svg_to_wkt(svg: &str) -> Result<String, GeometryToolsError> {
+    svg_to_wkt_with_options(svg, &SvgReadOptions::default(), Precision::Full)
+}
+
+/// Convert an SVG element into a WKT representation, reading it with the given options
+/// and rounding the coordinates to the given precision (see `geometry_with_precision`).
+/// Polygons and multi polygons are repaired on the way.
+///
+pub fn svg_to_wkt_with_options(
+    svg: &str,
+    options: &SvgReadOptions,
+    precision: Precision,
+) -> Result<String, GeometryToolsError> {
+    Ok(geometry_to_wkt(&geometry_with_precision(
+        &svg_to_repaired_geometry_with_options(svg, options)?,
+        precision,
+    )))
+}
+
+/// Convert an SVG element into WKT that keeps its curves, as a CURVEPOLYGON,
+/// COMPOUNDCURVE, or their multi variants (see `svg_path::path_to_curve_wkt`).
+///
+/// Elements without curves are converted as in `svg_to_wkt_with_options`.
+/// The curved WKT is not repaired, and it cannot be read back by `wkt_to_geometry`.
+///
+pub fn svg_to_curve_wkt(
+    svg: &str,
+    options: &SvgReadOptions,
+    precision: Precision,
+) -> Result<String, GeometryToolsError> {
+    match svg_reader::read_svg_as_curve_wkt(svg, options, precision)? {
+        Some(wkt) => Ok(wkt),
+        None => svg_to_wkt_with_options(svg, options, precision),
+    }
+}
+
+/// Convert an SVG <path> d-string into a WKT representation.
+///
+pub fn svg_path_string_to_wkt(d_string: &str) -> Result<String, GeometryToolsError> {
+    svg_to_wkt(&format!("<path d=\"{}\"/>", d_string))
+}
+
+/// Convert an SVG <path> d-string into a WKT representation (see `svg_to_wkt_with_options`).
+///
+pub fn svg_path_string_to_wkt_with_options(
+    d_string: &str,
+    options: &SvgReadOptions,
+    precision: Precision,
+) -> Result<String, GeometryToolsError> {
+    svg_to_wkt_with_options(&format!("<path d=\"{}\"/>", d_string), options, precision)
+}
+
+/// Convert an SVG <path> d-string into WKT that keeps its curves (see `svg_to_curve_wkt`).
+///
+pub fn svg_path_string_to_curve_wkt(
+    d_string: &str,
+    options: &SvgReadOptions,
+    precision: Precision,
+) -> Result<String, GeometryToolsError> {
+    svg_to_curve_wkt(&format!("<path d=\"{}\"/>", d_string), options, precision)
+}
+
+/// Writes a Geometry as SVG elements.
+///
+/// Unlike `ToSvg` for a Geometry, every member of a GEOMETRYCOLLECTION is
+/// written as its own element (one per line), and a Line is written as a
+/// `<polyline>`. Points and empty geometries have no SVG element and are left out.
+///
+pub fn geometry_to_svg(geom: &Geometry<f64>) -> String {
+    match geom {
+        Geometry::GeometryCollection(collection) => collection
+            .0
+            .iter()
+            .map(geometry_to_svg)
+            .filter(|svg| !svg.is_empty())
+            .collect::<Vec<String>>()
+            .join("\n"),
+        Geometry::Line(line) => LineString(vec![line.start, line.end]).to_svg(),
+        _ => geom.to_svg(),
+    }
+}
+
+/// Writes a Geometry as a single SVG <path> d-string, in which every member
+/// of a GEOMETRYCOLLECTION becomes one or more subpaths.
+///
+pub fn geometry_to_svg_path_string(geom: &Geometry<f64>) -> String {
+    match geom {
+        Geometry::GeometryCollection(collection) => collection
+            .0
+            .iter()
+            .map(geometry_to_svg_path_string)
+            .collect::<Vec<String>>()
+            .join(""),
+        Geometry::Line(line) => LineString(vec![line.start, line.end]).to_svg_string(),
+        _ => geom.to_svg_string(),
+    }
+}
+
+/// Converts a WKT geometry into SVG elements (see `geometry_to_svg`).
+///
+pub fn wkt_to_svg(wkt: &str) -> Result<String, GeometryToolsError> {
+    wkt_to_svg_with_precision(wkt, Precision::Full)
+}
+
+/// Converts a WKT geometry into SVG elements, rounding the coordinates
+/// to the given precision (see `geometry_with_precision`).
+///
+pub fn wkt_to_svg_with_precision(
+    wkt: &str,
+    precision: Precision,
+) -> Result<String, GeometryToolsError> {
+    Ok(geometry_to_svg(&geometry_with_precision(
+        &wkt_to_geometry(wkt)?,
+        precision,
+    )))
+}
+
+/// Converts a WKT geometry into an SVG <path> d-string (see `geometry_to_svg_path_string`).
+///
+pub fn wkt_to_svg_path_string(wkt: &str) -> Result<String, GeometryToolsError> {
+    wkt_to_svg_path_string_with_precision(wkt, Precision::Full)
+}
+
+/// Converts a WKT geometry into an SVG <path> d-string, rounding the coordinates
+/// to the given precision (see `geometry_with_precision`).
+///
+pub fn wkt_to_svg_path_string_with_precision(
+    wkt: &str,
+    precision: Precision,
+) -> Result<String, GeometryToolsError> {
+    Ok(geometry_to_svg_path_string(&geometry_with_precision(
+        &wkt_to_geometry(wkt)?,
+        precision,
+    )))
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_read_wkt_geometry() {
+        let geom = wkt_to_geometry("POLYGON((0 0,10 0,10 10,0 10,0 0))");
+        assert!(geom.is_ok());
+        assert!(geom.unwrap().into_polygon().is_some());
+    }
+
+    #[test]
+    fn errors_on_malformed_wkt() {
+        let geom = wkt_to_geometry("POLYGON((0 0,10 0,10 10,0 10,0 0)");
+        assert_eq!(
+            Err(GeometryToolsError::WktParse {
+                input: "POLYGON((0 0,10 0,10 10,0 10,0 0)".into(),
+                reason: "Missing closing parenthesis for type".into(),
+            }),
+            geom
+        );
+    }
+
+    #[test]
+    fn errors_on_empty_wkt() {
+        let geom = wkt_to_geometry("");
+        assert!(geom.is_err());
+        assert_eq!("WKT_PARSE_ERROR", geom.unwrap_err().code());
+    }
+
+    #[test]
+    fn errors_on_unsupported_svg_element() {
+        let wkt = svg_to_wkt(r#"<circle cx="5" cy="5" r="4"/>"#);
+        assert!(wkt.is_err());
+        assert_eq!("SVG_PARSE_ERROR", wkt.unwrap_err().code());
+    }
+
+    #[test]
+    fn flattens_curves_with_a_tolerance() {
+        let options = SvgReadOptions {
+            tolerance: Some(0.5),
+            ..Default::default()
+        };
+        let wkt = svg_path_string_to_wkt_with_options(
+            "M0 0L20 0A10 10 0 0 1 0 0Z",
+            &options,
+            Precision::Full,
+        );
+        let geom = wkt_to_geometry(&wkt.unwrap()).unwrap();
+        let rect = crate::geometry_information::geometry_bounding_rect(&geom).unwrap();
+        assert!((rect.max.y - 10.).abs() <= 0.5);
+
+        let scaled = r#"<path d="M0 0Q5 10 10 0Z" transform="scale(10)"/>"#;
+        let coarse = svg_to_wkt_with_options(scaled, &options, Precision::Full).unwrap();
+        let unscaled =
+            svg_path_string_to_wkt_with_options("M0 0Q5 10 10 0Z", &options, Precision::Full)
+                .unwrap();
+        assert!(coarse.matches(',').count() > unscaled.matches(',').count());
+
+        let options = SvgReadOptions {
+            tolerance: Some(-1.),
+            ..Default::default()
+        };
+        assert_eq!(
+            "INVALID_ARGUMENT",
+            svg_path_string_to_wkt_with_options("M0 0L1 1", &options, Precision::Full)
+                .unwrap_err()
+                .code()
+        );
+    }
+
+    #[test]
+    fn keeps_curves_as_curve_wkt() {
+        let options = SvgReadOptions::default();
+        let wkt = svg_path_string_to_curve_wkt("M0 0C0 10 10 10 10 0", &options, Precision::Full)
+            .unwrap();
+        assert!(wkt.starts_with("COMPOUNDCURVE(CIRCULARSTRING(0 0,"));
+        assert_eq!(
+            "POLYGON((0 0,10 0,10 10,0 0))",
+            svg_path_string_to_curve_wkt("M0 0L10 0L10 10Z", &options, Precision::Full).unwrap()
+        );
+    }
+
+    #[test]
+    fn converts_whole_svg_documents() {
+        let svg = r#"<svg>
+            <path id="roi-1" data-artefact="12" d="M0 0L10 0L10 10L0 10Z"/>
+            <g transform="translate(20 0)"><polyline class="line" points="0 0,5 5,10 0"/></g>
+        </svg>"#;
+        let options = SvgReadOptions::default();
+        assert_eq!(
+            "GEOMETRYCOLLECTION(POLYGON((0 0,10 0,10 10,0 10,0 0)),LINESTRING(20 0,25 5,30 0))",
+            svg_document_to_wkt(svg, &options).unwrap()
+        );
+        assert_eq!(
+            r#"[{"class":null,"data":{"artefact":"12"},"element":"path","id":"roi-1","wkt":"POLYGON((0 0,10 0,10 10,0 10,0 0))"},{"class":"line","data":{},"element":"polyline","id":null,"wkt":"LINESTRING(20 0,25 5,30 0)"}]"#,
+            svg_document_to_wkt_elements(svg, &options).unwrap()
+        );
+        assert_eq!(
+            "GEOMETRYCOLLECTION EMPTY",
+            svg_document_to_wkt("<svg><title>Empty</title></svg>", &options).unwrap()
+        );
+    }
+
+    #[test]
+    fn reads_every_item_of_multi_item_wkt() {
+        let expected = "GEOMETRYCOLLECTION(POINT(1 2),POLYGON((0 0,1 0,1 1,0 0)))";
+        for wkt in [
+            "POINT(1 2) POLYGON((0 0,1 0,1 1,0 0))",
+            "POINT(1 2), POLYGON ((0 0,1 0,1 1,0 0))",
+            "SRID=4326;POINT(1 2);POLYGON((0 0,1 0,1 1,0 0))",
+        ]
+        .iter()
+        {
+            assert_eq!(expected, geometry_to_wkt(&wkt_to_geometry(wkt).unwrap()));
+        }
+        assert_eq!(
+            "GEOMETRYCOLLECTION(POLYGON EMPTY,LINESTRING(0 0,1 1))",
+            geometry_to_wkt(&wkt_to_geometry("POLYGON EMPTY LINESTRING(0 0,1 1)").unwrap())
+        );
+        assert!(wkt_to_geometry("POINT(1 2) POINT(3)").is_err());
+    }
+
+    #[test]
+    fn reads_empty_wkt_geometries() {
+        assert_eq!(
+            "MULTIPOINT EMPTY",
+            geometry_to_wkt(&wkt_to_geometry("POINT EMPTY").unwrap())
+        );
+        assert_eq!(
+            "GEOMETRYCOLLECTION(MULTIPOINT EMPTY,POINT(1 2))",
+            geometry_to_wkt(
+                &wkt_to_geometry("GEOMETRYCOLLECTION(POINT EMPTY,POINT(1 2))").unwrap()
+            )
+        );
+        assert_eq!(
+            "GEOMETRYCOLLECTION(GEOMETRYCOLLECTION(MULTIPOINT EMPTY))",
+            geometry_to_wkt(
+                &wkt_to_geometry("GEOMETRYCOLLECTION(GEOMETRYCOLLECTION(POINT EMPTY))").unwrap()
+            )
+        );
+        for wkt in [
+            "GEOMETRYCOLLECTION EMPTY",
+            "POLYGON EMPTY",
+            "MULTIPOLYGON EMPTY",
+        ]
+        .iter()
+        {
+            assert_eq!("", wkt_to_svg(wkt).unwrap());
+            assert_eq!("", wkt_to_svg_path_string(wkt).unwrap());
+        }
+    }
+
+    #[test]
+    fn round_trips_empty_members_of_wkt() {
+        for wkt in [
+            "MULTIPOLYGON(EMPTY,((0 0,1 0,1 1,0 0)))",
+            "MULTIPOLYGON(((0 0,1 0,1 1,0 0)),EMPTY)",
+            "MULTIPOLYGON(((0 0,4 0,4 4,0 0),EMPTY),EMPTY)",
+            "MULTILINESTRING(EMPTY,(0 0,1 1))",
+            "POLYGON((0 0,4 0,4 4,0 0),EMPTY)",
+            "GEOMETRYCOLLECTION(MULTIPOLYGON(EMPTY),POLYGON EMPTY)",
+        ]
+        .iter()
+        {
+            let written = geometry_to_wkt(&wkt_to_geometry(wkt).unwrap());
+            assert_eq!(*wkt, written);
+            assert_eq!(
+                written,
+                geometry_to_wkt(&wkt_to_geometry(&written).unwrap())
+            );
+        }
+    }
+
+    #[test]
+    fn writes_collection_members_as_svg_elements() {
+        let wkt = "GEOMETRYCOLLECTION(POLYGON((0 0,1 0,1 1,0 0)),POINT(5 5),LINESTRING(0 0,2 2))";
+        assert_eq!(
+            "<path d=\"M0 0L1 0L1 1L0 0\"/>\n<polyline points=\"0,0 2,2\"/>",
+            wkt_to_svg(wkt).unwrap()
+        );
+        assert_eq!(
+            "M0 0L1 0L1 1L0 0M0 0L2 2",
+            wkt_to_svg_path_string(wkt).unwrap()
+        );
+        assert_eq!(
+            wkt_to_svg(wkt).unwrap(),
+            wkt_to_svg("POLYGON((0 0,1 0,1 1,0 0)) LINESTRING(0 0,2 2)").unwrap()
+        );
+    }
+
+    #[test]
+    fn writes_output_with_precision() {
+        let wkt = "POLYGON((0 0,1023.9999999999998 0,1024 0.4,1024 512.25,0 512.25,0 0))";
+        assert_eq!(
+            "<path d=\"M0 0L1024 0L1024 0.4L1024 512.25L0 512.25L0 0\"/>",
+            wkt_to_svg_with_precision(wkt, Precision::DecimalPlaces(3)).unwrap()
+        );
+        assert_eq!(
+            "M0 0L1024 0L1024 512L0 512L0 0",
+            wkt_to_svg_path_string_with_precision(wkt, Precision::IntegerGrid).unwrap()
+        );
+        let options = SvgReadOptions::default();
+        assert_eq!(
+            "POLYGON((0.333 0,1 0,1 1,0.333 0))",
+            svg_path_string_to_wkt_with_options(
+                "M0.33333 0L1 0L1 1Z",
+                &options,
+                Precision::SignificantDigits(3)
+            )
+            .unwrap()
+        );
+        assert!(svg_path_string_to_curve_wkt(
+            "M0 0C0 10 10 10 10 0",
+            &options,
+            Precision::IntegerGrid
+        )
+        .unwrap()
+        .chars()
+        .all(|c| c != '.'));
+    }
+}
@@ -0,0 +1,809 @@
+use crate::geometry_precision::Precision;
+use crate::geometry_transform::AffineTransform;
+use geo::algorithm::contains::Contains;
+use geo_types::{Coordinate, LineString, Point, Polygon};
+use std::f64::consts::PI;
+use svgtypes::{PathParser, PathSegment};
+
+/// The deepest a curve is split while looking for a good enough approximation
+const MAX_DEPTH: u32 = 16;
+
+/// The finest tolerance used for a curve, as a fraction of its size. Finer
+/// tolerances are raised to this, which keeps a curve to a few thousand points.
+pub const MIN_RELATIVE_TOLERANCE: f64 = 1e-6;
+
+/// A single drawing command of a path, in absolute coordinates
+#[derive(Debug, Clone, Copy)]
+enum Segment {
+    Line(Coordinate<f64>, Coordinate<f64>),
+    Quadratic(Coordinate<f64>, Coordinate<f64>, Coordinate<f64>),
+    Cubic(
+        Coordinate<f64>,
+        Coordinate<f64>,
+        Coordinate<f64>,
+        Coordinate<f64>,
+    ),
+    Arc {
+        start: Coordinate<f64>,
+        end: Coordinate<f64>,
+        center: Coordinate<f64>,
+        radii: (f64, f64),
+        rotation: f64,
+        start_angle: f64,
+        sweep_angle: f64,
+    },
+}
+
+impl Segment {
+    fn start(&self) -> Coordinate<f64> {
+        match *self {
+            Segment::Line(start, _)
+            | Segment::Quadratic(start, _, _)
+            | Segment::Cubic(start, _, _, _)
+            | Segment::Arc { start, .. } => start,
+        }
+    }
+
+    fn end(&self) -> Coordinate<f64> {
+        match *self {
+            Segment::Line(_, end)
+            | Segment::Quadratic(_, _, end)
+            | Segment::Cubic(_, _, _, end)
+            | Segment::Arc { end, .. } => end,
+        }
+    }
+
+    fn is_curve(&self) -> bool {
+        !matches!(self, Segment::Line(..))
+    }
+
+    /// Finds the point at `t` (from 0 to 1) along the segment
+    fn point_at(&self, t: f64) -> Coordinate<f64> {
+        if t <= 0. {
+            return self.start();
+        }
+        if t >= 1. {
+            return self.end();
+        }
+        let u = 1. - t;
+        match *self {
+            Segment::Line(p0, p1) => weighted(&[(p0, u), (p1, t)]),
+            Segment::Quadratic(p0, p1, p2) => {
+                weighted(&[(p0, u * u), (p1, 2. * u * t), (p2, t * t)])
+            }
+            Segment::Cubic(p0, p1, p2, p3) => weighted(&[
+                (p0, u * u * u),
+                (p1, 3. * u * u * t),
+                (p2, 3. * u * t * t),
+                (p3, t * t * t),
+            ]),
+            Segment::Arc {
+                center,
+                radii,
+                rotation,
+                start_angle,
+                sweep_angle,
+                ..
+            } => {
+                let (sin_angle, cos_angle) = (start_angle + t * sweep_angle).sin_cos();
+                let (sin_rot, cos_rot) = rotation.sin_cos();
+                Coordinate {
+                    x: center.x + radii.0 * cos_rot * cos_angle - radii.1 * sin_rot * sin_angle,
+                    y: center.y + radii.0 * sin_rot * cos_angle + radii.1 * cos_rot * sin_angle,
+                }
+            }
+        }
+    }
+}
+
+fn weighted(points: &[(Coordinate<f64>, f64)]) -> Coordinate<f64> {
+    points
+        .iter()
+        .fold(Coordinate { x: 0., y: 0. }, |sum, (point, weight)| {
+            Coordinate {
+                x: sum.x + point.x * weight,
+                y: sum.y + point.y * weight,
+            }
+        })
+}
+
+/// A run of segments that starts with a moveto
+#[derive(Debug, Clone)]
+struct SubPath {
+    start: Coordinate<f64>,
+    segments: Vec<Segment>,
+    closed: bool,
+}
+
+/// Reads a path d-string into its subpaths
+fn parse_path(d_string: &str) -> Result<Vec<SubPath>, String> {
+    let mut paths = vec![] as Vec<SubPath>;
+    let mut current = Coordinate { x: 0., y: 0. };
+    // The control point that S and T commands reflect, when the previous segment has one
+    let mut last_cubic_control = None as Option<Coordinate<f64>>;
+    let mut last_quadratic_control = None as Option<Coordinate<f64>>;
+
+    for token in PathParser::from(d_string) {
+        let token = token.map_err(|e| e.to_string())?;
+        let absolute = move |abs: bool, x: f64, y: f64| {
+            if abs {
+                Coordinate { x, y }
+            } else {
+                Coordinate {
+                    x: current.x + x,
+                    y: current.y + y,
+                }
+            }
+        };
+        let segment = match token {
+            PathSegment::MoveTo { abs, x, y } => {
+                current = absolute(abs, x, y);
+                paths.push(SubPath {
+                    start: current,
+                    segments: vec![],
+                    closed: false,
+                });
+                last_cubic_control = None;
+                last_quadratic_control = None;
+                continue;
+            }
+            PathSegment::ClosePath { .. } => {
+                if let Some(path) = paths.last_mut() {
+                    path.closed = true;
+                    current = path.start;
+                }
+                last_cubic_control = None;
+                last_quadratic_control = None;
+                continue;
+            }
+            PathSegment::LineTo { abs, x, y } => Segment::Line(current, absolute(abs, x, y)),
+            PathSegment::HorizontalLineTo { abs, x } => Segment::Line(
+                current,
+                Coordinate {
+                    x: if abs { x } else { current.x + x },
+                    y: current.y,
+                },
+            ),
+            PathSegment::VerticalLineTo { abs, y } => Segment::Line(
+                current,
+                Coordinate {
+                    x: current.x,
+                    y: if abs { y } else { current.y + y },
+                },
+            ),
+            PathSegment::CurveTo {
+                abs,
+                x1,
+                y1,
+                x2,
+                y2,
+                x,
+                y,
+            } => Segment::Cubic(
+                current,
+                absolute(abs, x1, y1),
+                absolute(abs, x2, y2),
+                absolute(abs, x, y),
+            ),
+            PathSegment::SmoothCurveTo { abs, x2, y2, x, y } => Segment::Cubic(
+                current,
+                reflect(last_cubic_control, current),
+                absolute(abs, x2, y2),
+                absolute(abs, x, y),
+            ),
+            PathSegment::Quadratic { abs, x1, y1, x, y } => {
+                Segment::Quadratic(current, absolute(abs, x1, y1), absolute(abs, x, y))
+            }
+            PathSegment::SmoothQuadratic { abs, x, y } => Segment::Quadratic(
+                current,
+                reflect(last_quadratic_control, current),
+                absolute(abs, x, y),
+            ),
+            PathSegment::EllipticalArc {
+                abs,
+                rx,
+                ry,
+                x_axis_rotation,
+                large_arc,
+                sweep,
+                x,
+                y,
+            } => arc(
+                current,
+                absolute(abs, x, y),
+                (rx, ry),
+                x_axis_rotation,
+                large_arc,
+                sweep,
+            ),
+        };
+
+        // A path that continues after a closepath starts a new subpath at the same point
+        if paths.last().is_none_or(|path| path.closed) {
+            paths.push(SubPath {
+                start: current,
+                segments: vec![],
+                closed: false,
+            });
+        }
+        last_cubic_control = match segment {
+            Segment::Cubic(_, _, control, _) => Some(control),
+            _ => None,
+        };
+        last_quadratic_control = match segment {
+            Segment::Quadratic(_, control, _) => Some(control),
+            _ => None,
+        };
+        current = segment.end();
+        paths.last_mut().unwrap().segments.push(segment);
+    }
+    Ok(paths)
+}
+
+fn reflect(control: Option<Coordinate<f64>>, about: Coordinate<f64>) -> Coordinate<f64> {
+    match control {
+        Some(control) => Coordinate {
+            x: 2. * about.x - control.x,
+            y: 2. * about.y - control.y,
+        },
+        None => about,
+    }
+}
+
+/// Converts an SVG endpoint arc into its center parameterization
+/// (see https://www.w3.org/TR/SVG11/implnote.html#ArcImplementationNotes)
+fn arc(
+    start: Coordinate<f64>,
+    end: Coordinate<f64>,
+    radii: (f64, f64),
+    rotation: f64,
+    large_arc: bool,
+    sweep: bool,
+) -> Segment {
+    let (mut rx, mut ry) = (radii.0.abs(), radii.1.abs());
+    if rx == 0. || ry == 0. || start == end {
+        return Segment::Line(start, end);
+    }
+    let rotation = rotation.to_radians();
+    let (sin_rot, cos_rot) = rotation.sin_cos();
+    let (dx, dy) = ((start.x - end.x) / 2., (start.y - end.y) / 2.);
+    let x1 = cos_rot * dx + sin_rot * dy;
+    let y1 = -sin_rot * dx + cos_rot * dy;
+
+    // Radii that are too small are scaled up until the arc fits
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1. {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+    let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let sign = if large_arc == sweep { -1. } else { 1. };
+    let coefficient = sign * (numerator / denominator).max(0.).sqrt();
+    let cx1 = coefficient * rx * y1 / ry;
+    let cy1 = -coefficient * ry * x1 / rx;
+
+    let center = Coordinate {
+        x: cos_rot * cx1 - sin_rot * cy1 + (start.x + end.x) / 2.,
+        y: sin_rot * cx1 + cos_rot * cy1 + (start.y + end.y) / 2.,
+    };
+    let start_angle = ((y1 - cy1) / ry).atan2((x1 - cx1) / rx);
+    let end_angle = ((-y1 - cy1) / ry).atan2((-x1 - cx1) / rx);
+    let mut sweep_angle = end_angle - start_angle;
+    if sweep && sweep_angle < 0. {
+        sweep_angle += 2. * PI;
+    } else if !sweep && sweep_angle > 0. {
+        sweep_angle -= 2. * PI;
+    }
+
+    Segment::Arc {
+        start,
+        end,
+        center,
+        radii: (rx, ry),
+        rotation,
+        start_angle,
+        sweep_angle,
+    }
+}
+
+/// Checks that a tolerance can be used to approximate curves
+pub fn is_valid_tolerance(tolerance: f64) -> bool {
+    tolerance.is_finite() && tolerance > 0.
+}
+
+/// The tolerance used for a curve: the given one, but no finer than
+/// `MIN_RELATIVE_TOLERANCE` of the size of the curve
+fn curve_tolerance(point: &dyn Fn(f64) -> Coordinate<f64>, tolerance: f64) -> f64 {
+    let samples = (0..=8)
+        .map(|step| point(step as f64 / 8.))
+        .collect::<Vec<Coordinate<f64>>>();
+    let (min, max) = samples
+        .iter()
+        .fold((samples[0], samples[0]), |(min, max), p| {
+            (
+                Coordinate {
+                    x: min.x.min(p.x),
+                    y: min.y.min(p.y),
+                },
+                Coordinate {
+                    x: max.x.max(p.x),
+                    y: max.y.max(p.y),
+                },
+            )
+        });
+    tolerance.max((max.x - min.x).hypot(max.y - min.y) * MIN_RELATIVE_TOLERANCE)
+}
+
+/// Rewrites a path d-string with straight lines only.
+///
+/// Curves are split into straight chords until no chord is further than
+/// `tolerance` from the curve, and elliptical arcs are supported. A tolerance
+/// finer than `MIN_RELATIVE_TOLERANCE` of the size of a curve is raised to
+/// that. The result only holds M, L and Z commands, and every closed subpath
+/// ends back on its first point.
+///
+pub fn flatten_path(d_string: &str, tolerance: f64) -> Result<String, String> {
+    let mut flattened = String::new();
+    for path in parse_path(d_string)? {
+        let mut points = vec![path.start];
+        for segment in &path.segments {
+            flatten_segment(segment, tolerance, &mut points);
+        }
+        if path.closed && points.last() != Some(&path.start) {
+            points.push(path.start);
+        }
+        if points.len() < 2 {
+            continue;
+        }
+        for (index, point) in points.iter().enumerate() {
+            let command = if index == 0 { 'M' } else { 'L' };
+            flattened.push_str(&format!("{}{} {}", command, point.x, point.y));
+        }
+    }
+    Ok(flattened)
+}
+
+fn flatten_segment(segment: &Segment, tolerance: f64, points: &mut Vec<Coordinate<f64>>) {
+    if !segment.is_curve() {
+        points.push(segment.end());
+        return;
+    }
+    let tolerance = curve_tolerance(&|t| segment.point_at(t), tolerance);
+    // The curve is split into quarters first, so S shaped curves
+    // cannot hide behind a midpoint that happens to sit on the chord
+    for quarter in 0..4 {
+        let (t0, t1) = (quarter as f64 / 4., (quarter + 1) as f64 / 4.);
+        flatten_span(
+            segment,
+            tolerance,
+            (t0, segment.point_at(t0)),
+            (t1, segment.point_at(t1)),
+            0,
+            points,
+        );
+    }
+}
+
+fn flatten_span(
+    segment: &Segment,
+    tolerance: f64,
+    (t0, p0): (f64, Coordinate<f64>),
+    (t1, p1): (f64, Coordinate<f64>),
+    depth: u32,
+    points: &mut Vec<Coordinate<f64>>,
+) {
+    let tm = (t0 + t1) / 2.;
+    let pm = segment.point_at(tm);
+    if depth >= MAX_DEPTH || distance_to_chord(&pm, &p0, &p1) <= tolerance {
+        points.push(p1);
+        return;
+    }
+    flatten_span(segment, tolerance, (t0, p0), (tm, pm), depth + 1, points);
+    flatten_span(segment, tolerance, (tm, pm), (t1, p1), depth + 1, points);
+}
+
+fn distance_to_chord(
+    point: &Coordinate<f64>,
+    start: &Coordinate<f64>,
+    end: &Coordinate<f64>,
+) -> f64 {
+    let (dx, dy) = (end.x - start.x, end.y - start.y);
+    let length = dx.hypot(dy);
+    if length == 0. {
+        return (point.x - start.x).hypot(point.y - start.y);
+    }
+    ((point.x - start.x) * dy - (point.y - start.y) * dx).abs() / length
+}
+
+/// A piece of a curve approximation
+enum CurvePart {
+    /// A straight line to a point
+    Line(Coordinate<f64>),
+    /// A circular arc through a point to another point
+    Arc(Coordinate<f64>, Coordinate<f64>),
+}
+
+/// Rounds the points of the parts, leaving out the lines that no longer move
+fn round_parts(
+    start: Coordinate<f64>,
+    parts: Vec<CurvePart>,
+    precision: Precision,
+) -> (Coordinate<f64>, Vec<CurvePart>) {
+    let start = precision.round_coordinate(start);
+    let mut last = start;
+    let mut rounded = vec![];
+    for part in parts {
+        match part {
+            CurvePart::Line(end) => {
+                let end = precision.round_coordinate(end);
+                if end != last {
+                    rounded.push(CurvePart::Line(end));
+                    last = end;
+                }
+            }
+            CurvePart::Arc(mid, end) => {
+                last = precision.round_coordinate(end);
+                rounded.push(CurvePart::Arc(precision.round_coordinate(mid), last));
+            }
+        }
+    }
+    (start, rounded)
+}
+
+/// Writes a path d-string as curved WKT, or returns None when the path
+/// has no curves.
+///
+/// Every curve is approximated by circular arcs that stay within `tolerance`
+/// of the curve (circular SVG arcs are matched exactly), where a tolerance is
+/// no finer than `MIN_RELATIVE_TOLERANCE` of the size of the curve. Closed subpaths become
+/// the rings of a CURVEPOLYGON (a MULTISURFACE when there are several shells),
+/// where a ring inside an odd number of other rings is a hole. Open subpaths
+/// become a COMPOUNDCURVE (or MULTICURVE). A ring or line without arcs is
+/// written as a plain linestring.
+///
+/// The transform is applied before the curves are approximated, so the
+/// tolerance applies to the transformed coordinates. The written coordinates
+/// are rounded to the given precision.
+///
+pub fn path_to_curve_wkt(
+    d_string: &str,
+    tolerance: f64,
+    transform: &AffineTransform,
+    precision: Precision,
+) -> Result<Option<String>, String> {
+    let paths = parse_path(d_string)?;
+    if !paths
+        .iter()
+        .any(|path| path.segments.iter().any(Segment::is_curve))
+    {
+        return Ok(None);
+    }
+
+    let mut rings = vec![] as Vec<(String, Polygon<f64>)>;
+    let mut curves = vec![] as Vec<String>;
+    for path in &paths {
+        let start = apply(transform, path.start);
+        let mut parts = vec![];
+        for segment in &path.segments {
+            fit_segment(segment, tolerance, transform, &mut parts);
+        }
+        let (start, mut parts) = round_parts(start, parts, precision);
+        if path.closed {
+            let closes = match parts.last() {
+                Some(CurvePart::Line(end)) | Some(CurvePart::Arc(_, end)) => *end == start,
+                None => false,
+            };
+            if !closes {
+                parts.push(CurvePart::Line(start));
+            }
+            let mut flattened = vec![];
+            flatten_path_into(path, tolerance, transform, &mut flattened);
+            rings.push((
+                write_curve(start, &parts, false),
+                Polygon::new(LineString(flattened), vec![]),
+            ));
+        } else if !parts.is_empty() {
+            curves.push(write_curve(start, &parts, true));
+        }
+    }
+
+    let mut surfaces = vec![] as Vec<String>;
+    let depths = rings
+        .iter()
+        .enumerate()
+        .map(|(index, (_, ring))| ring_depth(index, ring, &rings))
+        .collect::<Vec<usize>>();
+    for (index, (shell, shell_ring)) in rings.iter().enumerate() {
+        if depths[index] % 2 == 1 {
+            continue;
+        }
+        let mut members = vec![shell.clone()];
+        for (hole_index, (hole, hole_ring)) in rings.iter().enumerate() {
+            if depths[hole_index] == depths[index] + 1 && contains_ring(shell_ring, hole_ring) {
+                members.push(hole.clone());
+            }
+        }
+        surfaces.push(format!("CURVEPOLYGON({})", members.join(",")));
+    }
+
+    let surfaces = match surfaces.len() {
+        0 => None,
+        1 => Some(surfaces.remove(0)),
+        _ => Some(format!("MULTISURFACE({})", surfaces.join(","))),
+    };
+    let curves = match curves.len() {
+        0 => None,
+        1 => Some(curves.remove(0)),
+        _ => Some(format!(
+            "MULTICURVE({})",
+            curves
+                .iter()
+                .map(|c| c.trim_start_matches("LINESTRING").to_string())
+                .collect::<Vec<String>>()
+                .join(",")
+        )),
+    };
+    Ok(match (surfaces, curves) {
+        (Some(surfaces), Some(curves)) => {
+            Some(format!("GEOMETRYCOLLECTION({},{})", surfaces, curves))
+        }
+        (Some(wkt), None) | (None, Some(wkt)) => Some(wkt),
+        (None, None) => None,
+    })
+}
+
+fn apply(transform: &AffineTransform, point: Coordinate<f64>) -> Coordinate<f64> {
+    let (x, y) = transform.apply(point.x, point.y);
+    Coordinate { x, y }
+}
+
+/// Counts how many other rings surround a ring
+fn ring_depth(index: usize, ring: &Polygon<f64>, rings: &[(String, Polygon<f64>)]) -> usize {
+    rings
+        .iter()
+        .enumerate()
+        .filter(|(other_index, (_, other))| *other_index != index && contains_ring(other, ring))
+        .count()
+}
+
+fn contains_ring(outer: &Polygon<f64>, inner: &Polygon<f64>) -> bool {
+    inner
+        .exterior()
+        .0
+        .iter()
+        .any(|point| outer.contains(&Point(*point)))
+}
+
+fn flatten_path_into(
+    path: &SubPath,
+    tolerance: f64,
+    transform: &AffineTransform,
+    points: &mut Vec<Coordinate<f64>>,
+) {
+    let mut local = vec![path.start];
+    for segment in &path.segments {
+        flatten_segment(segment, tolerance, &mut local);
+    }
+    points.extend(local.into_iter().map(|point| apply(transform, point)));
+}
+
+/// Approximates a segment by lines and circular arcs, after transforming it
+fn fit_segment(
+    segment: &Segment,
+    tolerance: f64,
+    transform: &AffineTransform,
+    parts: &mut Vec<CurvePart>,
+) {
+    if !segment.is_curve() {
+        parts.push(CurvePart::Line(apply(transform, segment.end())));
+        return;
+    }
+    let point = |t: f64| apply(transform, segment.point_at(t));
+    let tolerance = curve_tolerance(&point, tolerance);
+    fit_span(
+        &point,
+        tolerance,
+        (0., point(0.)),
+        (1., point(1.)),
+        0,
+        parts,
+    );
+}
+
+fn fit_span(
+    point: &dyn Fn(f64) -> Coordinate<f64>,
+    tolerance: f64,
+    (t0, p0): (f64, Coordinate<f64>),
+    (t1, p1): (f64, Coordinate<f64>),
+    depth: u32,
+    parts: &mut Vec<CurvePart>,
+) {
+    let tm = (t0 + t1) / 2.;
+    let pm = point(tm);
+    let quarters = [point((t0 + tm) / 2.), point((tm + t1) / 2.)];
+
+    if quarters
+        .iter()
+        .chain(std::iter::once(&pm))
+        .all(|q| distance_to_chord(q, &p0, &p1) <= tolerance)
+    {
+        parts.push(CurvePart::Line(p1));
+        return;
+    }
+    let fits = match circle_through(&p0, &pm, &p1) {
+        Some((center, radius)) => quarters
+            .iter()
+            .all(|q| ((q.x - center.x).hypot(q.y - center.y) - radius).abs() <= tolerance),
+        None => false,
+    };
+    if fits || depth >= MAX_DEPTH {
+        parts.push(CurvePart::Arc(pm, p1));
+        return;
+    }
+    fit_span(point, tolerance, (t0, p0), (tm, pm), depth + 1, parts);
+    fit_span(point, tolerance, (tm, pm), (t1, p1), depth + 1, parts);
+}
+
+/// Finds the center and radius of the circle through three points
+fn circle_through(
+    a: &Coordinate<f64>,
+    b: &Coordinate<f64>,
+    c: &Coordinate<f64>,
+) -> Option<(Coordinate<f64>, f64)> {
+    let d = 2. * (a.x * (b.y - c.y) + b.x * (c.y - a.y) + c.x * (a.y - b.y));
+    if d.abs() < f64::EPSILON {
+        return None;
+    }
+    let (a2, b2, c2) = (
+        a.x * a.x + a.y * a.y,
+        b.x * b.x + b.y * b.y,
+        c.x * c.x + c.y * c.y,
+    );
+    let center = Coordinate {
+        x: (a2 * (b.y - c.y) + b2 * (c.y - a.y) + c2 * (a.y - b.y)) / d,
+        y: (a2 * (c.x - b.x) + b2 * (a.x - c.x) + c2 * (b.x - a.x)) / d,
+    };
+    Some((center, (a.x - center.x).hypot(a.y - center.y)))
+}
+
+/// Writes a ring or line as WKT: a plain linestring when it has no arcs,
+/// and a COMPOUNDCURVE of linestrings and CIRCULARSTRINGs otherwise.
+fn write_curve(start: Coordinate<f64>, parts: &[CurvePart], tagged: bool) -> String {
+    let mut members = vec![] as Vec<String>;
+    let mut run = vec![start];
+    let mut run_is_arc = false;
+    let mut last = start;
+    for part in parts {
+        let (is_arc, points) = match part {
+            CurvePart::Line(end) => (false, vec![*end]),
+            CurvePart::Arc(mid, end) => (true, vec![*mid, *end]),
+        };
+        if is_arc != run_is_arc && run.len() > 1 {
+            members.push(write_run(&run, run_is_arc));
+            run = vec![last];
+        }
+        run_is_arc = is_arc;
+        last = *points.last().unwrap();
+        run.extend(points);
+    }
+    if run.len() > 1 {
+        members.push(write_run(&run, run_is_arc));
+    }
+
+    if members.len() == 1 && !run_is_arc {
+        let line = members.remove(0);
+        return if tagged {
+            format!("LINESTRING{}", line)
+        } else {
+            line
+        };
+    }
+    format!("COMPOUNDCURVE({})", members.join(","))
+}
+
+fn write_run(points: &[Coordinate<f64>], is_arc: bool) -> String {
+    let coordinates = points
+        .iter()
+        .map(|point| format!("{} {}", point.x, point.y))
+        .collect::<Vec<String>>()
+        .join(",");
+    if is_arc {
+        format!("CIRCULARSTRING({})", coordinates)
+    } else {
+        format!("({})", coordinates)
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn max_deviation_from_circle(d_string: &str, radius: f64) -> f64 {
+        let flattened = flatten_path(d_string, 0.01).unwrap();
+        parse_path(&flattened).unwrap()[0]
+            .segments
+            .iter()
+            .map(|segment| {
+                let mid = segment.point_at(0.5);
+                (radius - mid.x.hypot(mid.y)).abs()
+            })
+            .fold(0., f64::max)
+    }
+
+    #[test]
+    fn flattens_arcs_within_tolerance() {
+        let circle = "M10 0A10 10 0 0 1 -10 0A10 10 0 0 1 10 0Z";
+        assert!(max_deviation_from_circle(circle, 10.) <= 0.01);
+        let coarse = flatten_path(circle, 1.).unwrap();
+        let fine = flatten_path(circle, 0.001).unwrap();
+        assert!(coarse.matches('L').count() < fine.matches('L').count());
+    }
+
+    #[test]
+    fn limits_the_points_of_tiny_tolerances() {
+        let cubic = "M0 0C0 100 100 100 100 0";
+        let tiny = flatten_path(cubic, 1e-300).unwrap();
+        assert!(tiny.matches('L').count() <= 4096);
+        assert_eq!(
+            tiny,
+            flatten_path(cubic, 100f64.hypot(75.) * MIN_RELATIVE_TOLERANCE).unwrap()
+        );
+        // The limit scales with the curve
+        let small = flatten_path("M0 0C0 1 1 1 1 0", 1e-300).unwrap();
+        assert_eq!(tiny.matches('L').count(), small.matches('L').count());
+    }
+
+    #[test]
+    fn reads_relative_and_smooth_commands() {
+        let paths = parse_path("M0 0c0 10 10 10 10 0s10 -10 10 0q5 5 10 0t10 0Z").unwrap();
+        assert_eq!(1, paths.len());
+        assert!(paths[0].closed);
+        assert_eq!(Coordinate { x: 40., y: 0. }, paths[0].segments[3].end());
+        match paths[0].segments[1] {
+            Segment::Cubic(_, control, _, _) => assert_eq!(Coordinate { x: 10., y: -10. }, control),
+            _ => panic!("expected a cubic curve"),
+        }
+        assert!(flatten_path("M0 0L10", 1.).is_err());
+    }
+
+    #[test]
+    fn writes_circular_arcs_as_curves() {
+        let wkt = path_to_curve_wkt(
+            "M10 0A10 10 0 0 1 -10 0L10 0Z",
+            0.01,
+            &AffineTransform::identity(),
+            Precision::Full,
+        );
+        let wkt = wkt.unwrap().unwrap();
+        assert!(wkt.starts_with("CURVEPOLYGON(COMPOUNDCURVE(CIRCULARSTRING(10 0,"));
+        assert!(wkt.ends_with(" 10,-10 0),(-10 0,10 0)))"));
+        assert_eq!(
+            None,
+            path_to_curve_wkt(
+                "M0 0L1 1",
+                0.01,
+                &AffineTransform::identity(),
+                Precision::Full
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn writes_holes_into_curve_polygons() {
+        let wkt = path_to_curve_wkt(
+            "M0 0L100 0L100 100L0 100Z M50 40A10 10 0 0 1 50 60A10 10 0 0 1 50 40Z",
+            0.01,
+            &AffineTransform::identity(),
+            Precision::Full,
+        )
+        .unwrap()
+        .unwrap();
+        assert!(wkt.starts_with(
+            "CURVEPOLYGON((0 0,100 0,100 100,0 100,0 0),COMPOUNDCURVE(CIRCULARSTRING(50 40,"
+        ));
+    }
+}
@@ -0,0 +1,487 @@
+use crate::error::GeometryToolsError;
+use crate::geometry_convert::svg_path;
+use crate::geometry_precision::Precision;
+use crate::geometry_transform::{transform_geometry, AffineTransform};
+use crate::json_errors;
+use geo_svg_io::geo_svg_reader;
+use geo_types::{Geometry, GeometryCollection};
+use std::str::FromStr;
+use xml::attribute::OwnedAttribute;
+use xml::escape::escape_str_attribute;
+use xml::reader::{EventReader, XmlEvent};
+
+/// The SVG elements that can be read as a geometry
+const SHAPE_ELEMENTS: [&str; 5] = ["path", "polygon", "polyline", "rect", "line"];
+
+/// The SVG elements that only describe the document and can be passed over safely
+const DESCRIPTIVE_ELEMENTS: [&str; 5] = ["svg", "g", "title", "desc", "metadata"];
+
+/// The tolerance used to fit curves when none is given
+pub const DEFAULT_CURVE_TOLERANCE: f64 = 0.01;
+
+/// Options for reading SVG
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SvgReadOptions {
+    /// Return an error for anything that cannot be read faithfully (unsupported
+    /// elements, invalid transforms, shapes without their coordinates, and
+    /// malformed XML) instead of passing over it.
+    pub strict: bool,
+    /// The largest distance allowed between a curve in a <path> and the straight
+    /// lines (or circular arcs) that replace it, in the units of the output
+    /// coordinates. When it is None, every curve is replaced by 100 points and
+    /// elliptical arcs are not supported.
+    pub tolerance: Option<f64>,
+}
+
+/// A shape element found in an SVG, with the transform that places it
+struct Shape {
+    element: String,
+    attributes: Vec<OwnedAttribute>,
+    transform: AffineTransform,
+}
+
+/// Reads the first shape element of an SVG into a GeometryCollection.
+///
+/// The `transform` attributes of the shape and of all its parent elements
+/// (e.g., `<g>` groups) are applied to the geometry, in the same order as
+/// when the SVG is drawn.
+///
+/// Elements inside `<defs>` and elements from other namespaces (such as
+/// editor metadata) are never read.
+///
+/// Without strict mode, unsupported elements and shapes without coordinates
+/// are passed over, and an invalid `transform` is ignored. In strict mode the
+/// whole input is checked and any of those returns an error.
+///
+pub fn read_svg(
+    svg: &str,
+    options: &SvgReadOptions,
+) -> Result<GeometryCollection<f64>, GeometryToolsError> {
+    let shape = first_shape(svg, options)?;
+    let geom = read_shape(svg, &shape, options)?;
+    transform_collection(svg, geom, &shape.transform)
+}
+
+/// A shape element of an SVG document, with the attributes that identify it
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgDocumentElement {
+    /// The name of the element, e.g., "path"
+    pub element: String,
+    pub geometry: Geometry<f64>,
+    pub id: Option<String>,
+    pub class: Option<String>,
+    /// The `data-*` attributes, by their name without the `data-` prefix, in document order
+    pub data: Vec<(String, String)>,
+}
+
+/// Reads every shape element of an SVG document, in document order.
+///
+/// Each element is read like the single element of `read_svg`, so the
+/// transforms of its groups are applied, and the same options are used.
+/// An element whose shape holds several geometries gets a GeometryCollection.
+///
+pub fn read_svg_document(
+    svg: &str,
+    options: &SvgReadOptions,
+) -> Result<Vec<SvgDocumentElement>, GeometryToolsError> {
+    check_options(options)?;
+    find_shapes(svg, options, false)?
+        .into_iter()
+        .map(|shape| document_element(svg, shape, options))
+        .collect()
+}
+
+/// Reads the first shape element of an SVG document, which is the element
+/// that `read_svg` reads the geometry of, together with its attributes.
+///
+pub fn read_svg_element(
+    svg: &str,
+    options: &SvgReadOptions,
+) -> Result<SvgDocumentElement, GeometryToolsError> {
+    let shape = first_shape(svg, options)?;
+    document_element(svg, shape, options)
+}
+
+fn document_element(
+    svg: &str,
+    shape: Shape,
+    options: &SvgReadOptions,
+) -> Result<SvgDocumentElement, GeometryToolsError> {
+    let mut geom = transform_collection(svg, read_shape(svg, &shape, options)?, &shape.transform)?;
+    let geometry = if geom.0.len() == 1 {
+        geom.0.remove(0)
+    } else {
+        Geometry::GeometryCollection(geom)
+    };
+    Ok(SvgDocumentElement {
+        geometry,
+        id: attribute(&shape.attributes, "id").map(String::from),
+        class: attribute(&shape.attributes, "class").map(String::from),
+        data: shape
+            .attributes
+            .iter()
+            .filter(|attr| attr.name.prefix.is_none())
+            .filter_map(|attr| {
+                attr.name
+                    .local_name
+                    .strip_prefix("data-")
+                    .filter(|key| !key.is_empty())
+                    .map(|key| (key.to_string(), attr.value.clone()))
+            })
+            .collect(),
+        element: shape.element,
+    })
+}
+
+/// Writes the first shape element of an SVG as curved WKT (see
+/// `svg_path::path_to_curve_wkt`), or returns None when the shape has no curves.
+///
+/// The curves are fitted with the tolerance of the options, or with
+/// `DEFAULT_CURVE_TOLERANCE` when the options have none, and the coordinates
+/// are rounded to the given precision.
+///
+pub fn read_svg_as_curve_wkt(
+    svg: &str,
+    options: &SvgReadOptions,
+    precision: Precision,
+) -> Result<Option<String>, GeometryToolsError> {
+    let shape = first_shape(svg, options)?;
+    if shape.element != "path" {
+        return Ok(None);
+    }
+    let d_string = attribute(&shape.attributes, "d").unwrap_or_default();
+    svg_path::path_to_curve_wkt(
+        d_string,
+        options.tolerance.unwrap_or(DEFAULT_CURVE_TOLERANCE),
+        &shape.transform,
+        precision,
+    )
+    .map_err(|reason| json_errors::svg_error::could_not_parse(svg, &reason))
+}
+
+fn check_options(options: &SvgReadOptions) -> Result<(), GeometryToolsError> {
+    match options.tolerance {
+        Some(tolerance) if !svg_path::is_valid_tolerance(tolerance) => {
+            Err(json_errors::argument_error::invalid_argument(
+                "tolerance",
+                "The tolerance must be a positive number",
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn first_shape(svg: &str, options: &SvgReadOptions) -> Result<Shape, GeometryToolsError> {
+    check_options(options)?;
+    find_shapes(svg, options, true)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            json_errors::svg_error::could_not_parse(
+                svg,
+                "The SVG could not be parsed to a valid Geometry type",
+            )
+        })
+}
+
+/// Finds the shape elements of an SVG, stopping at the first one
+/// when `first_only` is set (unless strict mode must check the rest)
+fn find_shapes(
+    svg: &str,
+    options: &SvgReadOptions,
+    first_only: bool,
+) -> Result<Vec<Shape>, GeometryToolsError> {
+    let mut transforms = vec![AffineTransform::identity()];
+    let mut shapes = vec![] as Vec<Shape>;
+    // Everything inside <defs> is only drawn when it is referenced, so it is skipped
+    let mut defs_depth = 0;
+    for event in EventReader::from_str(svg) {
+        match event {
+            Ok(XmlEvent::StartElement { name, .. })
+                if defs_depth > 0 || name.local_name == "defs" =>
+            {
+                defs_depth += 1;
+            }
+            Ok(XmlEvent::EndElement { .. }) if defs_depth > 0 => {
+                defs_depth -= 1;
+            }
+            Ok(XmlEvent::StartElement {
+                name, attributes, ..
+            }) => {
+                let transform =
+                    element_transform(svg, &attributes, options)?.then(transforms.last().unwrap());
+                transforms.push(transform);
+
+                let element = name.local_name;
+                if SHAPE_ELEMENTS.contains(&element.as_str()) {
+                    let readable = has_coordinates(svg, &element, &attributes, options)?;
+                    if readable && (!first_only || shapes.is_empty()) {
+                        shapes.push(Shape {
+                            element,
+                            attributes,
+                            transform,
+                        });
+                    }
+                } else if options.strict
+                    && name.prefix.is_none()
+                    && !DESCRIPTIVE_ELEMENTS.contains(&element.as_str())
+                {
+                    return Err(json_errors::svg_error::could_not_parse(
+                        svg,
+                        &format!("The <{}> element is not supported", element),
+                    ));
+                }
+                if first_only && !shapes.is_empty() && !options.strict {
+                    break;
+                }
+            }
+            Ok(XmlEvent::EndElement { .. }) => {
+                transforms.pop();
+            }
+            Ok(_) => {}
+            Err(e) => {
+                if options.strict {
+                    return Err(json_errors::svg_error::could_not_parse(
+                        svg,
+                        &format!("The XML could not be read: {}", e),
+                    ));
+                }
+                break;
+            }
+        }
+    }
+    Ok(shapes)
+}
+
+fn attribute<'a>(attributes: &'a [OwnedAttribute], name: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|attr| attr.name.prefix.is_none() && attr.name.local_name == name)
+        .map(|attr| attr.value.as_str())
+}
+
+/// Checks that a <path>, <polygon>, or <polyline> has its coordinates, which
+/// is an error in strict mode (otherwise the shape is passed over)
+fn has_coordinates(
+    svg: &str,
+    element: &str,
+    attributes: &[OwnedAttribute],
+    options: &SvgReadOptions,
+) -> Result<bool, GeometryToolsError> {
+    let required = match element {
+        "path" => "d",
+        "polygon" | "polyline" => "points",
+        _ => return Ok(true),
+    };
+    if attribute(attributes, required).is_some() {
+        return Ok(true);
+    }
+    if options.strict {
+        return Err(json_errors::svg_error::could_not_parse(
+            svg,
+            &format!("The <{}> element has no {} attribute", element, required),
+        ));
+    }
+    Ok(false)
+}
+
+/// Reads the `transform` attribute of an element
+fn element_transform(
+    svg: &str,
+    attributes: &[OwnedAttribute],
+    options: &SvgReadOptions,
+) -> Result<AffineTransform, GeometryToolsError> {
+    let value = match attribute(attributes, "transform") {
+        Some(value) => value,
+        None => return Ok(AffineTransform::identity()),
+    };
+    match svgtypes::Transform::from_str(value) {
+        Ok(t) => Ok(AffineTransform {
+            a: t.a,
+            b: t.b,
+            c: t.c,
+            d: t.d,
+            e: t.e,
+            f: t.f,
+        }),
+        Err(e) if options.strict => Err(json_errors::svg_error::could_not_parse(
+            svg,
+            &format!("The transform \"{}\" could not be parsed: {}", value, e),
+        )),
+        Err(_) => Ok(AffineTransform::identity()),
+    }
+}
+
+/// Reads a single shape element with the SVG reader
+fn read_shape(
+    svg: &str,
+    shape: &Shape,
+    options: &SvgReadOptions,
+) -> Result<GeometryCollection<f64>, GeometryToolsError> {
+    // Only the unprefixed attributes are kept, so the element can be read
+    // without the namespace declarations of its document
+    let mut attributes = vec![] as Vec<(String, String)>;
+    for attr in &shape.attributes {
+        if attr.name.prefix.is_some() || attr.name.local_name == "transform" {
+            continue;
+        }
+        let value = match (shape.element.as_str(), attr.name.local_name.as_str()) {
+            ("path", "d") if options.tolerance.is_some() => {
+                // The tolerance is given after the transform, so it is
+                // scaled back into the coordinates of the path
+                let tolerance = options.tolerance.unwrap() / largest_scale(&shape.transform);
+                svg_path::flatten_path(&attr.value, tolerance)
+                    .map_err(|reason| json_errors::svg_error::could_not_parse(svg, &reason))?
+            }
+            _ => attr.value.clone(),
+        };
+        attributes.push((attr.name.local_name.clone(), value));
+    }
+
+    let element = format!(
+        "<{}{}/>",
+        shape.element,
+        attributes
+            .iter()
+            .map(|(name, value)| format!(" {}=\"{}\"", name, escape_str_attribute(value)))
+            .collect::<String>()
+    );
+    geo_svg_reader::svg_to_geometry_collection(&element).map_err(|e| {
+        json_errors::svg_error::could_not_parse(svg, &json_errors::svg_error::describe(&e))
+    })
+}
+
+/// Finds how much a transform stretches lengths at most
+fn largest_scale(transform: &AffineTransform) -> f64 {
+    let AffineTransform { a, b, c, d, .. } = *transform;
+    let sum = a * a + b * b + c * c + d * d;
+    let determinant = a * d - b * c;
+    let scale = ((sum + (sum * sum - 4. * determinant * determinant).max(0.).sqrt()) / 2.).sqrt();
+    if scale > 0. {
+        scale
+    } else {
+        1.
+    }
+}
+
+fn transform_collection(
+    svg: &str,
+    geom: GeometryCollection<f64>,
+    transform: &AffineTransform,
+) -> Result<GeometryCollection<f64>, GeometryToolsError> {
+    if *transform == AffineTransform::identity() {
+        return Ok(geom);
+    }
+    Ok(GeometryCollection(
+        geom.0
+            .iter()
+            .map(|g| transform_geometry(g, transform))
+            .collect::<Result<Vec<Geometry<f64>>, GeometryToolsError>>()
+            .map_err(|_| {
+                json_errors::svg_error::could_not_parse(
+                    svg,
+                    "The transform moves coordinates beyond the range of finite numbers",
+                )
+            })?,
+    ))
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry_convert::geometry_to_wkt;
+
+    fn read_wkt(svg: &str, strict: bool) -> Result<String, GeometryToolsError> {
+        let options = SvgReadOptions {
+            strict,
+            ..Default::default()
+        };
+        let mut geom = read_svg(svg, &options)?;
+        Ok(geometry_to_wkt(&geom.0.remove(0)))
+    }
+
+    #[test]
+    fn applies_nested_transforms() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg">
+            <g transform="translate(100 0)">
+                <g transform="scale(2)">
+                    <rect x="0" y="0" width="1" height="1" transform="translate(1 1)"/>
+                </g>
+            </g>
+        </svg>"#;
+        assert_eq!(
+            "POLYGON((102 2,104 2,104 4,102 4,102 2))",
+            read_wkt(svg, false).unwrap()
+        );
+        assert_eq!(
+            "LINESTRING(1 2,3 4)",
+            read_wkt(
+                r#"<polyline points="0 0,2 2" transform="matrix(1 0 0 1 1 2)"/>"#,
+                false
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn closes_transform_scope_with_group() {
+        let svg = r#"<svg><g transform="translate(5 5)"><title>Mask</title></g><path d="M0 0L1 0L1 1Z"/></svg>"#;
+        assert_eq!("POLYGON((0 0,1 0,1 1,0 0))", read_wkt(svg, false).unwrap());
+    }
+
+    #[test]
+    fn reads_every_element_of_a_document() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg">
+            <g transform="translate(10 0)">
+                <path id="roi-1" class="roi" data-artefact="12" d="M0 0L1 0L1 1Z"/>
+            </g>
+            <line x1="0" y1="0" x2="5" y2="5" data-sign="a"/>
+            <circle cx="1" cy="1" r="1"/>
+        </svg>"#;
+        let elements = read_svg_document(svg, &SvgReadOptions::default()).unwrap();
+        assert_eq!(2, elements.len());
+        assert_eq!("path", elements[0].element);
+        assert_eq!(Some("roi-1".into()), elements[0].id);
+        assert_eq!(Some("roi".into()), elements[0].class);
+        assert_eq!(vec![("artefact".into(), "12".into())], elements[0].data);
+        assert_eq!(
+            "POLYGON((10 0,11 0,11 1,10 0))",
+            geometry_to_wkt(&elements[0].geometry)
+        );
+        assert_eq!(None, elements[1].id);
+        assert_eq!(
+            "LINESTRING(0 0,5 5)",
+            geometry_to_wkt(&elements[1].geometry)
+        );
+    }
+
+    #[test]
+    fn strict_mode_rejects_unsupported_constructs() {
+        let circle = r#"<g><circle cx="1" cy="1" r="1"/><path d="M0 0L1 0L1 1Z"/></g>"#;
+        assert!(read_wkt(circle, false).is_ok());
+        assert!(read_wkt(circle, true).is_err());
+
+        let bad_transform = r#"<path d="M0 0L1 0L1 1Z" transform="spin(20)"/>"#;
+        assert_eq!(
+            "POLYGON((0 0,1 0,1 1,0 0))",
+            read_wkt(bad_transform, false).unwrap()
+        );
+        assert_eq!(
+            "SVG_PARSE_ERROR",
+            read_wkt(bad_transform, true).unwrap_err().code()
+        );
+
+        let inkscape = r#"<svg xmlns:inkscape="http://www.inkscape.org/namespaces/inkscape">
+            <defs><linearGradient id="a"><stop offset="0"/></linearGradient></defs>
+            <inkscape:grid/>
+            <path d="M0 0L1 0L1 1Z"/>
+        </svg>"#;
+        assert!(read_wkt(inkscape, true).is_ok());
+
+        let no_path = r#"<g><path/><polygon points="0 0,1 0,1 1"/></g>"#;
+        assert!(read_wkt(no_path, false).is_ok());
+        assert!(read_wkt(no_path, true).is_err());
+    }
+}
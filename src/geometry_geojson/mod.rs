@@ -0,0 +1,427 @@
+use crate::error::GeometryToolsError;
+use crate::geometry_convert::svg_reader::{read_svg_element, SvgReadOptions};
+use crate::geometry_convert::{
+    geometry_to_svg_path_string, geometry_to_wkt, svg_to_repaired_geometry, wkt_to_geometry,
+};
+use crate::json_errors;
+use geo_types::{
+    Coordinate, Geometry, GeometryCollection, LineString, MultiLineString, MultiPoint,
+    MultiPolygon, Point, Polygon,
+};
+use geojson::feature::Id;
+use geojson::{Feature, GeoJson, JsonObject, JsonValue, Value};
+use std::str::FromStr;
+
+/// A geometry read from GeoJSON together with the members of its Feature.
+///
+/// A bare GeoJSON geometry has neither an `id` nor any `properties`.
+///
+pub struct GeoJsonFeature {
+    pub geometry: Geometry<f64>,
+    pub id: Option<String>,
+    pub properties: Option<JsonObject>,
+}
+
+/// Parses a GeoJSON string into a list of features.
+///
+/// A bare geometry or a Feature yields a single entry, while a FeatureCollection
+/// yields one entry per feature. Features without a geometry are read as an
+/// empty GeometryCollection.
+///
+pub fn geojson_to_features(geojson: &str) -> Result<Vec<GeoJsonFeature>, GeometryToolsError> {
+    read_features(geojson, parse_geojson(geojson)?)
+}
+
+/// Parses a GeoJSON string into a single Geometry.
+///
+/// The geometry of a Feature is returned as is, and the geometries of a
+/// FeatureCollection are gathered into a GeometryCollection.
+///
+pub fn geojson_to_geometry(geojson: &str) -> Result<Geometry<f64>, GeometryToolsError> {
+    let parsed = parse_geojson(geojson)?;
+    let is_collection = matches!(parsed, GeoJson::FeatureCollection(_));
+    let mut geoms = read_features(geojson, parsed)?
+        .into_iter()
+        .map(|feature| feature.geometry)
+        .collect::<Vec<Geometry<f64>>>();
+
+    if is_collection {
+        return Ok(Geometry::GeometryCollection(GeometryCollection(geoms)));
+    }
+    Ok(geoms.remove(0))
+}
+
+/// Converts a Geometry into a GeoJSON geometry object.
+///
+pub fn geometry_to_geojson(geom: &Geometry<f64>) -> geojson::Geometry {
+    geojson::Geometry::new(geometry_to_value(geom))
+}
+
+/// Converts a GeoJSON geometry, Feature, or FeatureCollection into WKT.
+/// A FeatureCollection is written as a GEOMETRYCOLLECTION.
+///
+pub fn geojson_to_wkt(geojson: &str) -> Result<String, GeometryToolsError> {
+    Ok(geometry_to_wkt(&geojson_to_geometry(geojson)?))
+}
+
+/// Converts a WKT geometry into a GeoJSON geometry object.
+///
+pub fn wkt_to_geojson(wkt: &str) -> Result<String, GeometryToolsError> {
+    Ok(geometry_to_geojson(&wkt_to_geometry(wkt)?).to_string())
+}
+
+/// Converts a GeoJSON geometry, Feature, or FeatureCollection into SVG.
+///
+/// Every feature is written as a single <path> element. The `id` of a feature
+/// becomes the `id` attribute, and each of its `properties` becomes a `data-*`
+/// attribute (string values are written as is, all others as JSON). Properties
+/// whose names cannot be used in an attribute name are left out, as are features
+/// that have no SVG representation (e.g., points).
+///
+pub fn geojson_to_svg(geojson: &str) -> Result<String, GeometryToolsError> {
+    Ok(geojson_to_features(geojson)?
+        .iter()
+        .filter_map(feature_to_svg)
+        .collect::<Vec<String>>()
+        .join("\n"))
+}
+
+/// Converts an SVG element into a GeoJSON Feature.
+///
+/// Polygons and multi polygons are repaired on the way. The `id` attribute of
+/// the shape element that the geometry is read from becomes the `id` of the
+/// feature, and its `data-*` attributes become the `properties` (values that
+/// are valid JSON are read as JSON, all others as strings). The attributes of
+/// an enclosing `<svg>` or `<g>` are not used.
+///
+pub fn svg_to_geojson(svg: &str) -> Result<String, GeometryToolsError> {
+    let geom = svg_to_repaired_geometry(svg)?;
+    // The attributes are those of the shape element that the geometry is read from
+    let element = read_svg_element(svg, &SvgReadOptions::default())?;
+    let properties = element
+        .data
+        .into_iter()
+        .map(|(key, value)| {
+            let value = match serde_json::from_str(&value) {
+                Ok(value) => value,
+                Err(_) => JsonValue::String(value),
+            };
+            (key, value)
+        })
+        .collect::<JsonObject>();
+
+    Ok(Feature {
+        bbox: None,
+        geometry: Some(geometry_to_geojson(&geom)),
+        id: element.id.map(Id::String),
+        properties: Some(properties),
+        foreign_members: None,
+    }
+    .to_string())
+}
+
+fn parse_geojson(geojson: &str) -> Result<GeoJson, GeometryToolsError> {
+    match GeoJson::from_str(geojson) {
+        Ok(parsed) => Ok(parsed),
+        Err(err) => Err(json_errors::geojson_error::could_not_parse(
+            geojson,
+            &err.to_string(),
+        )),
+    }
+}
+
+fn read_features(
+    geojson: &str,
+    parsed: GeoJson,
+) -> Result<Vec<GeoJsonFeature>, GeometryToolsError> {
+    match parsed {
+        GeoJson::Geometry(geom) => Ok(vec![GeoJsonFeature {
+            geometry: value_to_geometry(geojson, &geom.value)?,
+            id: None,
+            properties: None,
+        }]),
+        GeoJson::Feature(feature) => Ok(vec![read_feature(geojson, feature)?]),
+        GeoJson::FeatureCollection(collection) => collection
+            .features
+            .into_iter()
+            .map(|feature| read_feature(geojson, feature))
+            .collect(),
+    }
+}
+
+fn read_feature(geojson: &str, feature: Feature) -> Result<GeoJsonFeature, GeometryToolsError> {
+    Ok(GeoJsonFeature {
+        geometry: match &feature.geometry {
+            Some(geom) => value_to_geometry(geojson, &geom.value)?,
+            None => Geometry::GeometryCollection(GeometryCollection(vec![])),
+        },
+        id: match feature.id {
+            Some(Id::String(id)) => Some(id),
+            Some(Id::Number(id)) => Some(id.to_string()),
+            None => None,
+        },
+        properties: feature.properties,
+    })
+}
+
+fn feature_to_svg(feature: &GeoJsonFeature) -> Option<String> {
+    let d_string = geometry_to_svg_path_string(&feature.geometry);
+    if d_string.is_empty() {
+        return None;
+    }
+
+    let mut attributes = String::new();
+    if let Some(id) = &feature.id {
+        attributes.push_str(&format!(
+            " id=\"{}\"",
+            xml::escape::escape_str_attribute(id)
+        ));
+    }
+    if let Some(properties) = &feature.properties {
+        for (key, value) in properties {
+            if key.is_empty()
+                || !key
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+            {
+                continue;
+            }
+            let value = match value {
+                JsonValue::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+            attributes.push_str(&format!(
+                " data-{}=\"{}\"",
+                key,
+                xml::escape::escape_str_attribute(&value)
+            ));
+        }
+    }
+
+    Some(format!("<path d=\"{}\"{}/>", d_string, attributes))
+}
+
+/* GeoJSON -> Geometry */
+
+fn value_to_geometry(geojson: &str, value: &Value) -> Result<Geometry<f64>, GeometryToolsError> {
+    Ok(match value {
+        Value::Point(position) => {
+            Geometry::Point(Point(position_to_coordinate(geojson, position)?))
+        }
+        Value::MultiPoint(positions) => Geometry::MultiPoint(MultiPoint(
+            positions
+                .iter()
+                .map(|position| Ok(Point(position_to_coordinate(geojson, position)?)))
+                .collect::<Result<Vec<Point<f64>>, GeometryToolsError>>()?,
+        )),
+        Value::LineString(positions) => {
+            Geometry::LineString(positions_to_line_string(geojson, positions)?)
+        }
+        Value::MultiLineString(lines) => Geometry::MultiLineString(MultiLineString(
+            lines
+                .iter()
+                .map(|positions| positions_to_line_string(geojson, positions))
+                .collect::<Result<Vec<LineString<f64>>, GeometryToolsError>>()?,
+        )),
+        Value::Polygon(rings) => Geometry::Polygon(rings_to_polygon(geojson, rings)?),
+        Value::MultiPolygon(polygons) => Geometry::MultiPolygon(MultiPolygon(
+            polygons
+                .iter()
+                .map(|rings| rings_to_polygon(geojson, rings))
+                .collect::<Result<Vec<Polygon<f64>>, GeometryToolsError>>()?,
+        )),
+        Value::GeometryCollection(geoms) => Geometry::GeometryCollection(GeometryCollection(
+            geoms
+                .iter()
+                .map(|geom| value_to_geometry(geojson, &geom.value))
+                .collect::<Result<Vec<Geometry<f64>>, GeometryToolsError>>()?,
+        )),
+    })
+}
+
+fn position_to_coordinate(
+    geojson: &str,
+    position: &[f64],
+) -> Result<Coordinate<f64>, GeometryToolsError> {
+    match position {
+        [x, y, ..] => Ok(Coordinate { x: *x, y: *y }),
+        _ => Err(json_errors::geojson_error::could_not_parse(
+            geojson,
+            "A position must have at least two coordinates",
+        )),
+    }
+}
+
+fn positions_to_line_string(
+    geojson: &str,
+    positions: &[Vec<f64>],
+) -> Result<LineString<f64>, GeometryToolsError> {
+    Ok(LineString(
+        positions
+            .iter()
+            .map(|position| position_to_coordinate(geojson, position))
+            .collect::<Result<Vec<Coordinate<f64>>, GeometryToolsError>>()?,
+    ))
+}
+
+fn rings_to_polygon(
+    geojson: &str,
+    rings: &[Vec<Vec<f64>>],
+) -> Result<Polygon<f64>, GeometryToolsError> {
+    let mut rings = rings
+        .iter()
+        .map(|ring| positions_to_line_string(geojson, ring))
+        .collect::<Result<Vec<LineString<f64>>, GeometryToolsError>>()?;
+    if rings.is_empty() {
+        return Ok(Polygon::new(LineString(vec![]), vec![]));
+    }
+    let exterior = rings.remove(0);
+    Ok(Polygon::new(exterior, rings))
+}
+
+/* Geometry -> GeoJSON */
+
+fn geometry_to_value(geom: &Geometry<f64>) -> Value {
+    match geom {
+        Geometry::Point(point) => Value::Point(coordinate_to_position(&point.0)),
+        Geometry::Line(line) => Value::LineString(vec![
+            coordinate_to_position(&line.start),
+            coordinate_to_position(&line.end),
+        ]),
+        Geometry::LineString(line) => Value::LineString(line_string_to_positions(line)),
+        Geometry::Polygon(poly) => Value::Polygon(polygon_to_rings(poly)),
+        Geometry::MultiPoint(points) => Value::MultiPoint(
+            points
+                .0
+                .iter()
+                .map(|point| coordinate_to_position(&point.0))
+                .collect(),
+        ),
+        Geometry::MultiLineString(lines) => {
+            Value::MultiLineString(lines.0.iter().map(line_string_to_positions).collect())
+        }
+        Geometry::MultiPolygon(polys) => {
+            Value::MultiPolygon(polys.0.iter().map(polygon_to_rings).collect())
+        }
+        Geometry::GeometryCollection(geoms) => {
+            Value::GeometryCollection(geoms.0.iter().map(geometry_to_geojson).collect())
+        }
+    }
+}
+
+fn coordinate_to_position(coord: &Coordinate<f64>) -> Vec<f64> {
+    vec![coord.x, coord.y]
+}
+
+fn line_string_to_positions(line: &LineString<f64>) -> Vec<Vec<f64>> {
+    line.0.iter().map(coordinate_to_position).collect()
+}
+
+fn polygon_to_rings(poly: &Polygon<f64>) -> Vec<Vec<Vec<f64>>> {
+    if poly.exterior().0.is_empty() {
+        return vec![];
+    }
+    let mut rings = vec![line_string_to_positions(poly.exterior())];
+    rings.extend(poly.interiors().iter().map(line_string_to_positions));
+    rings
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_convert_geojson_polygon_to_wkt() {
+        let wkt = geojson_to_wkt(
+            r#"{"type":"Polygon","coordinates":[[[0,0],[10,0],[10,10],[0,10],[0,0]]]}"#,
+        );
+        assert_eq!("POLYGON((0 0,10 0,10 10,0 10,0 0))", wkt.unwrap());
+    }
+
+    #[test]
+    fn can_convert_feature_collection_to_wkt() {
+        let wkt = geojson_to_wkt(
+            r#"{"type":"FeatureCollection","features":[
+                {"type":"Feature","properties":{},"geometry":{"type":"Polygon","coordinates":[[[0,0],[1,0],[1,1],[0,0]]]}},
+                {"type":"Feature","properties":{},"geometry":{"type":"Polygon","coordinates":[[[5,5],[6,5],[6,6],[5,5]]]}}
+            ]}"#,
+        );
+        assert_eq!(
+            "GEOMETRYCOLLECTION(POLYGON((0 0,1 0,1 1,0 0)),POLYGON((5 5,6 5,6 6,5 5)))",
+            wkt.unwrap()
+        );
+    }
+
+    #[test]
+    fn can_convert_wkt_to_geojson() {
+        let geojson = wkt_to_geojson("POLYGON((0 0,10 0,10 10,0 10,0 0))");
+        assert_eq!(
+            r#"{"type":"Polygon","coordinates":[[[0.0,0.0],[10.0,0.0],[10.0,10.0],[0.0,10.0],[0.0,0.0]]]}"#,
+            geojson.unwrap()
+        );
+    }
+
+    #[test]
+    fn keeps_feature_properties_in_svg() {
+        let svg = geojson_to_svg(
+            r#"{"type":"Feature","id":"roi-1","properties":{"name":"a \"frag\"","artefact":12},
+                "geometry":{"type":"Polygon","coordinates":[[[0,0],[10,0],[10,10],[0,10],[0,0]]]}}"#,
+        );
+        assert_eq!(
+            r#"<path d="M0 0L10 0L10 10L0 10L0 0" id="roi-1" data-artefact="12" data-name="a &quot;frag&quot;"/>"#,
+            svg.unwrap()
+        );
+    }
+
+    #[test]
+    fn keeps_svg_attributes_as_feature_properties() {
+        let geojson = svg_to_geojson(
+            r#"<path id="roi-1" data-artefact="12" data-name="frag" d="M0 0L10 0L10 10L0 10L0 0"/>"#,
+        );
+        let feature = geojson_to_features(&geojson.unwrap()).unwrap().remove(0);
+        assert_eq!(Some("roi-1".into()), feature.id);
+        let properties = feature.properties.unwrap();
+        assert_eq!(Some(&JsonValue::from(12)), properties.get("artefact"));
+        assert_eq!(Some(&JsonValue::from("frag")), properties.get("name"));
+        assert!(feature.geometry.into_polygon().is_some());
+    }
+
+    #[test]
+    fn keeps_attributes_of_the_shape_element_only() {
+        let geojson = svg_to_geojson(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" id="document"><path id="roi-1" data-artefact="12" d="M0 0L10 0L10 10L0 10L0 0"/></svg>"#,
+        );
+        let feature = geojson_to_features(&geojson.unwrap()).unwrap().remove(0);
+        assert_eq!(Some("roi-1".into()), feature.id);
+        let properties = feature.properties.unwrap();
+        assert_eq!(1, properties.len());
+        assert_eq!(Some(&JsonValue::from(12)), properties.get("artefact"));
+
+        let geojson = svg_to_geojson(
+            r#"<g id="layer" data-kind="x"><path id="roi-1" data-name="frag" d="M0 0L10 0L10 10L0 10L0 0"/></g>"#,
+        );
+        let feature = geojson_to_features(&geojson.unwrap()).unwrap().remove(0);
+        assert_eq!(Some("roi-1".into()), feature.id);
+        let properties = feature.properties.unwrap();
+        assert_eq!(1, properties.len());
+        assert_eq!(Some(&JsonValue::from("frag")), properties.get("name"));
+
+        // Without attributes on the shape, the feature has none either
+        let geojson = svg_to_geojson(
+            r#"<g id="layer" data-kind="x"><path d="M0 0L10 0L10 10L0 10L0 0"/></g>"#,
+        );
+        let feature = geojson_to_features(&geojson.unwrap()).unwrap().remove(0);
+        assert_eq!(None, feature.id);
+        assert!(feature.properties.unwrap().is_empty());
+    }
+
+    #[test]
+    fn errors_on_malformed_geojson() {
+        let geom = geojson_to_geometry(r#"{"type":"Polygon"}"#);
+        assert_eq!("GEOJSON_PARSE_ERROR", geom.unwrap_err().code());
+        let geom = geojson_to_geometry(r#"{"type":"Point","coordinates":[1]}"#);
+        assert_eq!("GEOJSON_PARSE_ERROR", geom.unwrap_err().code());
+    }
+}
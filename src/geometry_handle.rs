@@ -0,0 +1,182 @@
+use crate::geometry_boolean;
+use crate::geometry_convert;
+use crate::geometry_information;
+use crate::geometry_transform::{self, AffineTransform};
+use crate::geometry_validate;
+use geo_booleanop::boolean::Operation;
+use geo_types::Geometry;
+use wasm_bindgen::prelude::*;
+
+/// A geometry that is kept in WASM memory between operations.
+///
+/// Parsing and serializing strings is only done by the constructors and
+/// exporters, so chaining several operations on the same shape avoids the
+/// round trips of the string based functions. Every operation returns a new
+/// Geometry and leaves the original untouched.
+///
+#[wasm_bindgen(js_name = Geometry)]
+pub struct GeometryHandle {
+    geom: Geometry<f64>,
+}
+
+#[wasm_bindgen(js_class = Geometry)]
+impl GeometryHandle {
+    /// Reads a WKT geometry.
+    /// `POINT EMPTY` is read as an empty MULTIPOINT, since a point cannot be empty.
+    ///
+    #[wasm_bindgen(js_name = fromWkt)]
+    pub fn from_wkt(wkt: String) -> Result<GeometryHandle, JsValue> {
+        Ok(geometry_convert::wkt_to_geometry(&wkt)?.into())
+    }
+
+    /// Reads an SVG element.
+    /// This function can read a <path>, <polygon>. <polyline>,
+    /// <rect>, and <line>, all other SVG elements will return
+    /// an error.
+    ///
+    #[wasm_bindgen(js_name = fromSvg)]
+    pub fn from_svg(svg: String) -> Result<GeometryHandle, JsValue> {
+        Ok(geometry_convert::svg_to_geometry(&svg)?.into())
+    }
+
+    /// Reads an SVG <path> d-string.
+    ///
+    #[wasm_bindgen(js_name = fromSvgPath)]
+    pub fn from_svg_path(d_string: String) -> Result<GeometryHandle, JsValue> {
+        Ok(geometry_convert::svg_to_geometry(&format!("<path d=\"{}\"/>", d_string))?.into())
+    }
+
+    /// Generates a union with another geometry.
+    ///
+    /// It throws an error if the union operation on the two geometry types is not supported,
+    /// or if invalid geometries have been submitted.
+    ///
+    pub fn union(&self, other: &GeometryHandle) -> Result<GeometryHandle, JsValue> {
+        self.boolean(other, Operation::Union)
+    }
+
+    /// Generates a difference with another geometry.
+    ///
+    /// It throws an error if the difference operation on the two geometry types is not supported,
+    /// or if invalid geometries have been submitted.
+    ///
+    pub fn difference(&self, other: &GeometryHandle) -> Result<GeometryHandle, JsValue> {
+        self.boolean(other, Operation::Difference)
+    }
+
+    /// Generates an intersection with another geometry.
+    ///
+    /// It throws an error if the intersection operation on the two geometry types is not supported,
+    /// or if invalid geometries have been submitted.
+    ///
+    pub fn intersection(&self, other: &GeometryHandle) -> Result<GeometryHandle, JsValue> {
+        self.boolean(other, Operation::Intersection)
+    }
+
+    /// Generates a symmetric difference with another geometry.
+    ///
+    /// It throws an error if the symmetric difference operation on the two geometry types is not supported,
+    /// or if invalid geometries have been submitted.
+    ///
+    pub fn xor(&self, other: &GeometryHandle) -> Result<GeometryHandle, JsValue> {
+        self.boolean(other, Operation::Xor)
+    }
+
+    /// Repairs the geometry.
+    /// Only polygons and multi polygons can be repaired.
+    ///
+    pub fn repair(&self) -> Result<GeometryHandle, JsValue> {
+        Ok(geometry_validate::repair_geometry(&self.geom)?.into())
+    }
+
+    /// Tests whether the geometry is valid.
+    ///
+    pub fn validate(&self) -> bool {
+        geometry_validate::geometry_is_valid(&self.geom)
+    }
+
+    /// Validates the geometry and returns a JSON report of every violation found.
+    /// See `wktValidationReport` for the format of the report.
+    ///
+    #[wasm_bindgen(js_name = validationReport)]
+    pub fn validation_report(&self) -> String {
+        geometry_validate::report::validation_report(&self.geom).to_json()
+    }
+
+    /// Returns the area covered by the geometry.
+    ///
+    pub fn area(&self) -> f64 {
+        geometry_information::geometry_area(&self.geom)
+    }
+
+    /// Returns the bounding box of the geometry as [minX, minY, maxX, maxY],
+    /// or undefined for an empty geometry.
+    ///
+    pub fn bbox(&self) -> Option<Vec<f64>> {
+        geometry_information::geometry_bounding_rect(&self.geom)
+            .map(|rect| vec![rect.min.x, rect.min.y, rect.max.x, rect.max.y])
+    }
+
+    /// Transforms the geometry with a 2x3 affine matrix [a, b, c, d, e, f].
+    /// See `wktTransform` for how the matrix is applied.
+    ///
+    pub fn transform(&self, matrix: Vec<f64>) -> Result<GeometryHandle, JsValue> {
+        let transform = AffineTransform::from_matrix(&matrix)?;
+        Ok(geometry_transform::transform_geometry(&self.geom, &transform)?.into())
+    }
+
+    /// Writes the geometry as WKT.
+    ///
+    #[wasm_bindgen(js_name = toWkt)]
+    pub fn to_wkt(&self) -> String {
+        geometry_convert::geometry_to_wkt(&self.geom)
+    }
+
+    /// Writes the geometry as an SVG element.
+    ///
+    #[wasm_bindgen(js_name = toSvg)]
+    pub fn to_svg(&self) -> String {
+        geometry_convert::geometry_to_svg(&self.geom)
+    }
+
+    /// Writes the geometry as an SVG <path> d-string.
+    ///
+    #[wasm_bindgen(js_name = toSvgPath)]
+    pub fn to_svg_path(&self) -> String {
+        geometry_convert::geometry_to_svg_path_string(&self.geom)
+    }
+
+    fn boolean(&self, other: &GeometryHandle, op: Operation) -> Result<GeometryHandle, JsValue> {
+        Ok(geometry_boolean::geometry_boolean(&self.geom, &other.geom, op)?.into())
+    }
+}
+
+impl From<Geometry<f64>> for GeometryHandle {
+    fn from(geom: Geometry<f64>) -> Self {
+        GeometryHandle { geom }
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_chain_operations_on_a_geometry() {
+        let square = GeometryHandle::from_wkt("POLYGON((0 0,10 0,10 10,0 10,0 0))".into())
+            .ok()
+            .unwrap();
+        let band = GeometryHandle::from_svg_path("M4 -1L6 -1L6 11L4 11Z".into())
+            .ok()
+            .unwrap();
+        let split = square.difference(&band).ok().unwrap();
+        assert_eq!(80., split.area());
+        assert!(split.validate());
+        assert_eq!(Some(vec![0., 0., 10., 10.]), split.bbox());
+        let whole = split.union(&band).ok().unwrap();
+        assert_eq!(Some(vec![0., -1., 10., 11.]), whole.bbox());
+        assert_eq!(square.to_wkt(), "POLYGON((0 0,10 0,10 10,0 10,0 0))");
+    }
+}
@@ -0,0 +1,218 @@
+use geo_types::Coordinate;
+use std::collections::HashMap;
+
+/// Digs into the edges of a convex hull to wrap the points more tightly, as
+/// the "concaveman" algorithm does.
+///
+/// The hull is given counterclockwise without its closing point. Each edge
+/// a-b is replaced by a-p-b with the nearest point p inside the hull, as long
+/// as p is no farther from a or b than the length of the edge divided by
+/// `concavity`, p is nearer to a-b than to the neighbouring edges, and the
+/// new edges do not meet any other edge. Edges shorter than
+/// `length_threshold` are left alone.
+///
+pub(crate) fn dig_hull(
+    hull: Vec<Coordinate<f64>>,
+    points: &[Coordinate<f64>],
+    concavity: f64,
+    length_threshold: f64,
+) -> Vec<Coordinate<f64>> {
+    let len = hull.len();
+    let mut nodes = hull;
+    let mut next = (0..len).map(|i| (i + 1) % len).collect::<Vec<usize>>();
+    let mut prev = (0..len)
+        .map(|i| (i + len - 1) % len)
+        .collect::<Vec<usize>>();
+    let mut inner = PointGrid::new(
+        points
+            .iter()
+            .filter(|point| !nodes.contains(point))
+            .copied()
+            .collect(),
+    );
+
+    let mut queue = (0..len).collect::<Vec<usize>>();
+    while let Some(a) = queue.pop() {
+        let b = next[a];
+        let (pa, pb) = (nodes[a], nodes[b]);
+        let length = squared_distance(pa, pb).sqrt();
+        if length < length_threshold {
+            continue;
+        }
+        let reach = length / concavity;
+        let (before, after) = (nodes[prev[a]], nodes[next[b]]);
+        let candidate = inner
+            .near_segment(pa, pb, reach)
+            .into_iter()
+            .find(|&(_, p)| {
+                let distance = segment_distance(p, pa, pb);
+                distance < segment_distance(p, pb, after)
+                    && distance < segment_distance(p, before, pa)
+                    && (0..nodes.len()).filter(|&n| n != a).all(|n| {
+                        let (u, v) = (nodes[n], nodes[next[n]]);
+                        !segments_meet(pa, p, u, v) && !segments_meet(p, pb, u, v)
+                    })
+            });
+        let (index, p) = match candidate {
+            Some(candidate) => candidate,
+            None => continue,
+        };
+        if squared_distance(p, pa).min(squared_distance(p, pb)) > reach * reach {
+            continue;
+        }
+
+        inner.remove(index);
+        let node = nodes.len();
+        nodes.push(p);
+        next.push(b);
+        prev.push(a);
+        next[a] = node;
+        prev[b] = node;
+        queue.push(node);
+        queue.push(a);
+    }
+
+    let mut ring = vec![nodes[0]];
+    let mut current = next[0];
+    while current != 0 {
+        ring.push(nodes[current]);
+        current = next[current];
+    }
+    ring
+}
+
+/// The points inside the hull in square cells, so that the points near an
+/// edge can be found without testing every point
+struct PointGrid {
+    points: Vec<Coordinate<f64>>,
+    removed: Vec<bool>,
+    origin: Coordinate<f64>,
+    size: f64,
+    cells: HashMap<(i64, i64), Vec<usize>>,
+}
+
+impl PointGrid {
+    fn new(points: Vec<Coordinate<f64>>) -> Self {
+        let (mut min, mut max) = (points.first().copied(), points.first().copied());
+        for p in &points {
+            min = min.map(|m| Coordinate {
+                x: m.x.min(p.x),
+                y: m.y.min(p.y),
+            });
+            max = max.map(|m| Coordinate {
+                x: m.x.max(p.x),
+                y: m.y.max(p.y),
+            });
+        }
+        let origin = min.unwrap_or(Coordinate { x: 0., y: 0. });
+        let extent = max.map_or(0., |max| (max.x - origin.x).max(max.y - origin.y));
+        let size = extent / (points.len() as f64).sqrt().max(1.);
+        let mut grid = PointGrid {
+            removed: vec![false; points.len()],
+            origin,
+            size: if size.is_finite() && size > 0. {
+                size
+            } else {
+                1.
+            },
+            cells: HashMap::new(),
+            points,
+        };
+        for (i, &p) in grid.points.iter().enumerate() {
+            let cell = grid.cell(p);
+            grid.cells.entry(cell).or_default().push(i);
+        }
+        grid
+    }
+
+    fn cell(&self, p: Coordinate<f64>) -> (i64, i64) {
+        (
+            ((p.x - self.origin.x) / self.size).floor() as i64,
+            ((p.y - self.origin.y) / self.size).floor() as i64,
+        )
+    }
+
+    /// The points within `reach` of the segment a-b, nearest first
+    fn near_segment(
+        &self,
+        a: Coordinate<f64>,
+        b: Coordinate<f64>,
+        reach: f64,
+    ) -> Vec<(usize, Coordinate<f64>)> {
+        let (min_x, min_y) = self.cell(Coordinate {
+            x: a.x.min(b.x) - reach,
+            y: a.y.min(b.y) - reach,
+        });
+        let (max_x, max_y) = self.cell(Coordinate {
+            x: a.x.max(b.x) + reach,
+            y: a.y.max(b.y) + reach,
+        });
+        let mut found = (min_x..=max_x)
+            .flat_map(|x| (min_y..=max_y).map(move |y| (x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .filter(|&&i| !self.removed[i])
+            .map(|&i| (segment_distance(self.points[i], a, b), i))
+            .filter(|&(distance, _)| distance <= reach)
+            .collect::<Vec<(f64, usize)>>();
+        found.sort_by(|x, y| x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal));
+        found
+            .into_iter()
+            .map(|(_, i)| (i, self.points[i]))
+            .collect()
+    }
+
+    fn remove(&mut self, index: usize) {
+        self.removed[index] = true;
+    }
+}
+
+fn cross(a: Coordinate<f64>, b: Coordinate<f64>, c: Coordinate<f64>) -> f64 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+fn squared_distance(a: Coordinate<f64>, b: Coordinate<f64>) -> f64 {
+    (a.x - b.x) * (a.x - b.x) + (a.y - b.y) * (a.y - b.y)
+}
+
+fn segment_distance(p: Coordinate<f64>, a: Coordinate<f64>, b: Coordinate<f64>) -> f64 {
+    let length = squared_distance(a, b);
+    if length == 0. {
+        return squared_distance(p, a).sqrt();
+    }
+    let t = (((p.x - a.x) * (b.x - a.x) + (p.y - a.y) * (b.y - a.y)) / length).clamp(0., 1.);
+    squared_distance(
+        p,
+        Coordinate {
+            x: a.x + t * (b.x - a.x),
+            y: a.y + t * (b.y - a.y),
+        },
+    )
+    .sqrt()
+}
+
+/// Tests whether the segments a-b and p-q meet anywhere other than at a
+/// shared end point
+fn segments_meet(
+    a: Coordinate<f64>,
+    b: Coordinate<f64>,
+    p: Coordinate<f64>,
+    q: Coordinate<f64>,
+) -> bool {
+    let (d1, d2) = (cross(a, b, p), cross(a, b, q));
+    let (d3, d4) = (cross(p, q, a), cross(p, q, b));
+    let opposite = |u: f64, v: f64| (u > 0. && v < 0.) || (u < 0. && v > 0.);
+    if opposite(d1, d2) && opposite(d3, d4) {
+        return true;
+    }
+    let touches = |d: f64, s: Coordinate<f64>, e: Coordinate<f64>, x: Coordinate<f64>| {
+        d == 0.
+            && x != s
+            && x != e
+            && x.x >= s.x.min(e.x)
+            && x.x <= s.x.max(e.x)
+            && x.y >= s.y.min(e.y)
+            && x.y <= s.y.max(e.y)
+    };
+    touches(d1, a, b, p) || touches(d2, a, b, q) || touches(d3, p, q, a) || touches(d4, p, q, b)
+}
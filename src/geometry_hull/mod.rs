@@ -0,0 +1,262 @@
+mod concave;
+
+use crate::error::GeometryToolsError;
+use crate::geometry_convert::{
+    geometry_to_svg, geometry_to_wkt, svg_to_geometry_or_collection, wkt_to_geometry,
+};
+use crate::geometry_validate::geometry_is_valid;
+use crate::json_errors;
+use concave::dig_hull;
+use geo::algorithm::area::Area;
+use geo::algorithm::convexhull::ConvexHull;
+use geo_types::{Coordinate, Geometry, LineString, MultiPoint, Point, Polygon};
+
+/// The concavity used when none is given
+pub const DEFAULT_CONCAVITY: f64 = 2.;
+
+/// Options for the concave hull
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConcaveHullOptions {
+    /// How far the hull may cut in between the points: an edge is only split at
+    /// a point that is no farther from one of its ends than the length of the
+    /// edge divided by the concavity. 1 gives a detailed outline, larger values
+    /// a smoother one, and infinity gives the convex hull.
+    pub concavity: f64,
+    /// Edges shorter than this are not split any further
+    pub length_threshold: f64,
+}
+
+impl Default for ConcaveHullOptions {
+    fn default() -> Self {
+        ConcaveHullOptions {
+            concavity: DEFAULT_CONCAVITY,
+            length_threshold: 0.,
+        }
+    }
+}
+
+impl ConcaveHullOptions {
+    /// Reads the options, using the defaults for the ones that are not given
+    pub fn parse(
+        concavity: Option<f64>,
+        length_threshold: Option<f64>,
+    ) -> Result<Self, GeometryToolsError> {
+        let concavity = concavity.unwrap_or(DEFAULT_CONCAVITY);
+        if concavity.is_nan() || concavity <= 0. {
+            return Err(json_errors::argument_error::invalid_argument(
+                "concavity",
+                "The concavity must be a number greater than 0",
+            ));
+        }
+        let length_threshold = length_threshold.unwrap_or(0.);
+        if !length_threshold.is_finite() || length_threshold < 0. {
+            return Err(json_errors::argument_error::invalid_argument(
+                "lengthThreshold",
+                "The length threshold must be a finite number of at least 0",
+            ));
+        }
+        Ok(ConcaveHullOptions {
+            concavity,
+            length_threshold,
+        })
+    }
+}
+
+/// Returns the smallest convex polygon that contains every coordinate of a Geometry.
+///
+/// Any geometry can be used, including collections. When the coordinates do
+/// not enclose any area (there are fewer than three, or they all lie on one
+/// line), the result is an empty polygon.
+///
+pub fn convex_hull(geom: &Geometry<f64>) -> Polygon<f64> {
+    hull_of(&distinct_coordinates(geom))
+}
+
+/// Returns a polygon that wraps every coordinate of a Geometry more tightly than
+/// the convex hull, following the "concaveman" algorithm.
+///
+/// Starting from the convex hull, each edge is split at the nearest coordinate
+/// inside the hull as long as that coordinate is close enough to the edge (see
+/// `ConcaveHullOptions`) and the outline does not cross itself. Every coordinate
+/// ends up inside the hull or on its outline. When the coordinates do not
+/// enclose any area, the result is an empty polygon.
+///
+pub fn concave_hull(geom: &Geometry<f64>, options: &ConcaveHullOptions) -> Polygon<f64> {
+    let coords = distinct_coordinates(geom);
+    let convex = hull_of(&coords);
+    if convex.exterior().0.is_empty() {
+        return convex;
+    }
+    let mut ring = convex.exterior().0.clone();
+    ring.pop();
+    if convex.area() < 0. {
+        ring.reverse();
+    }
+    let mut ring = dig_hull(ring, &coords, options.concavity, options.length_threshold);
+    ring.push(ring[0]);
+    let concave = Polygon::new(LineString(ring), vec![]);
+    // The outline is kept from crossing itself, so this only guards against rounding
+    if geometry_is_valid(&concave.clone().into()) {
+        concave
+    } else {
+        convex
+    }
+}
+
+/// Returns the convex hull of a WKT geometry as a WKT POLYGON (see `convex_hull`).
+///
+pub fn wkt_convex_hull(wkt: &str) -> Result<String, GeometryToolsError> {
+    Ok(geometry_to_wkt(&convex_hull(&wkt_to_geometry(wkt)?).into()))
+}
+
+/// Returns the convex hull of an SVG element as an SVG path (see `convex_hull`).
+///
+pub fn svg_convex_hull(svg: &str) -> Result<String, GeometryToolsError> {
+    Ok(geometry_to_svg(
+        &convex_hull(&svg_to_geometry_or_collection(svg)?).into(),
+    ))
+}
+
+/// Returns the concave hull of a WKT geometry as a WKT POLYGON (see `concave_hull`).
+///
+pub fn wkt_concave_hull(
+    wkt: &str,
+    options: &ConcaveHullOptions,
+) -> Result<String, GeometryToolsError> {
+    Ok(geometry_to_wkt(
+        &concave_hull(&wkt_to_geometry(wkt)?, options).into(),
+    ))
+}
+
+/// Returns the concave hull of an SVG element as an SVG path (see `concave_hull`).
+///
+pub fn svg_concave_hull(
+    svg: &str,
+    options: &ConcaveHullOptions,
+) -> Result<String, GeometryToolsError> {
+    Ok(geometry_to_svg(
+        &concave_hull(&svg_to_geometry_or_collection(svg)?, options).into(),
+    ))
+}
+
+fn hull_of(coords: &[Coordinate<f64>]) -> Polygon<f64> {
+    let empty = Polygon::new(LineString(vec![]), vec![]);
+    let hull = match coords.len() {
+        0..=2 => return empty,
+        // The convex hull of geo does not order fewer than four points
+        3 => Polygon::new(
+            LineString(vec![coords[0], coords[1], coords[2], coords[0]]),
+            vec![],
+        ),
+        _ => MultiPoint(coords.iter().map(|c| Point(*c)).collect()).convex_hull(),
+    };
+    if hull.area() == 0. {
+        return empty;
+    }
+    hull
+}
+
+/// Every coordinate of a geometry once, ordered by x and then y
+fn distinct_coordinates(geom: &Geometry<f64>) -> Vec<Coordinate<f64>> {
+    let mut coords = vec![] as Vec<Coordinate<f64>>;
+    collect_coordinates(geom, &mut coords);
+    coords.retain(|c| c.x.is_finite() && c.y.is_finite());
+    coords.sort_by(|a, b| {
+        a.x.partial_cmp(&b.x)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.y.partial_cmp(&b.y).unwrap_or(std::cmp::Ordering::Equal))
+    });
+    coords.dedup();
+    coords
+}
+
+fn collect_coordinates(geom: &Geometry<f64>, coords: &mut Vec<Coordinate<f64>>) {
+    let ring = |line: &LineString<f64>, coords: &mut Vec<Coordinate<f64>>| {
+        coords.extend(line.0.iter().copied())
+    };
+    match geom {
+        Geometry::Point(point) => coords.push(point.0),
+        Geometry::MultiPoint(points) => coords.extend(points.0.iter().map(|p| p.0)),
+        Geometry::Line(line) => coords.extend(&[line.start, line.end]),
+        Geometry::LineString(line) => ring(line, coords),
+        Geometry::MultiLineString(lines) => lines.0.iter().for_each(|l| ring(l, coords)),
+        Geometry::Polygon(poly) => ring(poly.exterior(), coords),
+        Geometry::MultiPolygon(polys) => polys.0.iter().for_each(|p| ring(p.exterior(), coords)),
+        Geometry::GeometryCollection(geoms) => {
+            geoms.0.iter().for_each(|g| collect_coordinates(g, coords))
+        }
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_any_geometry_in_a_convex_hull() {
+        assert_eq!(
+            "POLYGON((10 0,10 10,0 10,0 0,10 0))",
+            wkt_convex_hull(
+                "GEOMETRYCOLLECTION(POINT(0 0),LINESTRING(10 0,10 10),POLYGON((0 10,5 5,2 8,0 10)))"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            "POLYGON((4 0,4 3,0 0,4 0))",
+            wkt_convex_hull("MULTIPOINT((0 0),(4 0),(4 3),(2 1),(4 0))").unwrap()
+        );
+        assert_eq!(
+            "POLYGON EMPTY",
+            wkt_convex_hull("MULTIPOINT((0 0),(1 1),(2 2),(3 3))").unwrap()
+        );
+        assert_eq!(
+            "<path d=\"M15 0L15 5L0 5L0 0L15 0\"/>",
+            svg_convex_hull("<path d=\"M0 0L5 0L5 5L0 5ZM10 0L15 0L15 5L10 5Z\"/>").unwrap()
+        );
+    }
+
+    #[test]
+    fn wraps_points_tightly_in_a_concave_hull() {
+        // The convex hull also covers the notch at the top
+        let geom =
+            wkt_to_geometry("POLYGON((0 0,2 0,4 0,6 0,8 0,10 0,10 4,8 4,7 1.5,3 1.5,2 4,0 4,0 0))")
+                .unwrap();
+        let hull = concave_hull(&geom, &ConcaveHullOptions::default());
+        assert!(geometry_is_valid(&hull.clone().into()));
+        assert_eq!(40. - 12.5, hull.area().abs());
+        assert_eq!(40., convex_hull(&geom).area().abs());
+
+        let smooth = ConcaveHullOptions {
+            concavity: f64::INFINITY,
+            ..Default::default()
+        };
+        assert_eq!(convex_hull(&geom), concave_hull(&geom, &smooth));
+        let long_edges = ConcaveHullOptions {
+            length_threshold: 11.,
+            ..Default::default()
+        };
+        assert_eq!(40., concave_hull(&geom, &long_edges).area().abs());
+    }
+
+    #[test]
+    fn rejects_invalid_concave_hull_options() {
+        assert_eq!(
+            "INVALID_ARGUMENT",
+            ConcaveHullOptions::parse(Some(0.), None)
+                .unwrap_err()
+                .code()
+        );
+        assert_eq!(
+            "INVALID_ARGUMENT",
+            ConcaveHullOptions::parse(None, Some(f64::NAN))
+                .unwrap_err()
+                .code()
+        );
+        assert_eq!(
+            ConcaveHullOptions::default(),
+            ConcaveHullOptions::parse(None, None).unwrap()
+        );
+    }
+}
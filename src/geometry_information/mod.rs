@@ -0,0 +1,780 @@
+use crate::error::GeometryToolsError;
+use crate::geometry_convert::{
+    svg_to_geometry_collection, svg_to_geometry_or_collection, wkt_to_geometry,
+};
+use crate::json_errors;
+use geo::algorithm::bounding_rect::BoundingRect;
+use geo::algorithm::euclidean_length::EuclideanLength;
+use geo_types::{Coordinate, Geometry, LineString, Polygon, Rect};
+
+/// Returns the Geometry type recognized for the submitted SVG element.
+/// Only <path>, <polygon>. <polyline>, <rect>, and <line> will be recognized
+/// as valid Geom types.
+///
+pub fn svg_geom_type(svg: &str) -> String {
+    let geom = match svg_to_geometry_collection(svg) {
+        Ok(geom) => geom,
+        Err(_) => return "None".into(),
+    };
+
+    if geom.0.len() > 1 {
+        return "GEOMETRYCOLLECTION".into();
+    }
+
+    match geom.0[0] {
+        Geometry::MultiPolygon { .. } => "MULTIPOLYGON".into(),
+        Geometry::Polygon { .. } => "POLYGON".into(),
+        Geometry::MultiLineString { .. } => "MULTILINESTRING".into(),
+        Geometry::LineString { .. } => "LINESTRING".into(),
+        Geometry::Line { .. } => "LINESTRING".into(),
+        Geometry::MultiPoint { .. } => "MULTIPOINT".into(),
+        Geometry::Point { .. } => "POINT".into(),
+        _ => "INVALIDGEOMETRY".into(),
+    }
+}
+
+/// Returns the Geometry type recognized for the submitted SVG path d-string.
+///
+pub fn svg_path_geom_type(d_string: &str) -> String {
+    svg_geom_type(&format!("<path d=\"{}\"/>", d_string))
+}
+
+/// The geometry types that can be named in a WKT string
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WktGeometryKind {
+    Point,
+    LineString,
+    Polygon,
+    Triangle,
+    MultiPoint,
+    MultiLineString,
+    MultiPolygon,
+    GeometryCollection,
+}
+
+impl WktGeometryKind {
+    /// The WKT name of the geometry type
+    pub fn name(self) -> &'static str {
+        match self {
+            WktGeometryKind::Point => "POINT",
+            WktGeometryKind::LineString => "LINESTRING",
+            WktGeometryKind::Polygon => "POLYGON",
+            WktGeometryKind::Triangle => "TRIANGLE",
+            WktGeometryKind::MultiPoint => "MULTIPOINT",
+            WktGeometryKind::MultiLineString => "MULTILINESTRING",
+            WktGeometryKind::MultiPolygon => "MULTIPOLYGON",
+            WktGeometryKind::GeometryCollection => "GEOMETRYCOLLECTION",
+        }
+    }
+
+    /// Whether the geometry type can be used in a polygon boolean operation
+    pub fn is_polygonal(self) -> bool {
+        matches!(
+            self,
+            WktGeometryKind::Polygon | WktGeometryKind::MultiPolygon
+        )
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [
+            WktGeometryKind::Point,
+            WktGeometryKind::LineString,
+            WktGeometryKind::Polygon,
+            WktGeometryKind::Triangle,
+            WktGeometryKind::MultiPoint,
+            WktGeometryKind::MultiLineString,
+            WktGeometryKind::MultiPolygon,
+            WktGeometryKind::GeometryCollection,
+        ]
+        .iter()
+        .find(|kind| kind.name().eq_ignore_ascii_case(name))
+        .cloned()
+    }
+}
+
+/// The coordinate dimension declared in a WKT string
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WktDimension {
+    Xy,
+    Xyz,
+    Xym,
+    Xyzm,
+}
+
+impl WktDimension {
+    pub fn name(self) -> &'static str {
+        match self {
+            WktDimension::Xy => "XY",
+            WktDimension::Xyz => "XYZ",
+            WktDimension::Xym => "XYM",
+            WktDimension::Xyzm => "XYZM",
+        }
+    }
+
+    fn from_suffix(suffix: &str) -> Option<Self> {
+        match suffix.to_ascii_uppercase().as_str() {
+            "Z" => Some(WktDimension::Xyz),
+            "M" => Some(WktDimension::Xym),
+            "ZM" => Some(WktDimension::Xyzm),
+            _ => None,
+        }
+    }
+}
+
+/// The geometry type declared at the start of a WKT (or EWKT) string
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WktGeometryType {
+    pub kind: WktGeometryKind,
+    pub dimension: WktDimension,
+    pub empty: bool,
+    pub srid: Option<u32>,
+}
+
+impl WktGeometryType {
+    pub fn to_json(&self) -> String {
+        serde_json::json!({
+            "type": self.kind.name(),
+            "dimension": self.dimension.name(),
+            "empty": self.empty,
+            "srid": self.srid,
+        })
+        .to_string()
+    }
+}
+
+/// Reads the geometry type declared at the start of a WKT string.
+///
+/// The type name is matched case insensitively, may be preceded by whitespace
+/// and an EWKT `SRID=...;` prefix, and may be followed by a Z, M, or ZM
+/// dimension (either separate or attached to the name) and by EMPTY. Only the
+/// start of the string is read, so the coordinates are not checked.
+///
+pub fn wkt_geometry_type(wkt: &str) -> Result<WktGeometryType, GeometryToolsError> {
+    let (srid, body) = split_srid(wkt)?;
+    let body = body.trim_start();
+    if body.is_empty() {
+        return Err(json_errors::wkt_errors::wkt_cannot_be_parsed(
+            wkt,
+            "The wkt string is empty",
+        ));
+    }
+
+    let word_end = word_length(body);
+    let word = &body[..word_end];
+    let (kind, mut dimension) = match WktGeometryKind::from_name(word) {
+        Some(kind) => (kind, None),
+        None => ["ZM", "Z", "M"]
+            .iter()
+            .filter(|suffix| word.len() > suffix.len())
+            .find_map(|suffix| {
+                let (name, rest) = word.split_at(word.len() - suffix.len());
+                match (
+                    WktGeometryKind::from_name(name),
+                    WktDimension::from_suffix(rest),
+                ) {
+                    (Some(kind), Some(dimension)) => Some((kind, Some(dimension))),
+                    _ => None,
+                }
+            })
+            .ok_or_else(|| json_errors::wkt_errors::invalid_wkt_type(wkt))?,
+    };
+
+    let mut rest = body[word_end..].trim_start();
+    if dimension.is_none() {
+        let end = word_length(rest);
+        if let Some(declared) = WktDimension::from_suffix(&rest[..end]) {
+            dimension = Some(declared);
+            rest = rest[end..].trim_start();
+        }
+    }
+
+    let end = word_length(rest);
+    let empty = if rest[..end].eq_ignore_ascii_case("EMPTY") {
+        true
+    } else if rest.starts_with('(') {
+        false
+    } else {
+        return Err(json_errors::wkt_errors::wkt_cannot_be_parsed(
+            wkt,
+            "Missing open parenthesis for type",
+        ));
+    };
+
+    Ok(WktGeometryType {
+        kind,
+        dimension: dimension.unwrap_or(WktDimension::Xy),
+        empty,
+        srid,
+    })
+}
+
+fn word_length(text: &str) -> usize {
+    text.find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(text.len())
+}
+
+/// Splits an EWKT `SRID=...;` prefix from a WKT string.
+///
+pub fn split_srid(wkt: &str) -> Result<(Option<u32>, &str), GeometryToolsError> {
+    let trimmed = wkt.trim_start();
+    if !trimmed
+        .get(..5)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("SRID="))
+    {
+        return Ok((None, wkt));
+    }
+    match trimmed[5..].find(';') {
+        Some(end) => match trimmed[5..5 + end].trim().parse::<u32>() {
+            Ok(srid) => Ok((Some(srid), &trimmed[5 + end + 1..])),
+            Err(_) => Err(json_errors::wkt_errors::wkt_cannot_be_parsed(
+                wkt,
+                "The SRID is not a valid number",
+            )),
+        },
+        None => Err(json_errors::wkt_errors::wkt_cannot_be_parsed(
+            wkt,
+            "The SRID prefix is missing its ';'",
+        )),
+    }
+}
+
+/// Tests whether a Geometry is empty, i.e., it has no coordinates at all.
+///
+/// A collection is empty when all of its members are, so
+/// `GEOMETRYCOLLECTION(POLYGON EMPTY)` is empty too.
+///
+pub fn geometry_is_empty(geom: &Geometry<f64>) -> bool {
+    match geom {
+        Geometry::Point(_) | Geometry::Line(_) => false,
+        Geometry::LineString(line) => line.0.is_empty(),
+        Geometry::Polygon(poly) => polygon_is_empty(poly),
+        Geometry::MultiPoint(points) => points.0.is_empty(),
+        Geometry::MultiLineString(lines) => lines.0.iter().all(|line| line.0.is_empty()),
+        Geometry::MultiPolygon(polys) => polys.0.iter().all(polygon_is_empty),
+        Geometry::GeometryCollection(geoms) => geoms.0.iter().all(geometry_is_empty),
+    }
+}
+
+/// Tests whether a Polygon is empty, as `POLYGON EMPTY` is read.
+///
+pub fn polygon_is_empty(poly: &Polygon<f64>) -> bool {
+    poly.exterior().0.is_empty() && poly.interiors().iter().all(|ring| ring.0.is_empty())
+}
+
+/// Returns the area covered by a Geometry.
+///
+/// The area is always positive, whatever the winding order of the rings, and
+/// holes are subtracted from it. Geometries without an area return 0.
+///
+pub fn geometry_area(geom: &Geometry<f64>) -> f64 {
+    match geom {
+        Geometry::Polygon(poly) => polygon_area(poly),
+        Geometry::MultiPolygon(polys) => polys.0.iter().map(polygon_area).sum(),
+        Geometry::GeometryCollection(geoms) => geoms.0.iter().map(geometry_area).sum(),
+        _ => 0.,
+    }
+}
+
+fn polygon_area(poly: &Polygon<f64>) -> f64 {
+    poly.interiors()
+        .iter()
+        .fold(ring_area(poly.exterior()).abs(), |total, interior| {
+            total - ring_area(interior).abs()
+        })
+}
+
+/// The signed area of a ring, positive when it is wound counter clockwise
+fn ring_area(ring: &LineString<f64>) -> f64 {
+    ring.lines()
+        .map(|line| line.start.x * line.end.y - line.end.x * line.start.y)
+        .sum::<f64>()
+        / 2.
+}
+
+/// Returns the signed area of a Geometry.
+///
+/// Every ring adds its own signed area, which is positive when the ring is wound
+/// counter clockwise in a y-up coordinate system (as OGC shells are). In SVG's
+/// y-down coordinates the same ring appears clockwise on screen. A correctly
+/// wound polygon therefore has the same signed and unsigned area, while
+/// reversed rings give a negative area.
+///
+pub fn geometry_signed_area(geom: &Geometry<f64>) -> f64 {
+    match geom {
+        Geometry::Polygon(poly) => polygon_signed_area(poly),
+        Geometry::MultiPolygon(polys) => polys.0.iter().map(polygon_signed_area).sum(),
+        Geometry::GeometryCollection(geoms) => geoms.0.iter().map(geometry_signed_area).sum(),
+        _ => 0.,
+    }
+}
+
+fn polygon_signed_area(poly: &Polygon<f64>) -> f64 {
+    ring_area(poly.exterior()) + poly.interiors().iter().map(ring_area).sum::<f64>()
+}
+
+/// Returns the length of the boundary of a Geometry.
+///
+/// Polygons count the length of every ring (holes included), lines count
+/// their own length, and points have no length.
+///
+pub fn geometry_perimeter(geom: &Geometry<f64>) -> f64 {
+    match geom {
+        Geometry::Line(line) => line.euclidean_length(),
+        Geometry::LineString(line) => line.euclidean_length(),
+        Geometry::MultiLineString(lines) => lines.euclidean_length(),
+        Geometry::Polygon(poly) => polygon_perimeter(poly),
+        Geometry::MultiPolygon(polys) => polys.0.iter().map(polygon_perimeter).sum(),
+        Geometry::GeometryCollection(geoms) => geoms.0.iter().map(geometry_perimeter).sum(),
+        Geometry::Point(_) | Geometry::MultiPoint(_) => 0.,
+    }
+}
+
+fn polygon_perimeter(poly: &Polygon<f64>) -> f64 {
+    poly.exterior().euclidean_length()
+        + poly
+            .interiors()
+            .iter()
+            .map(|interior| interior.euclidean_length())
+            .sum::<f64>()
+}
+
+/// Returns the centroid of a Geometry, or None for an empty geometry.
+///
+/// Polygons are weighted by area (holes are taken out, whatever their winding
+/// order), lines by length, and points equally. As in OGC, only the parts of a
+/// collection with the highest dimension are used.
+///
+pub fn geometry_centroid(geom: &Geometry<f64>) -> Option<Coordinate<f64>> {
+    let mut sums = CentroidSums::default();
+    sums.add(geom);
+    sums.centroid()
+}
+
+/// The weighted coordinate sums of each dimension of a geometry
+#[derive(Default)]
+struct CentroidSums {
+    area: (f64, f64, f64),
+    length: (f64, f64, f64),
+    points: (f64, f64, f64),
+}
+
+impl CentroidSums {
+    fn add(&mut self, geom: &Geometry<f64>) {
+        match geom {
+            Geometry::Point(point) => self.add_point(&point.0),
+            Geometry::MultiPoint(points) => points.0.iter().for_each(|p| self.add_point(&p.0)),
+            Geometry::Line(line) => self.add_segment(&line.start, &line.end),
+            Geometry::LineString(line) => self.add_line(line),
+            Geometry::MultiLineString(lines) => lines.0.iter().for_each(|l| self.add_line(l)),
+            Geometry::Polygon(poly) => self.add_polygon(poly),
+            Geometry::MultiPolygon(polys) => polys.0.iter().for_each(|p| self.add_polygon(p)),
+            Geometry::GeometryCollection(geoms) => geoms.0.iter().for_each(|g| self.add(g)),
+        }
+    }
+
+    fn add_point(&mut self, coord: &Coordinate<f64>) {
+        self.points.0 += 1.;
+        self.points.1 += coord.x;
+        self.points.2 += coord.y;
+    }
+
+    fn add_segment(&mut self, start: &Coordinate<f64>, end: &Coordinate<f64>) {
+        let length = (end.x - start.x).hypot(end.y - start.y);
+        self.length.0 += length;
+        self.length.1 += length * (start.x + end.x) / 2.;
+        self.length.2 += length * (start.y + end.y) / 2.;
+        if length == 0. {
+            self.add_point(start);
+        }
+    }
+
+    fn add_line(&mut self, line: &LineString<f64>) {
+        for segment in line.lines() {
+            self.add_segment(&segment.start, &segment.end);
+        }
+    }
+
+    fn add_polygon(&mut self, poly: &Polygon<f64>) {
+        self.add_ring(poly.exterior(), 1.);
+        for interior in poly.interiors() {
+            self.add_ring(interior, -1.);
+        }
+        // A polygon without an area still has a boundary to fall back on
+        self.add_line(poly.exterior());
+    }
+
+    fn add_ring(&mut self, ring: &LineString<f64>, sign: f64) {
+        let area = ring_area(ring);
+        if area == 0. {
+            return;
+        }
+        let (mut x, mut y) = (0., 0.);
+        for line in ring.lines() {
+            let cross = line.start.x * line.end.y - line.end.x * line.start.y;
+            x += (line.start.x + line.end.x) * cross;
+            y += (line.start.y + line.end.y) * cross;
+        }
+        // x / (6 * area) is the centroid whatever the winding order, so
+        // scaling by |area| only needs the sign of the area
+        let weight = sign * area.signum() / 6.;
+        self.area.0 += sign * area.abs();
+        self.area.1 += x * weight;
+        self.area.2 += y * weight;
+    }
+
+    fn centroid(&self) -> Option<Coordinate<f64>> {
+        [self.area, self.length, self.points]
+            .iter()
+            .find(|(weight, _, _)| *weight != 0.)
+            .map(|(weight, x, y)| Coordinate {
+                x: x / weight,
+                y: y / weight,
+            })
+    }
+}
+
+/// Measures the area of a WKT geometry (see `geometry_area` and `geometry_signed_area`).
+///
+/// When `pixels_per_mm` is given, the area is converted from pixels into mm².
+///
+pub fn wkt_area(
+    wkt: &str,
+    pixels_per_mm: Option<f64>,
+    signed: bool,
+) -> Result<f64, GeometryToolsError> {
+    area(&wkt_to_geometry(wkt)?, pixels_per_mm, signed)
+}
+
+/// Measures the perimeter of a WKT geometry (see `geometry_perimeter`).
+///
+/// When `pixels_per_mm` is given, the perimeter is converted from pixels into mm.
+///
+pub fn wkt_perimeter(wkt: &str, pixels_per_mm: Option<f64>) -> Result<f64, GeometryToolsError> {
+    Ok(geometry_perimeter(&wkt_to_geometry(wkt)?) / scale(pixels_per_mm)?)
+}
+
+/// Finds the centroid of a WKT geometry (see `geometry_centroid`).
+///
+/// When `pixels_per_mm` is given, the centroid is converted from pixels into mm.
+///
+pub fn wkt_centroid(
+    wkt: &str,
+    pixels_per_mm: Option<f64>,
+) -> Result<Option<Coordinate<f64>>, GeometryToolsError> {
+    centroid(&wkt_to_geometry(wkt)?, pixels_per_mm)
+}
+
+/// Measures the area of an SVG element (see `geometry_area` and `geometry_signed_area`).
+///
+/// When `pixels_per_mm` is given, the area is converted from pixels into mm².
+///
+pub fn svg_area(
+    svg: &str,
+    pixels_per_mm: Option<f64>,
+    signed: bool,
+) -> Result<f64, GeometryToolsError> {
+    area(&svg_to_geometry_or_collection(svg)?, pixels_per_mm, signed)
+}
+
+/// Measures the perimeter of an SVG element (see `geometry_perimeter`).
+///
+/// When `pixels_per_mm` is given, the perimeter is converted from pixels into mm.
+///
+pub fn svg_perimeter(svg: &str, pixels_per_mm: Option<f64>) -> Result<f64, GeometryToolsError> {
+    Ok(geometry_perimeter(&svg_to_geometry_or_collection(svg)?) / scale(pixels_per_mm)?)
+}
+
+/// Finds the centroid of an SVG element (see `geometry_centroid`).
+///
+/// When `pixels_per_mm` is given, the centroid is converted from pixels into mm.
+///
+pub fn svg_centroid(
+    svg: &str,
+    pixels_per_mm: Option<f64>,
+) -> Result<Option<Coordinate<f64>>, GeometryToolsError> {
+    centroid(&svg_to_geometry_or_collection(svg)?, pixels_per_mm)
+}
+
+fn area(
+    geom: &Geometry<f64>,
+    pixels_per_mm: Option<f64>,
+    signed: bool,
+) -> Result<f64, GeometryToolsError> {
+    let scale = scale(pixels_per_mm)?;
+    let area = if signed {
+        geometry_signed_area(geom)
+    } else {
+        geometry_area(geom)
+    };
+    Ok(area / (scale * scale))
+}
+
+fn centroid(
+    geom: &Geometry<f64>,
+    pixels_per_mm: Option<f64>,
+) -> Result<Option<Coordinate<f64>>, GeometryToolsError> {
+    let scale = scale(pixels_per_mm)?;
+    Ok(geometry_centroid(geom).map(|c| Coordinate {
+        x: c.x / scale,
+        y: c.y / scale,
+    }))
+}
+
+fn scale(pixels_per_mm: Option<f64>) -> Result<f64, GeometryToolsError> {
+    match pixels_per_mm {
+        None => Ok(1.),
+        Some(scale) if scale.is_finite() && scale > 0. => Ok(scale),
+        Some(_) => Err(json_errors::argument_error::invalid_argument(
+            "pixelsPerMm",
+            "The scale must be a positive number",
+        )),
+    }
+}
+
+/// Returns the smallest axis aligned rectangle that contains a Geometry,
+/// or None for an empty geometry.
+///
+pub fn geometry_bounding_rect(geom: &Geometry<f64>) -> Option<Rect<f64>> {
+    match geom {
+        Geometry::Point(point) => Some(Rect {
+            min: point.0,
+            max: point.0,
+        }),
+        Geometry::Line(line) => Some(line.bounding_rect()),
+        Geometry::LineString(line) => line.bounding_rect(),
+        Geometry::Polygon(poly) => poly.bounding_rect(),
+        Geometry::MultiPoint(points) => points.bounding_rect(),
+        Geometry::MultiLineString(lines) => lines.bounding_rect(),
+        Geometry::MultiPolygon(polys) => polys.bounding_rect(),
+        Geometry::GeometryCollection(geoms) => geoms
+            .0
+            .iter()
+            .filter_map(geometry_bounding_rect)
+            .fold(None, |merged: Option<Rect<f64>>, rect| match merged {
+                Some(merged) => Some(Rect {
+                    min: (merged.min.x.min(rect.min.x), merged.min.y.min(rect.min.y)).into(),
+                    max: (merged.max.x.max(rect.max.x), merged.max.y.max(rect.max.y)).into(),
+                }),
+                None => Some(rect),
+            }),
+    }
+}
+
+/// The formats an envelope can be written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvelopeFormat {
+    /// A WKT POLYGON
+    Wkt,
+    /// An SVG <rect> element
+    Svg,
+}
+
+impl EnvelopeFormat {
+    /// Reads the format from its name, "wkt" or "svg" (in any case)
+    pub fn parse(format: &str) -> Result<Self, GeometryToolsError> {
+        match format.to_ascii_lowercase().as_str() {
+            "wkt" => Ok(EnvelopeFormat::Wkt),
+            "svg" => Ok(EnvelopeFormat::Svg),
+            _ => Err(json_errors::argument_error::invalid_argument(
+                "format",
+                "The format must be either \"wkt\" or \"svg\"",
+            )),
+        }
+    }
+}
+
+/// Writes a bounding rectangle as a WKT POLYGON or an SVG <rect>.
+///
+/// A missing rectangle (from an empty geometry) is written as "POLYGON EMPTY"
+/// or as an empty string, matching how empty geometries are written as SVG.
+///
+pub fn rect_to_envelope(rect: Option<Rect<f64>>, format: EnvelopeFormat) -> String {
+    match (rect, format) {
+        (Some(rect), EnvelopeFormat::Wkt) => format!(
+            "POLYGON(({0} {1},{2} {1},{2} {3},{0} {3},{0} {1}))",
+            rect.min.x, rect.min.y, rect.max.x, rect.max.y
+        ),
+        (Some(rect), EnvelopeFormat::Svg) => format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/>",
+            rect.min.x,
+            rect.min.y,
+            rect.max.x - rect.min.x,
+            rect.max.y - rect.min.y
+        ),
+        (None, EnvelopeFormat::Wkt) => "POLYGON EMPTY".into(),
+        (None, EnvelopeFormat::Svg) => "".into(),
+    }
+}
+
+/// Finds the bounding rectangle of a WKT geometry, or None for an empty geometry.
+///
+pub fn wkt_bounding_box(wkt: &str) -> Result<Option<Rect<f64>>, GeometryToolsError> {
+    Ok(geometry_bounding_rect(&wkt_to_geometry(wkt)?))
+}
+
+/// Finds the bounding rectangle of an SVG element, or None for an empty element.
+///
+pub fn svg_bounding_box(svg: &str) -> Result<Option<Rect<f64>>, GeometryToolsError> {
+    Ok(geometry_bounding_rect(&svg_to_geometry_or_collection(svg)?))
+}
+
+/// Writes the bounding rectangle of a WKT geometry in the requested format.
+///
+pub fn wkt_envelope(wkt: &str, format: EnvelopeFormat) -> Result<String, GeometryToolsError> {
+    Ok(rect_to_envelope(wkt_bounding_box(wkt)?, format))
+}
+
+/// Writes the bounding rectangle of an SVG element in the requested format.
+///
+pub fn svg_envelope(svg: &str, format: EnvelopeFormat) -> Result<String, GeometryToolsError> {
+    Ok(rect_to_envelope(svg_bounding_box(svg)?, format))
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_wkt_geometry_types() {
+        let cases = [
+            ("POLYGON((0 0,1 0,1 1,0 0))", "POLYGON", "XY", false, None),
+            (
+                "  multipolygon (((0 0,1 0,1 1,0 0)))",
+                "MULTIPOLYGON",
+                "XY",
+                false,
+                None,
+            ),
+            (
+                "MultiPolygon(((0 0,1 0,1 1,0 0)))",
+                "MULTIPOLYGON",
+                "XY",
+                false,
+                None,
+            ),
+            ("MULTIPOINT EMPTY", "MULTIPOINT", "XY", true, None),
+            ("POINT Z (1 2 3)", "POINT", "XYZ", false, None),
+            ("POINTZM(1 2 3 4)", "POINT", "XYZM", false, None),
+            ("LineString M EMPTY", "LINESTRING", "XYM", true, None),
+            ("TRIANGLE((0 0,1 0,1 1,0 0))", "TRIANGLE", "XY", false, None),
+            (
+                "SRID=4326;POLYGON((0 0,1 0,1 1,0 0))",
+                "POLYGON",
+                "XY",
+                false,
+                Some(4326),
+            ),
+            (
+                "srid=3857; GeometryCollection EMPTY",
+                "GEOMETRYCOLLECTION",
+                "XY",
+                true,
+                Some(3857),
+            ),
+        ];
+        for (wkt, kind, dimension, empty, srid) in cases.iter() {
+            let geom_type = wkt_geometry_type(wkt).unwrap();
+            assert_eq!(*kind, geom_type.kind.name(), "{}", wkt);
+            assert_eq!(*dimension, geom_type.dimension.name(), "{}", wkt);
+            assert_eq!(*empty, geom_type.empty, "{}", wkt);
+            assert_eq!(*srid, geom_type.srid, "{}", wkt);
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_wkt_geometry_types() {
+        let errors = [
+            ("", "WKT_PARSE_ERROR"),
+            ("   ", "WKT_PARSE_ERROR"),
+            ("CIRCLE(0 0, 1)", "INVALID_WKT_TYPE"),
+            ("POLYGONX((0 0))", "INVALID_WKT_TYPE"),
+            ("POLYGON 0 0", "WKT_PARSE_ERROR"),
+            ("SRID=abc;POINT(1 2)", "WKT_PARSE_ERROR"),
+        ];
+        for (wkt, code) in errors.iter() {
+            assert_eq!(*code, wkt_geometry_type(wkt).unwrap_err().code(), "{}", wkt);
+        }
+    }
+
+    #[test]
+    fn area_ignores_winding_order() {
+        let ccw = wkt_to_geometry("POLYGON((0 0,10 0,10 10,0 10,0 0),(3 3,3 6,6 6,6 3,3 3))");
+        let cw = wkt_to_geometry("POLYGON((0 0,0 10,10 10,10 0,0 0),(3 3,6 3,6 6,3 6,3 3))");
+        assert_eq!(91., geometry_area(&ccw.unwrap()));
+        assert_eq!(91., geometry_area(&cw.unwrap()));
+    }
+
+    #[test]
+    fn signed_area_follows_winding_order() {
+        let ccw = wkt_to_geometry("POLYGON((0 0,10 0,10 10,0 10,0 0),(3 3,3 6,6 6,6 3,3 3))");
+        let cw = wkt_to_geometry("POLYGON((0 0,0 10,10 10,10 0,0 0),(3 3,6 3,6 6,3 6,3 3))");
+        assert_eq!(91., geometry_signed_area(&ccw.unwrap()));
+        assert_eq!(-91., geometry_signed_area(&cw.unwrap()));
+    }
+
+    #[test]
+    fn can_measure_multi_polygon_with_scale() {
+        let wkt = "MULTIPOLYGON(((0 0,10 0,10 10,0 10,0 0),(3 3,3 6,6 6,6 3,3 3)),((20 0,30 0,30 10,20 10,20 0)))";
+        assert_eq!(191., wkt_area(wkt, None, false).unwrap());
+        assert_eq!(47.75, wkt_area(wkt, Some(2.), false).unwrap());
+        assert_eq!(92., wkt_perimeter(wkt, None).unwrap());
+        assert_eq!(46., wkt_perimeter(wkt, Some(2.)).unwrap());
+        assert_eq!(
+            "INVALID_ARGUMENT",
+            wkt_area(wkt, Some(0.), false).unwrap_err().code()
+        );
+    }
+
+    #[test]
+    fn centroid_takes_out_holes() {
+        let centroid = wkt_centroid("POLYGON((0 0,4 0,4 4,0 4,0 0),(0 0,0 2,2 2,2 0,0 0))", None);
+        let centroid = centroid.unwrap().unwrap();
+        assert!((centroid.x - 7. / 3.).abs() < 1e-12);
+        assert!((centroid.y - 7. / 3.).abs() < 1e-12);
+
+        let centroid = svg_centroid(r#"<path d="M0 0L4 0L4 4L0 4Z"/>"#, Some(2.));
+        assert_eq!(Some(Coordinate { x: 1., y: 1. }), centroid.unwrap());
+
+        let centroid = wkt_centroid("LINESTRING(0 0,10 0)", None).unwrap();
+        assert_eq!(Some(Coordinate { x: 5., y: 0. }), centroid);
+    }
+
+    #[test]
+    fn can_write_envelopes() {
+        let wkt = "MULTIPOLYGON(((0 0,10 0,10 10,0 10,0 0)),((20 -5,30 -5,30 5,20 5,20 -5)))";
+        let rect = wkt_bounding_box(wkt).unwrap().unwrap();
+        assert_eq!((0., -5.), rect.min.x_y());
+        assert_eq!((30., 10.), rect.max.x_y());
+        assert_eq!(
+            "POLYGON((0 -5,30 -5,30 10,0 10,0 -5))",
+            wkt_envelope(wkt, EnvelopeFormat::Wkt).unwrap()
+        );
+        assert_eq!(
+            r#"<rect x="0" y="-5" width="30" height="15"/>"#,
+            wkt_envelope(wkt, EnvelopeFormat::Svg).unwrap()
+        );
+        assert_eq!(
+            "POLYGON EMPTY",
+            wkt_envelope("POLYGON EMPTY", EnvelopeFormat::Wkt).unwrap()
+        );
+        assert_eq!(
+            r#"<rect x="1.5" y="2" width="3" height="4"/>"#,
+            svg_envelope(r#"<polyline points="1.5 2, 4.5 6"/>"#, EnvelopeFormat::Svg).unwrap()
+        );
+        assert!(EnvelopeFormat::parse("png").is_err());
+    }
+
+    #[test]
+    fn can_get_bounding_rect_of_collection() {
+        let geom =
+            wkt_to_geometry("GEOMETRYCOLLECTION(POINT(-1 20),POLYGON((0 0,10 0,10 10,0 10,0 0)))");
+        let rect = geometry_bounding_rect(&geom.unwrap()).unwrap();
+        assert_eq!((-1., 0.), rect.min.x_y());
+        assert_eq!((10., 20.), rect.max.x_y());
+    }
+}
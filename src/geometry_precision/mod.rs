@@ -0,0 +1,317 @@
+use crate::error::GeometryToolsError;
+use crate::json_errors;
+use geo_types::{
+    Coordinate, Geometry, GeometryCollection, Line, LineString, MultiLineString, MultiPoint,
+    MultiPolygon, Point, Polygon,
+};
+
+/// The largest number of decimal places that an f64 can still round to
+pub const MAX_DECIMAL_PLACES: u32 = 15;
+
+/// The largest number of significant digits that an f64 can hold
+pub const MAX_SIGNIFICANT_DIGITS: u32 = 17;
+
+/// How the coordinates of a geometry are rounded before it is written
+///
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Precision {
+    /// The coordinates are written as they are
+    #[default]
+    Full,
+    /// The coordinates are rounded to a number of decimal places
+    DecimalPlaces(u32),
+    /// The coordinates are rounded to a number of significant digits
+    SignificantDigits(u32),
+    /// The coordinates are snapped to the integer (pixel) grid
+    IntegerGrid,
+    /// The coordinates are snapped to the multiples of a grid size
+    Grid(f64),
+}
+
+impl Precision {
+    /// Reads the precision from a number of digits and the name of a mode:
+    /// "decimals" (the default when only digits are given), "significant",
+    /// or "grid", which does not take any digits.
+    ///
+    /// Without digits or a mode, the coordinates keep their full precision.
+    pub fn parse(digits: Option<u32>, mode: Option<&str>) -> Result<Self, GeometryToolsError> {
+        let mode = mode.map(str::to_ascii_lowercase);
+        match (mode.as_deref(), digits) {
+            (None, None) => Ok(Precision::Full),
+            (Some("grid"), _) => Ok(Precision::IntegerGrid),
+            (None, Some(places)) | (Some("decimals"), Some(places)) => {
+                if places > MAX_DECIMAL_PLACES {
+                    return Err(json_errors::argument_error::invalid_argument(
+                        "precision",
+                        &format!("At most {} decimal places can be kept", MAX_DECIMAL_PLACES),
+                    ));
+                }
+                Ok(Precision::DecimalPlaces(places))
+            }
+            (Some("significant"), Some(digits)) => {
+                if digits == 0 || digits > MAX_SIGNIFICANT_DIGITS {
+                    return Err(json_errors::argument_error::invalid_argument(
+                        "precision",
+                        &format!(
+                            "Between 1 and {} significant digits can be kept",
+                            MAX_SIGNIFICANT_DIGITS
+                        ),
+                    ));
+                }
+                Ok(Precision::SignificantDigits(digits))
+            }
+            (Some("decimals"), None) | (Some("significant"), None) => {
+                Err(json_errors::argument_error::invalid_argument(
+                    "precision",
+                    "The number of digits is required by this precision mode",
+                ))
+            }
+            (Some(_), _) => Err(json_errors::argument_error::invalid_argument(
+                "precisionMode",
+                "The precision mode must be \"decimals\", \"significant\", or \"grid\"",
+            )),
+        }
+    }
+
+    /// Rounds a single value
+    pub fn round(self, value: f64) -> f64 {
+        if !value.is_finite() {
+            return value;
+        }
+        // Adding 0 turns a rounded -0 into 0
+        match self {
+            Precision::Full => value,
+            Precision::DecimalPlaces(places) => round_to_decimals(value, places as i32) + 0.,
+            Precision::SignificantDigits(digits) => {
+                if value == 0. {
+                    return 0.;
+                }
+                let magnitude = value.abs().log10().floor() as i32;
+                round_to_decimals(value, digits as i32 - 1 - magnitude) + 0.
+            }
+            Precision::IntegerGrid => value.round() + 0.,
+            Precision::Grid(size) => snap_to_grid(value, size) + 0.,
+        }
+    }
+
+    /// Rounds both values of a coordinate
+    pub fn round_coordinate(self, coord: Coordinate<f64>) -> Coordinate<f64> {
+        Coordinate {
+            x: self.round(coord.x),
+            y: self.round(coord.y),
+        }
+    }
+}
+
+/// Rounds to a number of decimal places, which may be negative to round to tens,
+/// hundreds, etc.
+///
+/// Dividing by a power of ten (rather than multiplying by its inverse) gives the
+/// f64 closest to the decimal value, so it is written without trailing noise.
+fn round_to_decimals(value: f64, places: i32) -> f64 {
+    if places >= 0 {
+        let factor = 10f64.powi(places);
+        (value * factor).round() / factor
+    } else {
+        let factor = 10f64.powi(-places);
+        (value / factor).round() * factor
+    }
+}
+
+/// Snaps a value to the nearest multiple of the grid size.
+///
+/// A grid size that divides 1 (such as 0.1 or 0.25) divides the number of steps
+/// by its inverse, for the same reason as in `round_to_decimals`.
+pub fn snap_to_grid(value: f64, size: f64) -> f64 {
+    let steps = (value / size).round();
+    let inverse = (1. / size).round();
+    if inverse >= 1. && (inverse * size - 1.).abs() < 1e-12 {
+        steps / inverse
+    } else {
+        steps * size
+    }
+}
+
+/// Rounds every coordinate of a Geometry to the given precision.
+///
+/// Consecutive vertices that end up on the same position are merged into one.
+/// Rings that collapse to fewer than three distinct vertices are removed: a
+/// collapsed hole is dropped, a collapsed shell leaves an empty polygon, and
+/// the empty polygons of a MULTIPOLYGON are left out. Line strings of a
+/// MULTILINESTRING that collapse to a single point are left out too.
+///
+pub fn geometry_with_precision(geom: &Geometry<f64>, precision: Precision) -> Geometry<f64> {
+    if precision == Precision::Full {
+        return geom.clone();
+    }
+    match geom {
+        Geometry::Point(point) => Geometry::Point(Point(precision.round_coordinate(point.0))),
+        Geometry::Line(line) => Geometry::Line(Line::new(
+            precision.round_coordinate(line.start),
+            precision.round_coordinate(line.end),
+        )),
+        Geometry::LineString(line) => Geometry::LineString(round_line_string(line, precision)),
+        Geometry::Polygon(poly) => Geometry::Polygon(round_polygon(poly, precision)),
+        Geometry::MultiPoint(points) => Geometry::MultiPoint(MultiPoint(
+            points
+                .0
+                .iter()
+                .map(|point| Point(precision.round_coordinate(point.0)))
+                .collect(),
+        )),
+        Geometry::MultiLineString(lines) => Geometry::MultiLineString(MultiLineString(
+            lines
+                .0
+                .iter()
+                .map(|line| round_line_string(line, precision))
+                .filter(|line| line.0.len() > 1)
+                .collect(),
+        )),
+        Geometry::MultiPolygon(polys) => {
+            Geometry::MultiPolygon(multi_polygon_with_precision(polys, precision))
+        }
+        Geometry::GeometryCollection(geoms) => Geometry::GeometryCollection(GeometryCollection(
+            geoms
+                .0
+                .iter()
+                .map(|member| geometry_with_precision(member, precision))
+                .collect(),
+        )),
+    }
+}
+
+/// Rounds every coordinate of a MultiPolygon to the given precision
+/// (see `geometry_with_precision`).
+///
+pub fn multi_polygon_with_precision(
+    polys: &MultiPolygon<f64>,
+    precision: Precision,
+) -> MultiPolygon<f64> {
+    MultiPolygon(
+        polys
+            .0
+            .iter()
+            .map(|poly| round_polygon(poly, precision))
+            .filter(|poly| !poly.exterior().0.is_empty())
+            .collect(),
+    )
+}
+
+fn round_line_string(line: &LineString<f64>, precision: Precision) -> LineString<f64> {
+    let mut coords = line
+        .0
+        .iter()
+        .map(|coord| precision.round_coordinate(*coord))
+        .collect::<Vec<Coordinate<f64>>>();
+    coords.dedup();
+    LineString(coords)
+}
+
+fn round_polygon(poly: &Polygon<f64>, precision: Precision) -> Polygon<f64> {
+    let exterior = round_line_string(poly.exterior(), precision);
+    if ring_is_collapsed(&exterior) {
+        return Polygon::new(LineString(vec![]), vec![]);
+    }
+    Polygon::new(
+        exterior,
+        poly.interiors()
+            .iter()
+            .map(|ring| round_line_string(ring, precision))
+            .filter(|ring| !ring_is_collapsed(ring))
+            .collect(),
+    )
+}
+
+/// A closed ring needs at least three distinct vertices plus the closing one
+fn ring_is_collapsed(ring: &LineString<f64>) -> bool {
+    ring.0.len() < 4
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry_convert::{geometry_to_wkt, wkt_to_geometry};
+
+    fn round_wkt(wkt: &str, precision: Precision) -> String {
+        geometry_to_wkt(&geometry_with_precision(
+            &wkt_to_geometry(wkt).unwrap(),
+            precision,
+        ))
+    }
+
+    #[test]
+    fn rounds_to_decimal_places_and_significant_digits() {
+        assert_eq!(1024., Precision::DecimalPlaces(2).round(1023.9999999999998));
+        assert_eq!(0.3, Precision::DecimalPlaces(1).round(0.1 + 0.2));
+        assert_eq!(0., Precision::DecimalPlaces(0).round(-0.2));
+        assert_eq!(1230., Precision::SignificantDigits(3).round(1234.5));
+        assert_eq!(0.00123, Precision::SignificantDigits(3).round(0.0012345));
+        assert_eq!(12., Precision::IntegerGrid.round(11.5));
+        assert_eq!(0.3, Precision::Grid(0.1).round(0.29));
+        assert_eq!(7.5, Precision::Grid(2.5).round(8.));
+        assert_eq!(
+            "POLYGON((0 0,10.12 0,10.12 10,0 0))",
+            round_wkt(
+                "POLYGON((0 0,10.123456 0,10.1249 9.9999999,0 0))",
+                Precision::DecimalPlaces(2)
+            )
+        );
+    }
+
+    #[test]
+    fn snaps_to_the_grid_and_merges_vertices() {
+        assert_eq!(
+            "POLYGON((0 0,10 0,10 10,0 10,0 0))",
+            round_wkt(
+                "POLYGON((0 0,9.8 0.1,10.2 -0.2,10 10,0 10,0.1 0.1))",
+                Precision::IntegerGrid
+            )
+        );
+        assert_eq!(
+            "MULTIPOLYGON(((0 0,10 0,10 10,0 0)))",
+            round_wkt(
+                "MULTIPOLYGON(((0 0,10 0,10 10,0 0)),((20 20,20.2 20,20.2 20.2,20 20)))",
+                Precision::IntegerGrid
+            )
+        );
+        assert_eq!(
+            "POLYGON((0 0,10 0,10 10,0 10,0 0))",
+            round_wkt(
+                "POLYGON((0 0,10 0,10 10,0 10,0 0),(4 4,4.2 4,4.2 4.2,4 4))",
+                Precision::IntegerGrid
+            )
+        );
+    }
+
+    #[test]
+    fn parses_precision_arguments() {
+        assert_eq!(Precision::Full, Precision::parse(None, None).unwrap());
+        assert_eq!(
+            Precision::DecimalPlaces(3),
+            Precision::parse(Some(3), None).unwrap()
+        );
+        assert_eq!(
+            Precision::SignificantDigits(6),
+            Precision::parse(Some(6), Some("Significant")).unwrap()
+        );
+        assert_eq!(
+            Precision::IntegerGrid,
+            Precision::parse(None, Some("grid")).unwrap()
+        );
+        for (digits, mode) in [
+            (Some(16), None),
+            (Some(0), Some("significant")),
+            (None, Some("decimals")),
+            (Some(2), Some("pixels")),
+        ]
+        .iter()
+        {
+            assert_eq!(
+                "INVALID_ARGUMENT",
+                Precision::parse(*digits, *mode).unwrap_err().code()
+            );
+        }
+    }
+}
@@ -0,0 +1,557 @@
+mod relate;
+
+use crate::error::GeometryToolsError;
+use crate::geometry_convert::{svg_to_geometry_or_collection, wkt_to_geometry};
+use crate::geometry_information::geometry_area;
+use crate::json_errors;
+use geo_types::{Coordinate, Geometry, LineString, Polygon};
+use std::fmt;
+
+/// Where a point lies relative to a geometry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointLocation {
+    Interior = 0,
+    Boundary = 1,
+    Exterior = 2,
+}
+
+/// Finds where a point lies relative to a Geometry, following the OGC rules.
+///
+/// The boundary of a polygon is formed by its rings, and the boundary of a
+/// line by its two end points (a closed line has no boundary). In a multi line,
+/// only the end points shared by an odd number of lines are on the boundary.
+/// Points have no boundary. In a collection, the interior of one member takes
+/// precedence over the boundary of another.
+///
+pub fn locate_point(geom: &Geometry<f64>, point: Coordinate<f64>) -> PointLocation {
+    match geom {
+        Geometry::Point(p) => point_location(p.0 == point),
+        Geometry::MultiPoint(points) => point_location(points.0.iter().any(|p| p.0 == point)),
+        Geometry::Line(line) => locate_in_line(&[line.start, line.end], point),
+        Geometry::LineString(line) => locate_in_line(&line.0, point),
+        Geometry::MultiLineString(lines) => locate_in_lines(&lines.0, point),
+        Geometry::Polygon(poly) => locate_in_polygon(poly, point),
+        Geometry::MultiPolygon(polys) => {
+            combine(polys.0.iter().map(|poly| locate_in_polygon(poly, point)))
+        }
+        Geometry::GeometryCollection(geoms) => {
+            combine(geoms.0.iter().map(|g| locate_point(g, point)))
+        }
+    }
+}
+
+/// Tests whether a point lies in a Geometry.
+///
+/// A point inside a polygon, on a line, or equal to a point is contained.
+/// A point on the rings of a polygon or at the end of a line is only contained
+/// when `include_boundary` is true.
+///
+pub fn contains_point(
+    geom: &Geometry<f64>,
+    point: Coordinate<f64>,
+    include_boundary: bool,
+) -> bool {
+    match locate_point(geom, point) {
+        PointLocation::Interior => true,
+        PointLocation::Boundary => include_boundary,
+        PointLocation::Exterior => false,
+    }
+}
+
+/// Tests whether a point lies in a WKT geometry (see `contains_point`).
+///
+pub fn wkt_contains_point(
+    wkt: &str,
+    x: f64,
+    y: f64,
+    include_boundary: bool,
+) -> Result<bool, GeometryToolsError> {
+    let point = checked_point(x, y)?;
+    Ok(contains_point(
+        &wkt_to_geometry(wkt)?,
+        point,
+        include_boundary,
+    ))
+}
+
+/// Tests whether a point lies in an SVG element (see `contains_point`).
+///
+pub fn svg_contains_point(
+    svg: &str,
+    x: f64,
+    y: f64,
+    include_boundary: bool,
+) -> Result<bool, GeometryToolsError> {
+    let point = checked_point(x, y)?;
+    Ok(contains_point(
+        &svg_to_geometry_or_collection(svg)?,
+        point,
+        include_boundary,
+    ))
+}
+
+/// Returns the indices of the geometries that contain a point (see `contains_point`).
+///
+/// The indices are ordered by the area of their geometry, smallest first, so
+/// that the innermost of nested shapes comes first. Geometries with the same
+/// area keep their order.
+///
+pub fn hit_test(
+    geoms: &[Geometry<f64>],
+    point: Coordinate<f64>,
+    include_boundary: bool,
+) -> Vec<usize> {
+    let mut hits = geoms
+        .iter()
+        .enumerate()
+        .filter(|(_, geom)| contains_point(geom, point, include_boundary))
+        .map(|(index, geom)| (index, geometry_area(geom)))
+        .collect::<Vec<(usize, f64)>>();
+    hits.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+    hits.into_iter().map(|(index, _)| index).collect()
+}
+
+/// Returns the indices of the WKT geometries that contain a point (see `hit_test`).
+///
+/// A geometry that cannot be read causes an error that names its position
+/// in the submitted list.
+///
+pub fn wkt_hit_test(
+    wkts: &[String],
+    x: f64,
+    y: f64,
+    include_boundary: bool,
+) -> Result<Vec<usize>, GeometryToolsError> {
+    let point = checked_point(x, y)?;
+    Ok(hit_test(
+        &read_shapes(wkts, wkt_to_geometry)?,
+        point,
+        include_boundary,
+    ))
+}
+
+/// Returns the indices of the SVG elements that contain a point (see `hit_test`).
+///
+/// An element that cannot be read causes an error that names its position
+/// in the submitted list.
+///
+pub fn svg_hit_test(
+    svgs: &[String],
+    x: f64,
+    y: f64,
+    include_boundary: bool,
+) -> Result<Vec<usize>, GeometryToolsError> {
+    let point = checked_point(x, y)?;
+    Ok(hit_test(
+        &read_shapes(svgs, svg_to_geometry_or_collection)?,
+        point,
+        include_boundary,
+    ))
+}
+
+/// The DE-9IM matrix of two geometries: the dimension of the intersection of
+/// the interior, boundary and exterior of the first geometry (the rows) with
+/// the interior, boundary and exterior of the second (the columns).
+///
+/// It is written as nine characters row by row, with `F` for an empty
+/// intersection and `0`, `1` or `2` for the dimension of a non-empty one, e.g.,
+/// `212101212` for two polygons that partly overlap.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntersectionMatrix([[i8; 3]; 3]);
+
+impl IntersectionMatrix {
+    /// The dimension of the intersection of a part of the first geometry with
+    /// a part of the second, or `None` when they do not intersect
+    pub fn dimension(&self, a: PointLocation, b: PointLocation) -> Option<u8> {
+        let dimension = self.0[a as usize][b as usize];
+        if dimension == relate::EMPTY {
+            None
+        } else {
+            Some(dimension as u8)
+        }
+    }
+
+    /// The geometries have at least one point in common
+    pub fn intersects(&self) -> bool {
+        !self.disjoint()
+    }
+
+    /// The geometries have no point in common
+    pub fn disjoint(&self) -> bool {
+        self.matches("FF*FF****")
+    }
+
+    /// No point of the second geometry lies outside the first, and their interiors meet
+    pub fn contains(&self) -> bool {
+        self.matches("T*****FF*")
+    }
+
+    /// No point of the first geometry lies outside the second, and their interiors meet
+    pub fn within(&self) -> bool {
+        self.matches("T*F**F***")
+    }
+
+    /// The geometries meet, but only on their boundaries
+    pub fn touches(&self) -> bool {
+        self.matches("FT*******") || self.matches("F**T*****") || self.matches("F***T****")
+    }
+
+    /// The geometries have the same dimension, their interiors meet in that
+    /// dimension, and each has points outside the other
+    pub fn overlaps(&self) -> bool {
+        let dimension_a = self.0[0].iter().max();
+        let dimension_b = self.0.iter().map(|row| row[0]).max();
+        match (dimension_a, dimension_b) {
+            (Some(1), Some(1)) => self.matches("1*T***T**"),
+            (Some(&a), Some(b)) if a == b && a >= 0 => self.matches("T*T***T**"),
+            _ => false,
+        }
+    }
+
+    /// Tests the matrix against a pattern of nine characters, where `T` stands
+    /// for any non-empty intersection and `*` for anything
+    fn matches(&self, pattern: &str) -> bool {
+        self.0
+            .iter()
+            .flatten()
+            .zip(pattern.chars())
+            .all(|(&dimension, expected)| match expected {
+                '*' => true,
+                'T' => dimension != relate::EMPTY,
+                'F' => dimension == relate::EMPTY,
+                _ => expected.to_digit(10) == Some(dimension as u32),
+            })
+    }
+}
+
+impl fmt::Display for IntersectionMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for &dimension in self.0.iter().flatten() {
+            if dimension == relate::EMPTY {
+                write!(f, "F")?;
+            } else {
+                write!(f, "{}", dimension)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Computes the DE-9IM matrix of two geometries (see `IntersectionMatrix`).
+///
+/// Any geometries can be compared, including collections, as long as they are
+/// valid. Coordinates are compared exactly, so edges are only recognised as
+/// shared when they lie on the same line.
+///
+pub fn relate(a: &Geometry<f64>, b: &Geometry<f64>) -> IntersectionMatrix {
+    IntersectionMatrix(relate::intersection_matrix(a, b))
+}
+
+/// Computes the DE-9IM matrix of two WKT geometries (see `relate`).
+///
+pub fn wkt_relate(a: &str, b: &str) -> Result<IntersectionMatrix, GeometryToolsError> {
+    Ok(relate(&wkt_to_geometry(a)?, &wkt_to_geometry(b)?))
+}
+
+/// Computes the DE-9IM matrix of two SVG elements (see `relate`).
+///
+pub fn svg_relate(a: &str, b: &str) -> Result<IntersectionMatrix, GeometryToolsError> {
+    Ok(relate(
+        &svg_to_geometry_or_collection(a)?,
+        &svg_to_geometry_or_collection(b)?,
+    ))
+}
+
+fn checked_point(x: f64, y: f64) -> Result<Coordinate<f64>, GeometryToolsError> {
+    if !x.is_finite() || !y.is_finite() {
+        return Err(json_errors::argument_error::invalid_argument(
+            "point",
+            "The x and y coordinates of the point must be finite numbers",
+        ));
+    }
+    Ok(Coordinate { x, y })
+}
+
+fn read_shapes(
+    shapes: &[String],
+    read: fn(&str) -> Result<Geometry<f64>, GeometryToolsError>,
+) -> Result<Vec<Geometry<f64>>, GeometryToolsError> {
+    shapes
+        .iter()
+        .enumerate()
+        .map(|(index, shape)| {
+            read(shape).map_err(|err| {
+                json_errors::argument_error::invalid_argument(
+                    "shapes",
+                    &format!("The shape at index {} could not be read: {}", index, err),
+                )
+            })
+        })
+        .collect()
+}
+
+fn point_location(found: bool) -> PointLocation {
+    if found {
+        PointLocation::Interior
+    } else {
+        PointLocation::Exterior
+    }
+}
+
+/// Combines the locations of a point in the members of a multi geometry
+fn combine(locations: impl Iterator<Item = PointLocation>) -> PointLocation {
+    locations.fold(PointLocation::Exterior, |found, location| {
+        match (found, location) {
+            (PointLocation::Interior, _) | (_, PointLocation::Interior) => PointLocation::Interior,
+            (PointLocation::Boundary, _) | (_, PointLocation::Boundary) => PointLocation::Boundary,
+            _ => PointLocation::Exterior,
+        }
+    })
+}
+
+fn locate_in_lines(lines: &[LineString<f64>], point: Coordinate<f64>) -> PointLocation {
+    let mut ends = 0;
+    for line in lines {
+        match locate_in_line(&line.0, point) {
+            PointLocation::Interior => return PointLocation::Interior,
+            PointLocation::Boundary => ends += 1,
+            PointLocation::Exterior => {}
+        }
+    }
+    match ends {
+        0 => PointLocation::Exterior,
+        _ if ends % 2 == 1 => PointLocation::Boundary,
+        _ => PointLocation::Interior,
+    }
+}
+
+fn locate_in_line(coords: &[Coordinate<f64>], point: Coordinate<f64>) -> PointLocation {
+    let (first, last) = match (coords.first(), coords.last()) {
+        (Some(first), Some(last)) => (*first, *last),
+        _ => return PointLocation::Exterior,
+    };
+    if first != last && (point == first || point == last) {
+        return PointLocation::Boundary;
+    }
+    point_location(
+        coords.len() == 1 && first == point
+            || coords
+                .windows(2)
+                .any(|pair| on_segment(pair[0], pair[1], point)),
+    )
+}
+
+fn locate_in_polygon(poly: &Polygon<f64>, point: Coordinate<f64>) -> PointLocation {
+    let rings = std::iter::once(poly.exterior()).chain(poly.interiors());
+    let mut inside = false;
+    for (index, ring) in rings.enumerate() {
+        if ring
+            .0
+            .windows(2)
+            .any(|pair| on_segment(pair[0], pair[1], point))
+        {
+            return PointLocation::Boundary;
+        }
+        let in_ring = ring_contains(ring, point);
+        if index == 0 {
+            inside = in_ring;
+        } else if in_ring {
+            inside = false;
+        }
+    }
+    point_location(inside)
+}
+
+/// Finds where a point lies relative to the polygons of a Geometry, ignoring
+/// its points and lines
+fn locate_area(geom: &Geometry<f64>, point: Coordinate<f64>) -> PointLocation {
+    match geom {
+        Geometry::Polygon(poly) => locate_in_polygon(poly, point),
+        Geometry::MultiPolygon(polys) => {
+            combine(polys.0.iter().map(|poly| locate_in_polygon(poly, point)))
+        }
+        Geometry::GeometryCollection(geoms) => {
+            combine(geoms.0.iter().map(|g| locate_area(g, point)))
+        }
+        _ => PointLocation::Exterior,
+    }
+}
+
+/// Tests whether a point lies inside a ring, which may be open or closed
+fn ring_contains(ring: &LineString<f64>, point: Coordinate<f64>) -> bool {
+    let coords = &ring.0;
+    let mut inside = false;
+    for (i, &a) in coords.iter().enumerate() {
+        let b = coords[(i + 1) % coords.len()];
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+fn on_segment(a: Coordinate<f64>, b: Coordinate<f64>, point: Coordinate<f64>) -> bool {
+    (b.x - a.x) * (point.y - a.y) - (b.y - a.y) * (point.x - a.x) == 0.
+        && point.x >= a.x.min(b.x)
+        && point.x <= a.x.max(b.x)
+        && point.y >= a.y.min(b.y)
+        && point.y <= a.y.max(b.y)
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_points_in_polygons_with_holes() {
+        let wkt = "POLYGON((0 0,10 0,10 10,0 10,0 0),(4 4,6 4,6 6,4 6,4 4))";
+        assert!(wkt_contains_point(wkt, 2., 2., false).unwrap());
+        assert!(!wkt_contains_point(wkt, 5., 5., true).unwrap());
+        assert!(!wkt_contains_point(wkt, 11., 5., true).unwrap());
+        for &(x, y) in &[(0., 5.), (10., 10.), (5., 4.), (6., 5.)] {
+            assert!(wkt_contains_point(wkt, x, y, true).unwrap());
+            assert!(!wkt_contains_point(wkt, x, y, false).unwrap());
+        }
+
+        let svg = "<path d=\"M0 0L10 0L10 10L0 10ZM20 0L30 0L30 10L20 10Z\"/>";
+        assert!(svg_contains_point(svg, 25., 5., false).unwrap());
+        assert!(!svg_contains_point(svg, 15., 5., true).unwrap());
+
+        let geom = wkt_to_geometry("LINESTRING(0 0,10 0,10 10)").unwrap();
+        assert_eq!(
+            PointLocation::Interior,
+            locate_point(&geom, Coordinate { x: 5., y: 0. })
+        );
+        assert_eq!(
+            PointLocation::Boundary,
+            locate_point(&geom, Coordinate { x: 0., y: 0. })
+        );
+        assert_eq!(
+            PointLocation::Exterior,
+            locate_point(&geom, Coordinate { x: 5., y: 5. })
+        );
+        assert_eq!(
+            "INVALID_ARGUMENT",
+            wkt_contains_point(wkt, f64::NAN, 0., true)
+                .unwrap_err()
+                .code()
+        );
+    }
+
+    #[test]
+    fn hit_tests_nested_shapes_innermost_first() {
+        let shapes = vec![
+            "POLYGON((0 0,100 0,100 100,0 100,0 0))".to_string(),
+            "POLYGON((200 0,300 0,300 100,200 100,200 0))".to_string(),
+            "POLYGON((40 40,60 40,60 60,40 60,40 40))".to_string(),
+            "POLYGON((10 10,90 10,90 90,10 90,10 10))".to_string(),
+        ];
+        assert_eq!(
+            vec![2, 3, 0],
+            wkt_hit_test(&shapes, 50., 50., true).unwrap()
+        );
+        assert_eq!(vec![3, 0], wkt_hit_test(&shapes, 40., 50., false).unwrap());
+        assert_eq!(
+            Vec::<usize>::new(),
+            wkt_hit_test(&shapes, 150., 50., true).unwrap()
+        );
+
+        let svgs = vec![
+            "<rect x=\"0\" y=\"0\" width=\"100\" height=\"100\"/>".to_string(),
+            "<rect x=\"40\" y=\"40\" width=\"20\" height=\"20\"/>".to_string(),
+        ];
+        assert_eq!(vec![1, 0], svg_hit_test(&svgs, 50., 50., true).unwrap());
+        let broken = vec![svgs[0].clone(), "POLYGON((0 0,1 1))".to_string()];
+        let err = svg_hit_test(&broken, 50., 50., true).unwrap_err();
+        assert_eq!("INVALID_ARGUMENT", err.code());
+        assert!(err.to_string().contains("index 1"));
+    }
+
+    #[test]
+    fn relates_geometries_in_a_de9im_matrix() {
+        let square = "POLYGON((0 0,10 0,10 10,0 10,0 0))";
+        let cases = [
+            ("POLYGON((5 5,15 5,15 15,5 15,5 5))", "212101212"),
+            ("POLYGON((10 0,20 0,20 10,10 10,10 0))", "FF2F11212"),
+            ("POLYGON((20 0,30 0,30 10,20 10,20 0))", "FF2FF1212"),
+            ("POLYGON((0 0,0 10,10 10,10 0,0 0))", "2FFF1FFF2"),
+            ("POLYGON((2 2,8 2,8 8,2 8,2 2))", "212FF1FF2"),
+            ("POLYGON((0 0,5 0,5 5,0 5,0 0))", "212F11FF2"),
+            ("LINESTRING(-1 5,11 5)", "1F20F1102"),
+            ("LINESTRING(0 0,10 0)", "FF2101FF2"),
+            ("POINT(5 5)", "0F2FF1FF2"),
+            ("POINT(10 5)", "FF20F1FF2"),
+        ];
+        for &(other, expected) in cases.iter() {
+            assert_eq!(
+                expected,
+                wkt_relate(square, other).unwrap().to_string(),
+                "{}",
+                other
+            );
+        }
+        assert_eq!(
+            "101FF0212",
+            wkt_relate("LINESTRING(-1 5,11 5)", square)
+                .unwrap()
+                .to_string()
+        );
+        assert_eq!(
+            "1FFF0FFF2",
+            wkt_relate(
+                "MULTILINESTRING((0 0,5 0),(5 0,10 0))",
+                "LINESTRING(0 0,10 0)"
+            )
+            .unwrap()
+            .to_string()
+        );
+        // Three line ends meet at the point, so it is on the boundary
+        assert_eq!(
+            "F0FFFF102",
+            wkt_relate(
+                "POINT(5 0)",
+                "MULTILINESTRING((0 0,5 0),(5 0,10 0),(5 0,5 5))"
+            )
+            .unwrap()
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn answers_spatial_predicates() {
+        let mask = wkt_relate(
+            "POLYGON((2 2,8 2,8 8,2 8,2 2))",
+            "POLYGON((0 0,10 0,10 10,0 10,0 0),(4 4,6 4,6 6,4 6,4 4))",
+        )
+        .unwrap();
+        assert!(mask.intersects() && mask.overlaps());
+        assert!(!mask.within() && !mask.contains() && !mask.touches() && !mask.disjoint());
+
+        let inner = wkt_relate(
+            "POLYGON((1 1,3 1,3 3,1 3,1 1))",
+            "POLYGON((0 0,10 0,10 10,0 10,0 0),(4 4,6 4,6 6,4 6,4 4))",
+        )
+        .unwrap();
+        assert!(inner.within() && !inner.contains() && !inner.overlaps());
+
+        let svg = svg_relate(
+            "<rect x=\"0\" y=\"0\" width=\"10\" height=\"10\"/>",
+            "<path d=\"M10 2L14 2L14 6L10 6Z\"/>",
+        )
+        .unwrap();
+        assert!(svg.touches() && svg.intersects() && !svg.overlaps());
+        assert_eq!(
+            Some(1),
+            svg.dimension(PointLocation::Boundary, PointLocation::Boundary)
+        );
+        assert_eq!(
+            None,
+            svg.dimension(PointLocation::Interior, PointLocation::Interior)
+        );
+    }
+}
@@ -0,0 +1,475 @@
+use super::{locate_area, locate_point, on_segment, PointLocation};
+use geo_types::{Coordinate, Geometry, LineString, Polygon};
+use std::collections::{HashMap, HashSet};
+
+/// The dimension of an empty intersection
+pub(super) const EMPTY: i8 = -1;
+
+type Key = (u64, u64);
+
+/// The parts of the segments in path order, as the index of the segment and
+/// the coordinates of the part
+type Walk = Vec<(usize, Coordinate<f64>, Coordinate<f64>)>;
+
+/// Computes the DE-9IM matrix of two geometries, with the interior, boundary
+/// and exterior of `a` as rows and those of `b` as columns.
+///
+/// Every segment is split where it meets a segment of the other geometry.
+/// The matrix is then filled in from the locations of the nodes (dimension 0),
+/// of the split segments (dimension 1) and of the areas on both sides of the
+/// polygon rings (dimension 2). Segments that overlap are split at the same
+/// points, so they are recognised as one edge.
+///
+pub(super) fn intersection_matrix(a: &Geometry<f64>, b: &Geometry<f64>) -> [[i8; 3]; 3] {
+    let geoms = [a, b];
+    let mut segments = vec![];
+    let mut nodes = HashMap::new();
+    for (owner, geom) in geoms.iter().enumerate() {
+        collect_components(geom, owner, &mut segments, &mut nodes);
+    }
+    node_segments(&mut segments, &mut nodes);
+    let (mut edges, walk) = split_edges(&segments);
+    locate_along_paths(geoms, &segments, &walk, &mut edges, &mut nodes);
+
+    // The exteriors of two bounded geometries always share an area
+    let mut matrix = [
+        [EMPTY, EMPTY, EMPTY],
+        [EMPTY, EMPTY, EMPTY],
+        [EMPTY, EMPTY, 2],
+    ];
+    let mut mark = |a: PointLocation, b: PointLocation, dimension: i8| {
+        let cell = &mut matrix[a as usize][b as usize];
+        *cell = (*cell).max(dimension);
+    };
+
+    for node in nodes.values() {
+        let locate = |k: usize| node.known[k].unwrap_or_else(|| locate_point(geoms[k], node.coord));
+        mark(locate(0), locate(1), 0);
+    }
+
+    for edge in edges.values() {
+        let located =
+            |k: usize| edge.located[k].unwrap_or_else(|| locate_area(geoms[k], edge.middle()));
+        let locate = |k: usize| {
+            let side = &edge.sides[k];
+            if side.ring {
+                PointLocation::Boundary
+            } else if side.line {
+                PointLocation::Interior
+            } else {
+                located(k)
+            }
+        };
+        mark(locate(0), locate(1), 1);
+
+        if edge.sides.iter().any(|side| side.ring) {
+            let area = |k: usize, left: bool| {
+                let side = &edge.sides[k];
+                if !side.ring {
+                    located(k)
+                } else if (left && side.left) || (!left && side.right) {
+                    PointLocation::Interior
+                } else {
+                    PointLocation::Exterior
+                }
+            };
+            mark(area(0, true), area(1, true), 2);
+            mark(area(0, false), area(1, false), 2);
+        }
+    }
+
+    matrix
+}
+
+/// Locates the split edges and nodes of each geometry in the other one.
+///
+/// Along a line or ring, the location in the other geometry can only change
+/// where the path meets it, and it swaps between interior and exterior where
+/// the path crosses a ring. So the middle of an edge is only located after
+/// the path touches the other geometry, and the location is passed on to the
+/// following edges and nodes. An edge that does not run along the other
+/// geometry lies in its interior only when it lies inside its polygons.
+///
+fn locate_along_paths(
+    geoms: [&Geometry<f64>; 2],
+    segments: &[Segment],
+    walk: &Walk,
+    edges: &mut HashMap<(Key, Key), Edge>,
+    nodes: &mut HashMap<Key, Node>,
+) {
+    let mut met = [HashSet::new(), HashSet::new()] as [HashSet<Key>; 2];
+    for segment in segments {
+        let on_path = [segment.start, segment.end];
+        for &c in on_path.iter().chain(&segment.splits) {
+            met[segment.owner].insert(key(c));
+        }
+    }
+
+    let mut previous = None as Option<(usize, Coordinate<f64>)>;
+    let mut current = None as Option<PointLocation>;
+    for (index, &(segment, from, to)) in walk.iter().enumerate() {
+        let (owner, path) = (segments[segment].owner, segments[segment].path);
+        let other = 1 - owner;
+        let after = previous == Some((path, from));
+        let before = walk
+            .get(index + 1)
+            .map(|&(next, from, _)| (segments[next].path, from))
+            == Some((path, to));
+        previous = Some((path, to));
+        let edge = match edges.get_mut(&edge_key(from, to)) {
+            Some(edge) => edge,
+            None => continue,
+        };
+
+        // The nodes of a ring are on the boundary, and those inside a line in
+        // the interior, while the ends of a line are left to `locate_point`
+        let own = if edge.sides[owner].ring {
+            [Some(PointLocation::Boundary); 2]
+        } else {
+            [after, before].map(|inside| Some(PointLocation::Interior).filter(|_| inside))
+        };
+        for (&c, location) in [from, to].iter().zip(own.iter()) {
+            if let (Some(node), Some(location)) = (nodes.get_mut(&key(c)), location) {
+                node.known[owner].get_or_insert(*location);
+            }
+        }
+
+        if edge.sides[other].ring || edge.sides[other].line {
+            current = None;
+            continue;
+        }
+        let crossings = nodes
+            .get(&key(from))
+            .filter(|node| !node.vertex)
+            .map(|node| node.crossings[other]);
+        let located = match (current, crossings) {
+            (Some(location), _) if after && !met[other].contains(&key(from)) => location,
+            (Some(location), Some(crossings)) if after => {
+                if crossings % 2 == 0 {
+                    location
+                } else {
+                    match location {
+                        PointLocation::Interior => PointLocation::Exterior,
+                        PointLocation::Exterior => PointLocation::Interior,
+                        PointLocation::Boundary => PointLocation::Boundary,
+                    }
+                }
+            }
+            _ => locate_area(geoms[other], edge.middle()),
+        };
+        current = Some(located);
+        edge.located[other] = Some(located);
+        for &c in &[from, to] {
+            if met[other].contains(&key(c)) {
+                continue;
+            }
+            if let Some(node) = nodes.get_mut(&key(c)) {
+                node.known[other].get_or_insert(located);
+            }
+        }
+    }
+}
+
+/// A segment of a line or ring with the points where it has to be split
+struct Segment {
+    start: Coordinate<f64>,
+    end: Coordinate<f64>,
+    owner: usize,
+    /// The line or ring it belongs to
+    path: usize,
+    /// For a ring, whether the polygon lies on the left of the segment
+    ring: Option<bool>,
+    splits: Vec<Coordinate<f64>>,
+}
+
+/// A node, with its location in each geometry when it is known without
+/// locating it: at a computed crossing, which is not exactly on either
+/// segment, or along a path away from the other geometry
+struct Node {
+    coord: Coordinate<f64>,
+    known: [Option<PointLocation>; 2],
+    /// Whether it is a coordinate of either geometry rather than a crossing
+    vertex: bool,
+    /// The number of rings of each geometry that cross at it
+    crossings: [u32; 2],
+}
+
+/// How one geometry runs along a split edge
+#[derive(Default)]
+struct Side {
+    ring: bool,
+    line: bool,
+    left: bool,
+    right: bool,
+}
+
+/// A split edge, directed from its smallest to its largest coordinate, with
+/// its location in each geometry it does not run along
+struct Edge {
+    start: Coordinate<f64>,
+    end: Coordinate<f64>,
+    sides: [Side; 2],
+    located: [Option<PointLocation>; 2],
+}
+
+impl Edge {
+    fn middle(&self) -> Coordinate<f64> {
+        Coordinate {
+            x: (self.start.x + self.end.x) / 2.,
+            y: (self.start.y + self.end.y) / 2.,
+        }
+    }
+}
+
+fn collect_components(
+    geom: &Geometry<f64>,
+    owner: usize,
+    segments: &mut Vec<Segment>,
+    nodes: &mut HashMap<Key, Node>,
+) {
+    match geom {
+        Geometry::Point(point) => add_point(point.0, owner, segments, nodes),
+        Geometry::MultiPoint(points) => points
+            .0
+            .iter()
+            .for_each(|p| add_point(p.0, owner, segments, nodes)),
+        Geometry::Line(line) => add_path(&[line.start, line.end], owner, None, segments, nodes),
+        Geometry::LineString(line) => add_path(&line.0, owner, None, segments, nodes),
+        Geometry::MultiLineString(lines) => lines
+            .0
+            .iter()
+            .for_each(|line| add_path(&line.0, owner, None, segments, nodes)),
+        Geometry::Polygon(poly) => add_polygon(poly, owner, segments, nodes),
+        Geometry::MultiPolygon(polys) => polys
+            .0
+            .iter()
+            .for_each(|poly| add_polygon(poly, owner, segments, nodes)),
+        Geometry::GeometryCollection(geoms) => geoms
+            .0
+            .iter()
+            .for_each(|g| collect_components(g, owner, segments, nodes)),
+    }
+}
+
+fn add_polygon(
+    poly: &Polygon<f64>,
+    owner: usize,
+    segments: &mut Vec<Segment>,
+    nodes: &mut HashMap<Key, Node>,
+) {
+    let rings = std::iter::once(poly.exterior()).chain(poly.interiors());
+    for (index, ring) in rings.enumerate() {
+        // The polygon lies left of a counterclockwise shell and right of a counterclockwise hole
+        let counterclockwise = signed_area(ring) > 0.;
+        add_path(
+            &ring.0,
+            owner,
+            Some(counterclockwise == (index == 0)),
+            segments,
+            nodes,
+        );
+    }
+}
+
+fn add_path(
+    coords: &[Coordinate<f64>],
+    owner: usize,
+    ring: Option<bool>,
+    segments: &mut Vec<Segment>,
+    nodes: &mut HashMap<Key, Node>,
+) {
+    coords.iter().for_each(|&c| add_node(nodes, c));
+    let path = segments.len();
+    for pair in coords.windows(2).filter(|pair| pair[0] != pair[1]) {
+        segments.push(Segment {
+            start: pair[0],
+            end: pair[1],
+            owner,
+            path,
+            ring,
+            splits: vec![],
+        });
+    }
+}
+
+/// Adds a point as a segment of no length, so that it splits the segments of
+/// the other geometry that it lies on
+fn add_point(
+    coord: Coordinate<f64>,
+    owner: usize,
+    segments: &mut Vec<Segment>,
+    nodes: &mut HashMap<Key, Node>,
+) {
+    add_node(nodes, coord);
+    segments.push(Segment {
+        start: coord,
+        end: coord,
+        owner,
+        path: segments.len(),
+        ring: None,
+        splits: vec![],
+    });
+}
+
+fn add_node(nodes: &mut HashMap<Key, Node>, coord: Coordinate<f64>) {
+    nodes.entry(key(coord)).or_insert(Node {
+        coord,
+        known: [None, None],
+        vertex: true,
+        crossings: [0, 0],
+    });
+}
+
+/// Finds where the segments of the two geometries meet, sweeping from left
+/// to right so that only segments that overlap along x are compared
+fn node_segments(segments: &mut [Segment], nodes: &mut HashMap<Key, Node>) {
+    let min_x = |s: &Segment| s.start.x.min(s.end.x);
+    let max_x = |s: &Segment| s.start.x.max(s.end.x);
+    let mut order = (0..segments.len()).collect::<Vec<usize>>();
+    order.sort_by(|&i, &j| {
+        min_x(&segments[i])
+            .partial_cmp(&min_x(&segments[j]))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut active = [vec![], vec![]] as [Vec<usize>; 2];
+    for i in order {
+        let x = min_x(&segments[i]);
+        for list in active.iter_mut() {
+            list.retain(|&j| max_x(&segments[j]) >= x);
+        }
+        let owner = segments[i].owner;
+        for &j in &active[1 - owner] {
+            split_at_meeting(segments, i, j, nodes);
+        }
+        active[owner].push(i);
+    }
+}
+
+fn split_at_meeting(segments: &mut [Segment], i: usize, j: usize, nodes: &mut HashMap<Key, Node>) {
+    let (p1, p2) = (segments[i].start, segments[i].end);
+    let (q1, q2) = (segments[j].start, segments[j].end);
+    if p1.y.max(p2.y) < q1.y.min(q2.y) || q1.y.max(q2.y) < p1.y.min(p2.y) {
+        return;
+    }
+
+    // End points on the other segment, which covers touching and overlapping segments
+    for &c in &[q1, q2] {
+        if on_segment(p1, p2, c) {
+            segments[i].splits.push(c);
+        }
+    }
+    for &c in &[p1, p2] {
+        if on_segment(q1, q2, c) {
+            segments[j].splits.push(c);
+        }
+    }
+
+    let (d1, d2) = (cross(q1, q2, p1), cross(q1, q2, p2));
+    let (d3, d4) = (cross(p1, p2, q1), cross(p1, p2, q2));
+    let opposite = |u: f64, v: f64| (u > 0. && v < 0.) || (u < 0. && v > 0.);
+    if !opposite(d1, d2) || !opposite(d3, d4) {
+        return;
+    }
+    let t = d1 / (d1 - d2);
+    let crossing = Coordinate {
+        x: p1.x + t * (p2.x - p1.x),
+        y: p1.y + t * (p2.y - p1.y),
+    };
+    let node = nodes.entry(key(crossing)).or_insert(Node {
+        coord: crossing,
+        known: [None, None],
+        vertex: false,
+        crossings: [0, 0],
+    });
+    for &k in &[i, j] {
+        let segment = &mut segments[k];
+        segment.splits.push(crossing);
+        if segment.ring.is_some() {
+            node.known[segment.owner] = Some(PointLocation::Boundary);
+            node.crossings[segment.owner] += 1;
+        } else {
+            node.known[segment.owner] = Some(PointLocation::Interior);
+        }
+    }
+}
+
+/// Splits the segments at their nodes, merging the parts of segments that
+/// overlap, and lists the parts of every segment in the order of its path
+fn split_edges(segments: &[Segment]) -> (HashMap<(Key, Key), Edge>, Walk) {
+    let mut edges = HashMap::new() as HashMap<(Key, Key), Edge>;
+    let mut walk = vec![];
+    for (index, segment) in segments.iter().enumerate() {
+        if segment.start == segment.end {
+            continue;
+        }
+        let (start, end) = (segment.start, segment.end);
+        let along = |c: &Coordinate<f64>| {
+            (c.x - start.x) * (end.x - start.x) + (c.y - start.y) * (end.y - start.y)
+        };
+        let mut points = segment.splits.clone();
+        points.push(start);
+        points.push(end);
+        points.sort_by(|a, b| {
+            along(a)
+                .partial_cmp(&along(b))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        points.dedup();
+
+        for pair in points.windows(2) {
+            walk.push((index, pair[0], pair[1]));
+            let reversed = (pair[1].x, pair[1].y) < (pair[0].x, pair[0].y);
+            let (from, to) = if reversed {
+                (pair[1], pair[0])
+            } else {
+                (pair[0], pair[1])
+            };
+            let edge = edges.entry(edge_key(from, to)).or_insert_with(|| Edge {
+                start: from,
+                end: to,
+                sides: Default::default(),
+                located: [None, None],
+            });
+            let side = &mut edge.sides[segment.owner];
+            match segment.ring {
+                Some(polygon_on_left) => {
+                    side.ring = true;
+                    if polygon_on_left != reversed {
+                        side.left = true;
+                    } else {
+                        side.right = true;
+                    }
+                }
+                None => side.line = true,
+            }
+        }
+    }
+    (edges, walk)
+}
+
+/// The key of the edge between two coordinates, whichever way it runs
+fn edge_key(a: Coordinate<f64>, b: Coordinate<f64>) -> (Key, Key) {
+    if (b.x, b.y) < (a.x, a.y) {
+        (key(b), key(a))
+    } else {
+        (key(a), key(b))
+    }
+}
+
+fn key(c: Coordinate<f64>) -> Key {
+    // Adding zero turns -0 into 0, so that equal coordinates get the same key
+    ((c.x + 0.).to_bits(), (c.y + 0.).to_bits())
+}
+
+fn cross(a: Coordinate<f64>, b: Coordinate<f64>, c: Coordinate<f64>) -> f64 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+fn signed_area(ring: &LineString<f64>) -> f64 {
+    ring.0
+        .windows(2)
+        .map(|pair| pair[0].x * pair[1].y - pair[1].x * pair[0].y)
+        .sum::<f64>()
+        / 2.
+}
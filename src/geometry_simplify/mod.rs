@@ -0,0 +1,411 @@
+mod preserve;
+
+use crate::error::GeometryToolsError;
+use crate::geometry_convert::{
+    geometry_to_svg, geometry_to_wkt, svg_to_geometry_or_collection, wkt_to_geometry,
+};
+use crate::geometry_validate::geometry_is_valid;
+use crate::json_errors;
+use geo::algorithm::simplify::Simplify;
+use geo::algorithm::simplifyvw::SimplifyVW;
+use geo_types::{
+    Coordinate, Geometry, GeometryCollection, LineString, MultiLineString, MultiPolygon, Polygon,
+};
+use geo_validator::Validate;
+use preserve::Network;
+
+/// The algorithm used to remove vertices
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SimplifyAlgorithm {
+    /// Ramer-Douglas-Peucker: the tolerance is the largest distance between a
+    /// removed vertex and the simplified line
+    #[default]
+    DouglasPeucker,
+    /// Visvalingam-Whyatt: the tolerance is the largest area of the triangle
+    /// that a removed vertex forms with its neighbours
+    Visvalingam,
+}
+
+impl SimplifyAlgorithm {
+    /// Reads an algorithm name: "douglas-peucker" (or "dp") or "visvalingam" (or "vw").
+    ///
+    pub fn parse(algorithm: &str) -> Result<Self, GeometryToolsError> {
+        match algorithm.to_lowercase().as_str() {
+            "douglas-peucker" | "douglaspeucker" | "dp" => Ok(SimplifyAlgorithm::DouglasPeucker),
+            "visvalingam" | "visvalingam-whyatt" | "vw" => Ok(SimplifyAlgorithm::Visvalingam),
+            _ => Err(json_errors::argument_error::invalid_argument(
+                "algorithm",
+                "The algorithm must be \"douglas-peucker\" or \"visvalingam\"",
+            )),
+        }
+    }
+}
+
+/// Options for simplification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SimplifyOptions {
+    pub algorithm: SimplifyAlgorithm,
+    /// Keeps every line and ring from crossing or touching itself or any other,
+    /// and every hole inside its shell, so that a valid geometry stays valid
+    pub preserve_topology: bool,
+}
+
+impl SimplifyOptions {
+    /// Reads the options, using the defaults for the ones that are not given
+    pub fn parse(
+        algorithm: Option<&str>,
+        preserve_topology: Option<bool>,
+    ) -> Result<Self, GeometryToolsError> {
+        Ok(SimplifyOptions {
+            algorithm: algorithm
+                .map(SimplifyAlgorithm::parse)
+                .transpose()?
+                .unwrap_or_default(),
+            preserve_topology: preserve_topology.unwrap_or(false),
+        })
+    }
+}
+
+/// A simplified geometry (as WKT or SVG) with the number of vertices before and after
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimplifyReport {
+    pub geometry: String,
+    pub vertices_before: usize,
+    pub vertices_after: usize,
+}
+
+impl SimplifyReport {
+    pub fn to_json(&self) -> String {
+        serde_json::json!({
+            "geometry": self.geometry,
+            "verticesBefore": self.vertices_before,
+            "verticesAfter": self.vertices_after,
+        })
+        .to_string()
+    }
+}
+
+/// Removes the vertices of the lines and rings of a Geometry that hardly change its shape.
+///
+/// Points are returned as they are. Without `preserve_topology`, every line
+/// and ring is simplified by itself, which can make a polygon invalid; rings
+/// that collapse are dropped, and a polygon whose shell collapses becomes empty.
+///
+/// With `preserve_topology`, a vertex is only removed when no line or ring of
+/// the same member crosses, touches or ends up on the other side of the
+/// simplified line. The result passes the same checks as `geometry_is_valid`:
+/// should a numerical corner case still break one, the geometry is returned
+/// unsimplified, and should that be because the polygons were not valid to
+/// begin with, an INVALID_GEOMETRY error is returned.
+///
+pub fn simplify_geometry(
+    geom: &Geometry<f64>,
+    tolerance: f64,
+    options: &SimplifyOptions,
+) -> Result<Geometry<f64>, GeometryToolsError> {
+    if !tolerance.is_finite() || tolerance < 0. {
+        return Err(json_errors::argument_error::invalid_argument(
+            "tolerance",
+            "The tolerance must be a finite number of at least 0",
+        ));
+    }
+    if !options.preserve_topology {
+        return Ok(simplify_each(geom, tolerance, options.algorithm));
+    }
+
+    // Only the result is validated, since that is much faster than
+    // validating a detailed input
+    let simplified = simplify_together(geom, tolerance, options.algorithm);
+    if geometry_is_valid(&simplified) {
+        return Ok(simplified);
+    }
+    check_polygons(geom, geom)?;
+    Ok(geom.clone())
+}
+
+/// Simplifies a WKT geometry and reports the vertex counts (see `simplify_geometry`).
+///
+pub fn wkt_simplify(
+    wkt: &str,
+    tolerance: f64,
+    options: &SimplifyOptions,
+) -> Result<SimplifyReport, GeometryToolsError> {
+    let geom = wkt_to_geometry(wkt)?;
+    let simplified = simplify_geometry(&geom, tolerance, options)?;
+    Ok(SimplifyReport {
+        geometry: geometry_to_wkt(&simplified),
+        vertices_before: vertex_count(&geom),
+        vertices_after: vertex_count(&simplified),
+    })
+}
+
+/// Simplifies an SVG element and reports the vertex counts (see `simplify_geometry`).
+///
+pub fn svg_simplify(
+    svg: &str,
+    tolerance: f64,
+    options: &SimplifyOptions,
+) -> Result<SimplifyReport, GeometryToolsError> {
+    let geom = svg_to_geometry_or_collection(svg)?;
+    let simplified = simplify_geometry(&geom, tolerance, options)?;
+    Ok(SimplifyReport {
+        geometry: geometry_to_svg(&simplified),
+        vertices_before: vertex_count(&geom),
+        vertices_after: vertex_count(&simplified),
+    })
+}
+
+/// Counts the coordinates of a Geometry, including the closing coordinate of every ring.
+///
+pub fn vertex_count(geom: &Geometry<f64>) -> usize {
+    let rings = |poly: &Polygon<f64>| {
+        poly.exterior().0.len() + poly.interiors().iter().map(|r| r.0.len()).sum::<usize>()
+    };
+    match geom {
+        Geometry::Point(_) => 1,
+        Geometry::MultiPoint(points) => points.0.len(),
+        Geometry::Line(_) => 2,
+        Geometry::LineString(line) => line.0.len(),
+        Geometry::MultiLineString(lines) => lines.0.iter().map(|l| l.0.len()).sum(),
+        Geometry::Polygon(poly) => rings(poly),
+        Geometry::MultiPolygon(polys) => polys.0.iter().map(rings).sum(),
+        Geometry::GeometryCollection(geoms) => geoms.0.iter().map(vertex_count).sum(),
+    }
+}
+
+fn simplify_each(
+    geom: &Geometry<f64>,
+    tolerance: f64,
+    algorithm: SimplifyAlgorithm,
+) -> Geometry<f64> {
+    let line = |line: &LineString<f64>| match algorithm {
+        SimplifyAlgorithm::DouglasPeucker => line.simplify(&tolerance),
+        SimplifyAlgorithm::Visvalingam => line.simplifyvw(&tolerance),
+    };
+    let polygon = |poly: &Polygon<f64>| {
+        let collapsed = |ring: &LineString<f64>| ring.0.len() < 4;
+        let exterior = line(poly.exterior());
+        if collapsed(&exterior) {
+            return None;
+        }
+        Some(Polygon::new(
+            exterior,
+            poly.interiors()
+                .iter()
+                .map(line)
+                .filter(|ring| !collapsed(ring))
+                .collect(),
+        ))
+    };
+    match geom {
+        Geometry::LineString(l) => line(l).into(),
+        Geometry::MultiLineString(lines) => {
+            MultiLineString(lines.0.iter().map(line).collect()).into()
+        }
+        Geometry::Polygon(poly) => polygon(poly)
+            .unwrap_or_else(|| Polygon::new(LineString(vec![]), vec![]))
+            .into(),
+        Geometry::MultiPolygon(polys) => {
+            MultiPolygon(polys.0.iter().filter_map(polygon).collect()).into()
+        }
+        Geometry::GeometryCollection(geoms) => Geometry::GeometryCollection(GeometryCollection(
+            geoms
+                .0
+                .iter()
+                .map(|g| simplify_each(g, tolerance, algorithm))
+                .collect(),
+        )),
+        _ => geom.clone(),
+    }
+}
+
+/// Simplifies all the lines or rings of a geometry in one network, so that
+/// they keep their positions relative to each other
+fn simplify_together(
+    geom: &Geometry<f64>,
+    tolerance: f64,
+    algorithm: SimplifyAlgorithm,
+) -> Geometry<f64> {
+    let run = |chains: Vec<(Vec<Coordinate<f64>>, bool)>| {
+        let mut network = Network::new(chains);
+        match algorithm {
+            SimplifyAlgorithm::DouglasPeucker => network.douglas_peucker(tolerance),
+            SimplifyAlgorithm::Visvalingam => network.visvalingam(tolerance),
+        }
+        network.into_chains().into_iter().map(LineString)
+    };
+    let open = |line: &LineString<f64>| {
+        let mut coords = line.0.clone();
+        coords.dedup();
+        (coords, false)
+    };
+    let closed = |ring: &LineString<f64>| {
+        let mut coords = ring.0.clone();
+        coords.dedup();
+        if coords.len() > 1 && coords.first() == coords.last() {
+            coords.pop();
+        }
+        (coords, true)
+    };
+    let rings = |poly: &Polygon<f64>| {
+        std::iter::once(poly.exterior())
+            .chain(poly.interiors())
+            .map(closed)
+            .collect::<Vec<(Vec<Coordinate<f64>>, bool)>>()
+    };
+    // Rebuilds the polygons from the simplified rings, in the order that they were read
+    let polygons = |polys: &[Polygon<f64>]| {
+        let mut simplified = run(polys.iter().flat_map(rings).collect());
+        polys
+            .iter()
+            .map(|poly| {
+                let exterior = simplified.next().unwrap_or_else(|| LineString(vec![]));
+                Polygon::new(
+                    exterior,
+                    simplified.by_ref().take(poly.interiors().len()).collect(),
+                )
+            })
+            .collect::<Vec<Polygon<f64>>>()
+    };
+
+    match geom {
+        Geometry::LineString(line) => run(vec![open(line)])
+            .next()
+            .unwrap_or_else(|| line.clone())
+            .into(),
+        Geometry::MultiLineString(lines) => {
+            MultiLineString(run(lines.0.iter().map(open).collect()).collect()).into()
+        }
+        Geometry::Polygon(poly) => polygons(std::slice::from_ref(poly)).remove(0).into(),
+        Geometry::MultiPolygon(polys) => MultiPolygon(polygons(&polys.0)).into(),
+        Geometry::GeometryCollection(geoms) => Geometry::GeometryCollection(GeometryCollection(
+            geoms
+                .0
+                .iter()
+                .map(|g| simplify_together(g, tolerance, algorithm))
+                .collect(),
+        )),
+        _ => geom.clone(),
+    }
+}
+
+/// Returns an INVALID_GEOMETRY error for the first polygonal part that is not valid
+fn check_polygons(geom: &Geometry<f64>, source: &Geometry<f64>) -> Result<(), GeometryToolsError> {
+    let polys = match geom {
+        Geometry::Polygon(poly) => MultiPolygon(vec![poly.clone()]),
+        Geometry::MultiPolygon(polys) => polys.clone(),
+        Geometry::GeometryCollection(geoms) => {
+            return geoms.0.iter().try_for_each(|g| check_polygons(g, source));
+        }
+        _ => return Ok(()),
+    };
+    if geometry_is_valid(geom) {
+        return Ok(());
+    }
+    Err(json_errors::wkt_errors::invalid_geometry(
+        &geometry_to_wkt(source),
+        &polys.validate_detailed(),
+    ))
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry_validate::report::validation_report;
+
+    fn options(algorithm: SimplifyAlgorithm, preserve_topology: bool) -> SimplifyOptions {
+        SimplifyOptions {
+            algorithm,
+            preserve_topology,
+        }
+    }
+
+    #[test]
+    fn simplifies_lines_and_polygons() {
+        let line = "LINESTRING(0 0,5 4,11 5.5,17.3 3.2,27.8 0.1)";
+        assert_eq!(
+            "LINESTRING(0 0,5 4,11 5.5,27.8 0.1)",
+            wkt_simplify(line, 1., &SimplifyOptions::default())
+                .unwrap()
+                .geometry
+        );
+
+        let square = "POLYGON((0 0,5 0.1,10 0,10 10,0 10,0 0))";
+        for algorithm in &[
+            SimplifyAlgorithm::DouglasPeucker,
+            SimplifyAlgorithm::Visvalingam,
+        ] {
+            for &preserve in &[false, true] {
+                let report = wkt_simplify(square, 1., &options(*algorithm, preserve)).unwrap();
+                assert_eq!("POLYGON((0 0,10 0,10 10,0 10,0 0))", report.geometry);
+                assert_eq!((6, 5), (report.vertices_before, report.vertices_after));
+            }
+        }
+
+        let report = svg_simplify(
+            "<polygon points=\"0,0 5,0.1 10,0 10,10 0,10\"/>",
+            1.,
+            &SimplifyOptions::default(),
+        )
+        .unwrap();
+        assert_eq!("<path d=\"M0 0L0 10L10 10L10 0L0 0\"/>", report.geometry);
+        assert_eq!(
+            "{\"geometry\":\"<path d=\\\"M0 0L0 10L10 10L10 0L0 0\\\"/>\",\"verticesAfter\":5,\"verticesBefore\":6}",
+            report.to_json()
+        );
+    }
+
+    #[test]
+    fn preserves_topology_on_request() {
+        // Straightening the bottom edge of the shell would cut through the hole
+        let poly = "POLYGON((0 0,50 -8,100 0,100 100,0 100,0 0),(35 -4,65 -4,65 36,35 36,35 -4))";
+        let geom = wkt_to_geometry(poly).unwrap();
+        for &(algorithm, tolerance) in &[
+            (SimplifyAlgorithm::DouglasPeucker, 10.),
+            (SimplifyAlgorithm::Visvalingam, 500.),
+        ] {
+            let simplified =
+                simplify_geometry(&geom, tolerance, &options(algorithm, false)).unwrap();
+            assert!(!geometry_is_valid(&simplified));
+            let preserved = simplify_geometry(&geom, tolerance, &options(algorithm, true)).unwrap();
+            assert!(geometry_is_valid(&preserved));
+            assert_eq!(geometry_to_wkt(&geom), geometry_to_wkt(&preserved));
+        }
+
+        // Filling in the notch of the first polygon would cover the second
+        let polys = "MULTIPOLYGON(((0 0,10 0,10 10,5 9,0 10,0 0)),((3 9.8,7 9.8,7 20,3 20,3 9.8)))";
+        let geom = wkt_to_geometry(polys).unwrap();
+        let options = options(SimplifyAlgorithm::DouglasPeucker, false);
+        let simplified = simplify_geometry(&geom, 1.5, &options).unwrap();
+        assert!(!validation_report(&simplified).valid);
+        let options = SimplifyOptions {
+            preserve_topology: true,
+            ..options
+        };
+        assert_eq!(polys, wkt_simplify(polys, 1.5, &options).unwrap().geometry);
+    }
+
+    #[test]
+    fn rejects_invalid_simplify_input() {
+        let options = options(SimplifyAlgorithm::DouglasPeucker, true);
+        assert_eq!(
+            "INVALID_GEOMETRY",
+            wkt_simplify("POLYGON((0 0,10 10,10 0,0 10,0 0))", 1., &options)
+                .unwrap_err()
+                .code()
+        );
+        assert_eq!(
+            "INVALID_ARGUMENT",
+            wkt_simplify("LINESTRING(0 0,1 1)", -1., &options)
+                .unwrap_err()
+                .code()
+        );
+        assert!(SimplifyAlgorithm::parse("spline").is_err());
+        assert_eq!(
+            SimplifyAlgorithm::Visvalingam,
+            SimplifyAlgorithm::parse("VW").unwrap()
+        );
+    }
+}
@@ -0,0 +1,472 @@
+use geo_types::Coordinate;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Marks the missing neighbour at the ends of an open chain
+const NONE: usize = usize::MAX;
+
+/// A line string or ring whose vertices can be removed one section at a time.
+///
+/// The retained vertices form a linked list over the original vertices, so a
+/// ring keeps its starting point until that vertex itself is removed.
+///
+struct Chain {
+    coords: Vec<Coordinate<f64>>,
+    closed: bool,
+    next: Vec<usize>,
+    prev: Vec<usize>,
+    retained: Vec<bool>,
+    count: usize,
+}
+
+impl Chain {
+    fn new(coords: Vec<Coordinate<f64>>, closed: bool) -> Self {
+        let len = coords.len();
+        Chain {
+            next: (0..len)
+                .map(|i| match i + 1 {
+                    n if n < len => n,
+                    _ if closed => 0,
+                    _ => NONE,
+                })
+                .collect(),
+            prev: (0..len)
+                .map(|i| match i {
+                    0 if closed => len - 1,
+                    0 => NONE,
+                    _ => i - 1,
+                })
+                .collect(),
+            retained: vec![true; len],
+            count: len,
+            coords,
+            closed,
+        }
+    }
+
+    /// The retained vertices from `from` to `to` along the chain, both included
+    fn section(&self, from: usize, to: usize) -> Vec<usize> {
+        let mut section = vec![from];
+        let mut current = from;
+        while current != to {
+            current = self.next[current];
+            section.push(current);
+        }
+        section
+    }
+}
+
+/// A set of chains that are simplified together, so that no chain crosses,
+/// touches or jumps over another while its vertices are removed.
+///
+/// A section of a chain is only replaced by a straight segment when that
+/// segment does not meet any other segment, and when no other vertex lies in
+/// the area between the section and the segment. Rings also keep at least
+/// three vertices.
+///
+pub(crate) struct Network {
+    chains: Vec<Chain>,
+    grid: SegmentGrid,
+}
+
+impl Network {
+    /// Builds a network from coordinate lists, which must not repeat the
+    /// first coordinate at the end for closed chains
+    pub(crate) fn new(chains: Vec<(Vec<Coordinate<f64>>, bool)>) -> Self {
+        let chains = chains
+            .into_iter()
+            .map(|(coords, closed)| Chain::new(coords, closed))
+            .collect::<Vec<Chain>>();
+        let mut grid = SegmentGrid::new(&chains);
+        for (c, chain) in chains.iter().enumerate() {
+            for (s, &e) in chain.next.iter().enumerate() {
+                if e != NONE {
+                    grid.insert((c, s), chain.coords[s], chain.coords[e]);
+                }
+            }
+        }
+        Network { chains, grid }
+    }
+
+    /// Removes every vertex that lies within `tolerance` of the simplified
+    /// chain, splitting the sections at their farthest vertex as Douglas-Peucker does
+    pub(crate) fn douglas_peucker(&mut self, tolerance: f64) {
+        for c in 0..self.chains.len() {
+            let chain = &self.chains[c];
+            let len = chain.coords.len();
+            let mut sections = vec![] as Vec<(usize, usize)>;
+            if chain.closed && len > 3 {
+                let start = chain.coords[0];
+                let far = (1..len)
+                    .max_by(|&a, &b| {
+                        squared_distance(start, chain.coords[a])
+                            .partial_cmp(&squared_distance(start, chain.coords[b]))
+                            .unwrap_or(Ordering::Equal)
+                    })
+                    .unwrap_or(0);
+                sections.push((far, 0));
+                sections.push((0, far));
+            } else if !chain.closed && len > 2 {
+                sections.push((0, len - 1));
+            }
+
+            while let Some((from, to)) = sections.pop() {
+                let chain = &self.chains[c];
+                let (a, b) = (chain.coords[from], chain.coords[to]);
+                let farthest = chain.section(from, to)[1..]
+                    .iter()
+                    .filter(|&&v| v != to)
+                    .map(|&v| (v, segment_distance(chain.coords[v], a, b)))
+                    .fold(None, |found: Option<(usize, f64)>, (v, d)| match found {
+                        Some((_, max)) if max >= d => found,
+                        _ => Some((v, d)),
+                    });
+                let (far, distance) = match farthest {
+                    Some(farthest) => farthest,
+                    None => continue,
+                };
+                if distance <= tolerance && self.shortcut(c, from, to) {
+                    continue;
+                }
+                sections.push((far, to));
+                sections.push((from, far));
+            }
+        }
+    }
+
+    /// Removes vertices in order of the area of the triangle that they form
+    /// with their neighbours, as long as that area is within `tolerance`,
+    /// as Visvalingam-Whyatt does
+    pub(crate) fn visvalingam(&mut self, tolerance: f64) {
+        let mut queue = BinaryHeap::new();
+        for (c, chain) in self.chains.iter().enumerate() {
+            for v in 0..chain.coords.len() {
+                if let Some(candidate) = self.candidate(c, v) {
+                    queue.push(candidate);
+                }
+            }
+        }
+
+        while let Some(candidate) = queue.pop() {
+            if candidate.area > tolerance {
+                break;
+            }
+            let chain = &self.chains[candidate.chain];
+            // The neighbours have changed since the candidate was queued
+            if !chain.retained[candidate.vertex]
+                || chain.prev[candidate.vertex] != candidate.prev
+                || chain.next[candidate.vertex] != candidate.next
+            {
+                continue;
+            }
+            if !self.shortcut(candidate.chain, candidate.prev, candidate.next) {
+                continue;
+            }
+            for &v in &[candidate.prev, candidate.next] {
+                if let Some(neighbour) = self.candidate(candidate.chain, v) {
+                    queue.push(neighbour);
+                }
+            }
+        }
+    }
+
+    /// The retained coordinates of every chain, with closed chains ending on
+    /// their first coordinate again
+    pub(crate) fn into_chains(self) -> Vec<Vec<Coordinate<f64>>> {
+        self.chains
+            .into_iter()
+            .map(|chain| {
+                let mut coords = chain
+                    .coords
+                    .iter()
+                    .zip(chain.retained.iter())
+                    .filter(|(_, &retained)| retained)
+                    .map(|(coord, _)| *coord)
+                    .collect::<Vec<Coordinate<f64>>>();
+                if chain.closed && !coords.is_empty() {
+                    coords.push(coords[0]);
+                }
+                coords
+            })
+            .collect()
+    }
+
+    fn candidate(&self, c: usize, v: usize) -> Option<Candidate> {
+        let chain = &self.chains[c];
+        let (prev, next) = (chain.prev[v], chain.next[v]);
+        if prev == NONE || next == NONE || prev == next || !chain.retained[v] {
+            return None;
+        }
+        Some(Candidate {
+            area: (cross(chain.coords[prev], chain.coords[v], chain.coords[next]) / 2.).abs(),
+            chain: c,
+            vertex: v,
+            prev,
+            next,
+        })
+    }
+
+    /// Replaces the section of chain `c` between `from` and `to` with a
+    /// straight segment, unless that would change the topology of the network
+    fn shortcut(&mut self, c: usize, from: usize, to: usize) -> bool {
+        let chain = &self.chains[c];
+        let section = chain.section(from, to);
+        let removed = section.len().saturating_sub(2);
+        if removed == 0 || (chain.closed && chain.count - removed < 3) {
+            return false;
+        }
+        let (a, b) = (chain.coords[from], chain.coords[to]);
+        if a == b {
+            return false;
+        }
+
+        let points = section
+            .iter()
+            .map(|&v| chain.coords[v])
+            .collect::<Vec<Coordinate<f64>>>();
+        let own = section[..section.len() - 1]
+            .iter()
+            .copied()
+            .collect::<HashSet<usize>>();
+        let (min, max) = bounds(&points);
+        for (c2, s2) in self.grid.query(min, max) {
+            if c2 == c && own.contains(&s2) {
+                continue;
+            }
+            let other = &self.chains[c2];
+            let (p, q) = (other.coords[s2], other.coords[other.next[s2]]);
+            if segments_conflict(a, b, p, q) {
+                return false;
+            }
+            if [p, q]
+                .iter()
+                .any(|&x| x != a && x != b && inside_section(x, &points))
+            {
+                return false;
+            }
+        }
+
+        for pair in section.windows(2) {
+            self.grid
+                .remove((c, pair[0]), chain.coords[pair[0]], chain.coords[pair[1]]);
+        }
+        self.grid.insert((c, from), a, b);
+        let chain = &mut self.chains[c];
+        for &v in &section[1..section.len() - 1] {
+            chain.retained[v] = false;
+            chain.next[v] = NONE;
+            chain.prev[v] = NONE;
+        }
+        chain.next[from] = to;
+        chain.prev[to] = from;
+        chain.count -= removed;
+        true
+    }
+}
+
+/// A vertex that Visvalingam-Whyatt may remove, ordered so that the smallest
+/// area is taken from the queue first
+struct Candidate {
+    area: f64,
+    chain: usize,
+    vertex: usize,
+    prev: usize,
+    next: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .area
+            .partial_cmp(&self.area)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.chain.cmp(&self.chain))
+            .then_with(|| other.vertex.cmp(&self.vertex))
+    }
+}
+
+/// The segments of a network in square cells, so that the segments near a
+/// section can be found without testing every segment
+struct SegmentGrid {
+    origin: Coordinate<f64>,
+    size: f64,
+    cells: HashMap<(i64, i64), Vec<(usize, usize)>>,
+}
+
+impl SegmentGrid {
+    fn new(chains: &[Chain]) -> Self {
+        let coords = chains
+            .iter()
+            .flat_map(|chain| chain.coords.iter().copied())
+            .collect::<Vec<Coordinate<f64>>>();
+        let (min, max) = bounds(&coords);
+        let extent = (max.x - min.x).max(max.y - min.y);
+        let size = extent / (coords.len() as f64).sqrt().max(1.);
+        SegmentGrid {
+            origin: min,
+            size: if size.is_finite() && size > 0. {
+                size
+            } else {
+                1.
+            },
+            cells: HashMap::new(),
+        }
+    }
+
+    fn cell(&self, coord: Coordinate<f64>) -> (i64, i64) {
+        (
+            ((coord.x - self.origin.x) / self.size).floor() as i64,
+            ((coord.y - self.origin.y) / self.size).floor() as i64,
+        )
+    }
+
+    fn cells(&self, a: Coordinate<f64>, b: Coordinate<f64>) -> Vec<(i64, i64)> {
+        let (min_x, min_y) = self.cell(Coordinate {
+            x: a.x.min(b.x),
+            y: a.y.min(b.y),
+        });
+        let (max_x, max_y) = self.cell(Coordinate {
+            x: a.x.max(b.x),
+            y: a.y.max(b.y),
+        });
+        (min_x..=max_x)
+            .flat_map(|x| (min_y..=max_y).map(move |y| (x, y)))
+            .collect()
+    }
+
+    fn insert(&mut self, key: (usize, usize), a: Coordinate<f64>, b: Coordinate<f64>) {
+        for cell in self.cells(a, b) {
+            self.cells.entry(cell).or_default().push(key);
+        }
+    }
+
+    fn remove(&mut self, key: (usize, usize), a: Coordinate<f64>, b: Coordinate<f64>) {
+        for cell in self.cells(a, b) {
+            if let Some(keys) = self.cells.get_mut(&cell) {
+                keys.retain(|&k| k != key);
+            }
+        }
+    }
+
+    fn query(&self, min: Coordinate<f64>, max: Coordinate<f64>) -> Vec<(usize, usize)> {
+        let mut keys = self
+            .cells(min, max)
+            .iter()
+            .filter_map(|cell| self.cells.get(cell))
+            .flatten()
+            .copied()
+            .collect::<Vec<(usize, usize)>>();
+        keys.sort_unstable();
+        keys.dedup();
+        keys
+    }
+}
+
+fn bounds(coords: &[Coordinate<f64>]) -> (Coordinate<f64>, Coordinate<f64>) {
+    coords.iter().fold(
+        (
+            Coordinate {
+                x: f64::INFINITY,
+                y: f64::INFINITY,
+            },
+            Coordinate {
+                x: f64::NEG_INFINITY,
+                y: f64::NEG_INFINITY,
+            },
+        ),
+        |(min, max), c| {
+            (
+                Coordinate {
+                    x: min.x.min(c.x),
+                    y: min.y.min(c.y),
+                },
+                Coordinate {
+                    x: max.x.max(c.x),
+                    y: max.y.max(c.y),
+                },
+            )
+        },
+    )
+}
+
+fn cross(a: Coordinate<f64>, b: Coordinate<f64>, c: Coordinate<f64>) -> f64 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+fn squared_distance(a: Coordinate<f64>, b: Coordinate<f64>) -> f64 {
+    (a.x - b.x) * (a.x - b.x) + (a.y - b.y) * (a.y - b.y)
+}
+
+fn segment_distance(p: Coordinate<f64>, a: Coordinate<f64>, b: Coordinate<f64>) -> f64 {
+    let length = squared_distance(a, b);
+    if length == 0. {
+        return squared_distance(p, a).sqrt();
+    }
+    let t = (((p.x - a.x) * (b.x - a.x) + (p.y - a.y) * (b.y - a.y)) / length).clamp(0., 1.);
+    squared_distance(
+        p,
+        Coordinate {
+            x: a.x + t * (b.x - a.x),
+            y: a.y + t * (b.y - a.y),
+        },
+    )
+    .sqrt()
+}
+
+/// Tests whether the segments a-b and p-q meet anywhere other than at a
+/// shared end point
+fn segments_conflict(
+    a: Coordinate<f64>,
+    b: Coordinate<f64>,
+    p: Coordinate<f64>,
+    q: Coordinate<f64>,
+) -> bool {
+    if (p == a && q == b) || (p == b && q == a) {
+        return true;
+    }
+    let (d1, d2) = (cross(a, b, p), cross(a, b, q));
+    let (d3, d4) = (cross(p, q, a), cross(p, q, b));
+    let opposite = |u: f64, v: f64| (u > 0. && v < 0.) || (u < 0. && v > 0.);
+    if opposite(d1, d2) && opposite(d3, d4) {
+        return true;
+    }
+    let touches = |d: f64, s: Coordinate<f64>, e: Coordinate<f64>, x: Coordinate<f64>| {
+        d == 0.
+            && x != s
+            && x != e
+            && x.x >= s.x.min(e.x)
+            && x.x <= s.x.max(e.x)
+            && x.y >= s.y.min(e.y)
+            && x.y <= s.y.max(e.y)
+    };
+    touches(d1, a, b, p) || touches(d2, a, b, q) || touches(d3, p, q, a) || touches(d4, p, q, b)
+}
+
+/// Tests whether a point lies in the area enclosed by a section and the
+/// segment that closes it
+fn inside_section(point: Coordinate<f64>, section: &[Coordinate<f64>]) -> bool {
+    let mut inside = false;
+    for (i, &u) in section.iter().enumerate() {
+        let v = section[(i + 1) % section.len()];
+        if (u.y > point.y) != (v.y > point.y)
+            && point.x < (v.x - u.x) * (point.y - u.y) / (v.y - u.y) + u.x
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
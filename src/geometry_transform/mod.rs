@@ -0,0 +1,462 @@
+use crate::error::GeometryToolsError;
+use crate::geometry_convert::{
+    geometry_to_svg, geometry_to_wkt, svg_to_geometry_or_collection, wkt_to_geometry,
+};
+use crate::geometry_information::geometry_centroid;
+use crate::json_errors;
+use geo::algorithm::map_coords::MapCoords;
+use geo_types::{Coordinate, Geometry, GeometryCollection, LineString, MultiPolygon, Polygon};
+use std::cell::Cell;
+
+/// A 2D affine transform, stored in the same order as an SVG `matrix(a b c d e f)`.
+///
+/// A point (x, y) is mapped to (a * x + c * y + e, b * x + d * y + f).
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AffineTransform {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64,
+}
+
+impl AffineTransform {
+    /// The transform that leaves every point where it is
+    pub fn identity() -> Self {
+        AffineTransform {
+            a: 1.,
+            b: 0.,
+            c: 0.,
+            d: 1.,
+            e: 0.,
+            f: 0.,
+        }
+    }
+
+    /// Reads a transform from the six values [a, b, c, d, e, f] of a 2x3 matrix.
+    ///
+    /// It returns an error unless exactly six finite values are given.
+    ///
+    pub fn from_matrix(matrix: &[f64]) -> Result<Self, GeometryToolsError> {
+        match matrix {
+            [a, b, c, d, e, f] if matrix.iter().all(|v| v.is_finite()) => Ok(AffineTransform {
+                a: *a,
+                b: *b,
+                c: *c,
+                d: *d,
+                e: *e,
+                f: *f,
+            }),
+            _ => Err(json_errors::argument_error::invalid_argument(
+                "matrix",
+                "The matrix must contain six finite numbers [a, b, c, d, e, f]",
+            )),
+        }
+    }
+
+    /// Returns the six values [a, b, c, d, e, f] of the matrix
+    pub fn to_matrix(&self) -> Vec<f64> {
+        vec![self.a, self.b, self.c, self.d, self.e, self.f]
+    }
+
+    /// Moves every point by (x, y)
+    pub fn translate(x: f64, y: f64) -> Self {
+        AffineTransform {
+            e: x,
+            f: y,
+            ..AffineTransform::identity()
+        }
+    }
+
+    /// Scales every point away from `origin`
+    pub fn scale(x: f64, y: f64, origin: Coordinate<f64>) -> Self {
+        AffineTransform::translate(-origin.x, -origin.y)
+            .then(&AffineTransform {
+                a: x,
+                d: y,
+                ..AffineTransform::identity()
+            })
+            .then(&AffineTransform::translate(origin.x, origin.y))
+    }
+
+    /// Rotates every point about `origin` by an angle in degrees.
+    ///
+    /// Positive angles turn the x axis towards the y axis, which is counter
+    /// clockwise in y-up coordinates and clockwise on screen in SVG (the same
+    /// as the SVG `rotate()` transform). Multiples of 90 degrees turn the
+    /// points exactly, without the rounding errors of `sin` and `cos`.
+    ///
+    pub fn rotate(degrees: f64, origin: Coordinate<f64>) -> Self {
+        let quarters = degrees / 90.;
+        let (sin, cos) = if quarters.fract() == 0. {
+            match quarters.rem_euclid(4.) as u8 {
+                0 => (0., 1.),
+                1 => (1., 0.),
+                2 => (0., -1.),
+                _ => (-1., 0.),
+            }
+        } else {
+            degrees.to_radians().sin_cos()
+        };
+        AffineTransform::translate(-origin.x, -origin.y)
+            .then(&AffineTransform {
+                a: cos,
+                b: sin,
+                c: -sin,
+                d: cos,
+                ..AffineTransform::identity()
+            })
+            .then(&AffineTransform::translate(origin.x, origin.y))
+    }
+
+    /// Returns the transform that applies this transform first and `next` afterwards
+    pub fn then(&self, next: &AffineTransform) -> Self {
+        AffineTransform {
+            a: next.a * self.a + next.c * self.b,
+            b: next.b * self.a + next.d * self.b,
+            c: next.a * self.c + next.c * self.d,
+            d: next.b * self.c + next.d * self.d,
+            e: next.a * self.e + next.c * self.f + next.e,
+            f: next.b * self.e + next.d * self.f + next.f,
+        }
+    }
+
+    /// Maps a single point
+    pub fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            self.a * x + self.c * y + self.e,
+            self.b * x + self.d * y + self.f,
+        )
+    }
+}
+
+/// A transform described by its parts instead of by a matrix.
+///
+/// The geometry is first scaled and then rotated about `origin`, and is
+/// finally moved by `translate`.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransformParameters {
+    pub translate: (f64, f64),
+    /// The rotation in degrees, see `AffineTransform::rotate` for its direction
+    pub rotate: f64,
+    pub scale: (f64, f64),
+    pub origin: Coordinate<f64>,
+}
+
+impl Default for TransformParameters {
+    fn default() -> Self {
+        TransformParameters {
+            translate: (0., 0.),
+            rotate: 0.,
+            scale: (1., 1.),
+            origin: Coordinate { x: 0., y: 0. },
+        }
+    }
+}
+
+impl TransformParameters {
+    /// Builds the matrix of the transform.
+    ///
+    /// It returns an error if any of the parameters is not a finite number.
+    ///
+    pub fn to_transform(&self) -> Result<AffineTransform, GeometryToolsError> {
+        let transform = AffineTransform::scale(self.scale.0, self.scale.1, self.origin)
+            .then(&AffineTransform::rotate(self.rotate, self.origin))
+            .then(&AffineTransform::translate(
+                self.translate.0,
+                self.translate.1,
+            ));
+        AffineTransform::from_matrix(&transform.to_matrix()).map_err(|_| {
+            json_errors::argument_error::invalid_argument(
+                "parameters",
+                "The translation, rotation, scale, and origin must be finite numbers",
+            )
+        })
+    }
+}
+
+/// The direction in which a geometry is mirrored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MirrorDirection {
+    /// Swaps left and right, by reflecting across a vertical line
+    Horizontal,
+    /// Swaps top and bottom, by reflecting across a horizontal line
+    Vertical,
+}
+
+impl MirrorDirection {
+    /// Reads the direction from its name, "horizontal" or "vertical" (in any case)
+    pub fn parse(direction: &str) -> Result<Self, GeometryToolsError> {
+        match direction.to_ascii_lowercase().as_str() {
+            "horizontal" => Ok(MirrorDirection::Horizontal),
+            "vertical" => Ok(MirrorDirection::Vertical),
+            _ => Err(json_errors::argument_error::invalid_argument(
+                "direction",
+                "The direction must be either \"horizontal\" or \"vertical\"",
+            )),
+        }
+    }
+}
+
+/// Applies an affine transform to every coordinate of a Geometry.
+///
+/// A transform that mirrors the geometry would also reverse the winding order
+/// of polygon rings, so their points are put back in the original winding order.
+/// It returns an error if a transformed coordinate overflows to a non-finite number.
+///
+pub fn transform_geometry(
+    geom: &Geometry<f64>,
+    transform: &AffineTransform,
+) -> Result<Geometry<f64>, GeometryToolsError> {
+    let finite = Cell::new(true);
+    let transformed = geom.map_coords(&|&(x, y)| {
+        let (x, y) = transform.apply(x, y);
+        if !x.is_finite() || !y.is_finite() {
+            finite.set(false);
+        }
+        (x, y)
+    });
+    if !finite.get() {
+        return Err(json_errors::argument_error::invalid_argument(
+            "transform",
+            "The transform moves coordinates beyond the range of finite numbers",
+        ));
+    }
+    if transform.a * transform.d - transform.b * transform.c < 0. {
+        Ok(rewind_polygons(transformed))
+    } else {
+        Ok(transformed)
+    }
+}
+
+fn rewind_polygons(geom: Geometry<f64>) -> Geometry<f64> {
+    match geom {
+        Geometry::Polygon(poly) => Geometry::Polygon(rewind_polygon(poly)),
+        Geometry::MultiPolygon(polys) => Geometry::MultiPolygon(MultiPolygon(
+            polys.0.into_iter().map(rewind_polygon).collect(),
+        )),
+        Geometry::GeometryCollection(geoms) => Geometry::GeometryCollection(GeometryCollection(
+            geoms.0.into_iter().map(rewind_polygons).collect(),
+        )),
+        geom => geom,
+    }
+}
+
+fn rewind_polygon(poly: Polygon<f64>) -> Polygon<f64> {
+    let (exterior, interiors) = poly.into_inner();
+    let reverse = |mut ring: LineString<f64>| {
+        ring.0.reverse();
+        ring
+    };
+    Polygon::new(
+        reverse(exterior),
+        interiors.into_iter().map(reverse).collect(),
+    )
+}
+
+/// Mirrors a Geometry in place, across a line through its centroid.
+///
+pub fn mirror_geometry(
+    geom: &Geometry<f64>,
+    direction: MirrorDirection,
+) -> Result<Geometry<f64>, GeometryToolsError> {
+    let origin = match geometry_centroid(geom) {
+        Some(origin) => origin,
+        None => return Ok(geom.clone()),
+    };
+    let (x, y) = match direction {
+        MirrorDirection::Horizontal => (-1., 1.),
+        MirrorDirection::Vertical => (1., -1.),
+    };
+    transform_geometry(geom, &AffineTransform::scale(x, y, origin))
+}
+
+/// Rotates a Geometry about its own centroid by an angle in degrees.
+///
+/// It returns an error if `degrees` is not a finite number.
+///
+pub fn rotate_geometry_about_centroid(
+    geom: &Geometry<f64>,
+    degrees: f64,
+) -> Result<Geometry<f64>, GeometryToolsError> {
+    if !degrees.is_finite() {
+        return Err(json_errors::argument_error::invalid_argument(
+            "degrees",
+            "The rotation must be a finite number of degrees",
+        ));
+    }
+    match geometry_centroid(geom) {
+        Some(origin) => transform_geometry(geom, &AffineTransform::rotate(degrees, origin)),
+        None => Ok(geom.clone()),
+    }
+}
+
+/// Transforms a WKT geometry and writes the result as WKT.
+///
+pub fn wkt_transform(wkt: &str, transform: &AffineTransform) -> Result<String, GeometryToolsError> {
+    Ok(geometry_to_wkt(&transform_geometry(
+        &wkt_to_geometry(wkt)?,
+        transform,
+    )?))
+}
+
+/// Transforms an SVG element and writes the result as an SVG element.
+///
+pub fn svg_transform(svg: &str, transform: &AffineTransform) -> Result<String, GeometryToolsError> {
+    Ok(geometry_to_svg(&transform_geometry(
+        &svg_to_geometry_or_collection(svg)?,
+        transform,
+    )?))
+}
+
+/// Mirrors a WKT geometry across a line through its centroid.
+///
+pub fn wkt_mirror(wkt: &str, direction: MirrorDirection) -> Result<String, GeometryToolsError> {
+    Ok(geometry_to_wkt(&mirror_geometry(
+        &wkt_to_geometry(wkt)?,
+        direction,
+    )?))
+}
+
+/// Mirrors an SVG element across a line through its centroid.
+///
+pub fn svg_mirror(svg: &str, direction: MirrorDirection) -> Result<String, GeometryToolsError> {
+    Ok(geometry_to_svg(&mirror_geometry(
+        &svg_to_geometry_or_collection(svg)?,
+        direction,
+    )?))
+}
+
+/// Rotates a WKT geometry about its centroid by an angle in degrees.
+///
+pub fn wkt_rotate_about_centroid(wkt: &str, degrees: f64) -> Result<String, GeometryToolsError> {
+    Ok(geometry_to_wkt(&rotate_geometry_about_centroid(
+        &wkt_to_geometry(wkt)?,
+        degrees,
+    )?))
+}
+
+/// Rotates an SVG element about its centroid by an angle in degrees.
+///
+pub fn svg_rotate_about_centroid(svg: &str, degrees: f64) -> Result<String, GeometryToolsError> {
+    Ok(geometry_to_svg(&rotate_geometry_about_centroid(
+        &svg_to_geometry_or_collection(svg)?,
+        degrees,
+    )?))
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry_information::geometry_signed_area;
+
+    #[test]
+    fn can_transform_with_a_matrix() {
+        let transform = AffineTransform::from_matrix(&[2., 0., 0., 3., 10., 20.]).unwrap();
+        assert_eq!(
+            "POLYGON((10 20,12 20,12 23,10 20))",
+            wkt_transform("POLYGON((0 0,1 0,1 1,0 0))", &transform).unwrap()
+        );
+        assert!(AffineTransform::from_matrix(&[1., 0., 0., 1.]).is_err());
+        assert!(AffineTransform::from_matrix(&[1., 0., 0., 1., f64::NAN, 0.]).is_err());
+    }
+
+    #[test]
+    fn can_transform_with_parameters() {
+        let transform = TransformParameters {
+            translate: (5., 0.),
+            rotate: 90.,
+            scale: (2., 2.),
+            origin: Coordinate { x: 1., y: 1. },
+        }
+        .to_transform()
+        .unwrap();
+        let (x, y) = transform.apply(2., 1.);
+        assert!((x - 6.).abs() < 1e-12);
+        assert!((y - 3.).abs() < 1e-12);
+        assert_eq!(
+            r#"<path d="M0 0L0 2L2 2L2 0L0 0"/>"#,
+            svg_transform(
+                r#"<rect x="0" y="0" width="1" height="1"/>"#,
+                &AffineTransform::scale(2., 2., Coordinate { x: 0., y: 0. })
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn can_mirror_and_rotate_about_centroid() {
+        assert_eq!(
+            "LINESTRING(4 0,2 1,0 0)",
+            wkt_mirror("LINESTRING(0 0,2 1,4 0)", MirrorDirection::Horizontal).unwrap()
+        );
+        assert_eq!(
+            "LINESTRING(0 1,2 0,4 1)",
+            wkt_mirror("LINESTRING(0 0,2 1,4 0)", MirrorDirection::Vertical).unwrap()
+        );
+        let triangle = wkt_to_geometry("POLYGON((0 0,1 0,0 1,0 0))").unwrap();
+        let mirrored = mirror_geometry(&triangle, MirrorDirection::Horizontal).unwrap();
+        assert_eq!(0.5, geometry_signed_area(&mirrored));
+        assert!(MirrorDirection::parse("diagonal").is_err());
+
+        let square = "POLYGON((0 0,10 0,10 10,0 10,0 0))";
+        assert_eq!(
+            "POLYGON((10 0,10 10,0 10,0 0,10 0))",
+            wkt_rotate_about_centroid(square, 90.).unwrap()
+        );
+        assert_eq!(
+            "POLYGON((10 10,0 10,0 0,10 0,10 10))",
+            wkt_rotate_about_centroid(square, 180.).unwrap()
+        );
+        assert_eq!(
+            "POLYGON((0 10,0 0,10 0,10 10,0 10))",
+            wkt_rotate_about_centroid(square, -90.).unwrap()
+        );
+        assert_eq!(
+            "LINESTRING(4 -1,4 3)",
+            wkt_rotate_about_centroid("LINESTRING(2 1,6 1)", 450.).unwrap()
+        );
+
+        let rotated =
+            rotate_geometry_about_centroid(&wkt_to_geometry("LINESTRING(0 0,2 0)").unwrap(), 90.)
+                .unwrap();
+        match rotated {
+            Geometry::LineString(line) => {
+                let (x, y) = line.0[0].x_y();
+                assert!((x - 1.).abs() < 1e-12 && (y + 1.).abs() < 1e-12);
+            }
+            _ => panic!("expected a LineString"),
+        }
+    }
+
+    #[test]
+    fn errors_on_non_finite_angles_and_results() {
+        let square = "POLYGON((0 0,10 0,10 10,0 10,0 0))";
+        for degrees in &[f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert_eq!(
+                "INVALID_ARGUMENT",
+                wkt_rotate_about_centroid(square, *degrees)
+                    .unwrap_err()
+                    .code()
+            );
+        }
+        assert_eq!(
+            "INVALID_ARGUMENT",
+            svg_rotate_about_centroid(r#"<rect x="0" y="0" width="1" height="1"/>"#, f64::NAN)
+                .unwrap_err()
+                .code()
+        );
+
+        let transform = AffineTransform::from_matrix(&[1e308, 0., 0., 1e308, 0., 0.]).unwrap();
+        assert_eq!(
+            "INVALID_ARGUMENT",
+            wkt_transform(square, &transform).unwrap_err().code()
+        );
+        assert!(wkt_transform("POINT(1 1)", &transform).is_ok());
+    }
+}
@@ -0,0 +1,383 @@
+pub mod report;
+
+use crate::error::GeometryToolsError;
+use crate::geometry_convert::svg_reader::{read_svg_document, SvgReadOptions};
+use crate::geometry_convert::{
+    geometry_to_wkt, svg_to_geometry_collection, svg_to_geometry_or_collection, wkt_to_geometry,
+};
+use crate::geometry_information::{geometry_is_empty, polygon_is_empty};
+use crate::geometry_precision::{geometry_with_precision, Precision};
+use crate::json_errors;
+use geo_repair_polygon::repair::Repair;
+use geo_types::{Geometry, GeometryCollection, MultiPolygon};
+use report::{validation_report, ValidationReport};
+
+/* Validators */
+
+/// Tests whether an SVG element can represent a valid Geometry.
+/// This function can read a <path>, <polygon>. <polyline>,
+/// <rect>, and <line>, all other SVG elements will fail
+/// immediately. Every shape element of the SVG is read, so a
+/// <g> with several shapes is checked as a GeometryCollection,
+/// and every geometry read must pass the checks of `geometry_is_valid`.
+///
+pub fn svg_is_valid_geom(svg: &str) -> bool {
+    match read_svg_document(svg, &SvgReadOptions::default()) {
+        Ok(elements) => {
+            !elements.is_empty()
+                && elements
+                    .iter()
+                    .all(|element| geometry_is_valid(&element.geometry))
+        }
+        Err(_) => false,
+    }
+}
+
+/// Tests whether an SVG <path> d-string can represent a valid Geometry.
+///
+pub fn svg_path_string_is_valid_geom(d_string: &str) -> bool {
+    svg_is_valid_geom(&format!("<path d=\"{}\"/>", d_string))
+}
+
+/// Tests whether an SVG element is a valid polygon.
+/// This function can read a <path>, <polygon>. <polyline>,
+/// <rect>, and <line>, all other SVG elements will fail
+/// immediately.
+///
+pub fn validate_svg_polygon(svg: &str) -> bool {
+    let geom = match svg_to_geometry_collection(svg) {
+        Ok(geom) => geom,
+        Err(_) => return false,
+    };
+    match geom.0.as_slice() {
+        [poly @ Geometry::Polygon(_)] => geometry_is_valid(poly),
+        _ => false,
+    }
+}
+
+/// Tests whether an SVG <path> d-string is a valid polygon.
+///
+pub fn validate_svg_path_string_as_polygon(d_string: &str) -> bool {
+    validate_svg_polygon(&format!("<path d=\"{}\"/>", d_string))
+}
+
+/// Tests whether an SVG element represents a valid multi polygon geometry.
+/// This function can read a <path>, <polygon>. <polyline>,
+/// <rect>, and <line>, all other SVG elements will fail
+/// immediately.
+///
+pub fn validate_svg_multi_polygon(svg: &str) -> bool {
+    let geom = match svg_to_geometry_collection(svg) {
+        Ok(geom) => geom,
+        Err(_) => return false,
+    };
+    match geom.0.as_slice() {
+        [polys @ Geometry::MultiPolygon(_)] => geometry_is_valid(polys),
+        _ => false,
+    }
+}
+
+/// Tests whether an SVG <path> d-string represents a valid multi polygon geometry.
+///
+pub fn validate_svg_path_string_as_multi_polygon(d_string: &str) -> bool {
+    validate_svg_multi_polygon(&format!("<path d=\"{}\"/>", d_string))
+}
+
+/// Tests whether a WKT string can represent a valid Geometry.
+/// The same rules are applied as for SVG input (see `geometry_is_valid`).
+///
+pub fn wkt_is_valid_geom(wkt: &str) -> bool {
+    match wkt_to_geometry(wkt) {
+        Ok(geom) => geometry_is_valid(&geom),
+        Err(_) => false,
+    }
+}
+
+/// Tests whether a WKT string is a valid POLYGON.
+///
+pub fn validate_wkt_polygon(wkt: &str) -> bool {
+    match wkt_to_geometry(wkt) {
+        Ok(poly @ Geometry::Polygon(_)) => geometry_is_valid(&poly),
+        _ => false,
+    }
+}
+
+/// Tests whether a WKT string is a valid MULTIPOLYGON.
+///
+pub fn validate_wkt_multi_polygon(wkt: &str) -> bool {
+    match wkt_to_geometry(wkt) {
+        Ok(polys @ Geometry::MultiPolygon(_)) => geometry_is_valid(&polys),
+        _ => false,
+    }
+}
+
+/// Tests whether a Geometry is valid.
+///
+/// Polygons and multi polygons must pass every `geo_validator` rule, their holes
+/// must not overlap each other, and the polygons of a multi polygon must not
+/// overlap either. Lines and line strings need at least two distinct points,
+/// and all coordinates must be finite numbers. A geometry collection is valid
+/// when all of its members are, and empty geometries are valid.
+///
+/// These are the rules of `report::validation_report`, which lists the
+/// violations that make a geometry invalid.
+///
+pub fn geometry_is_valid(geom: &Geometry<f64>) -> bool {
+    validation_report(geom).valid
+}
+
+/// Validates a WKT geometry and reports every violation that was found.
+///
+pub fn wkt_validation_report(wkt: &str) -> Result<ValidationReport, GeometryToolsError> {
+    Ok(validation_report(&wkt_to_geometry(wkt)?))
+}
+
+/// Validates an SVG element and reports every violation that was found.
+/// This function can read a <path>, <polygon>. <polyline>,
+/// <rect>, and <line>, all other SVG elements will return
+/// an error.
+///
+pub fn svg_validation_report(svg: &str) -> Result<ValidationReport, GeometryToolsError> {
+    Ok(validation_report(&svg_to_geometry_or_collection(svg)?))
+}
+
+/// Validates an SVG <path> d-string and reports every violation that was found.
+///
+pub fn svg_path_string_validation_report(
+    d_string: &str,
+) -> Result<ValidationReport, GeometryToolsError> {
+    svg_validation_report(&format!("<path d=\"{}\"/>", d_string))
+}
+
+/// Repairs a Geometry.
+/// Only POLYGON and MULTIPOLYGON geometries can be repaired, and a
+/// GEOMETRYCOLLECTION is repaired member by member. Empty geometries
+/// are returned unchanged, and the empty members of a MULTIPOLYGON are dropped.
+///
+pub fn repair_geometry(geom: &Geometry<f64>) -> Result<Geometry<f64>, GeometryToolsError> {
+    let repaired = match geom {
+        _ if geometry_is_empty(geom) => return Ok(geom.clone()),
+        Geometry::GeometryCollection(geoms) => {
+            return Ok(Geometry::GeometryCollection(GeometryCollection(
+                geoms
+                    .0
+                    .iter()
+                    .map(repair_geometry)
+                    .collect::<Result<Vec<Geometry<f64>>, GeometryToolsError>>()?,
+            )))
+        }
+        Geometry::MultiPolygon(polys) => MultiPolygon(
+            polys
+                .0
+                .iter()
+                .filter(|poly| !polygon_is_empty(poly))
+                .cloned()
+                .collect(),
+        )
+        .repair()
+        .map(Geometry::from),
+        _ => geom.repair(),
+    };
+    repaired.ok_or_else(|| {
+        json_errors::geometry_processing_error::irreparable_geom(&geometry_to_wkt(geom))
+    })
+}
+
+/// Repairs a WKT geometry.
+///
+pub fn repair_wkt(wkt: &str) -> Result<String, GeometryToolsError> {
+    repair_wkt_with_precision(wkt, Precision::Full)
+}
+
+/// Repairs a WKT geometry and rounds the coordinates of the result to the
+/// given precision (see `geometry_with_precision`).
+///
+pub fn repair_wkt_with_precision(
+    wkt: &str,
+    precision: Precision,
+) -> Result<String, GeometryToolsError> {
+    Ok(geometry_to_wkt(&geometry_with_precision(
+        &repair_geometry(&wkt_to_geometry(wkt)?)?,
+        precision,
+    )))
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_valid_svg_geometries() {
+        let valid = [
+            r#"<path d="M0 0L10 0L10 10L0 10Z"/>"#,
+            r#"<path d="M0 0h10v10h-10z"/>"#,
+            r#"<path d="M0 0L10 0L10 10L0 10Z M2 2L4 2L4 4Z"/>"#,
+            r#"<path d="M0 0L10 0L10 10L0 10Z M20 20L30 20L30 30Z"/>"#,
+            r#"<path d="M0 0L10 0L10 10"/>"#,
+            r#"<path d="M0 0L10 0 M20 20L30 30"/>"#,
+            r#"<polygon points="0,0 10,0 10,10"/>"#,
+            r#"<polyline points="0,0 10,0 10,10"/>"#,
+            r#"<rect x="0" y="0" width="10" height="10"/>"#,
+            r#"<line x1="0" y1="0" x2="10" y2="10"/>"#,
+            r#"<g><path d="M0 0L10 0L10 10L0 10Z"/><polyline points="20,0 30,10"/><rect x="40" y="0" width="5" height="5"/></g>"#,
+            r#"<svg xmlns="http://www.w3.org/2000/svg"><g><line x1="0" y1="0" x2="10" y2="10"/></g><polygon points="0,0 10,0 10,10"/></svg>"#,
+        ];
+        for svg in valid.iter() {
+            assert!(svg_is_valid_geom(svg), "{} should be valid", svg);
+        }
+        assert!(svg_path_string_is_valid_geom("M0 0L10 0L10 10L0 10Z"));
+    }
+
+    #[test]
+    fn rejects_invalid_svg_geometries() {
+        let invalid = [
+            r#"<path d="M0 0L10 10L10 0L0 10Z"/>"#,
+            r#"<polygon points="0,0 10,10 10,0 0,10"/>"#,
+            r#"<rect x="0" y="0" width="0" height="10"/>"#,
+            r#"<path d="M5 5"/>"#,
+            r#"<path d="M5 5Z"/>"#,
+            r#"<polyline points="0,0 0,0"/>"#,
+            r#"<polyline points="0,0"/>"#,
+            r#"<line x1="0" y1="0" x2="0" y2="0"/>"#,
+            r#"<circle cx="5" cy="5" r="4"/>"#,
+            r#"<path d=""/>"#,
+            "",
+            r#"<g><path d="M0 0L10 0L10 10L0 10Z"/><path d="M0 0L10 10L10 0L0 10Z"/></g>"#,
+            r#"<g><polyline points="0,0 10,10"/><line x1="5" y1="5" x2="5" y2="5"/></g>"#,
+        ];
+        for svg in invalid.iter() {
+            assert!(!svg_is_valid_geom(svg), "{} should be invalid", svg);
+        }
+        assert!(!svg_path_string_is_valid_geom("M0 0L10 10L10 0L0 10Z"));
+    }
+
+    #[test]
+    fn checks_every_geometry_kind() {
+        let valid = [
+            "POINT(1 2)",
+            "MULTIPOINT((1 2),(3 4))",
+            "LINESTRING(0 0,1 1)",
+            "MULTILINESTRING((0 0,1 1),(2 2,3 3))",
+            "POLYGON((0 0,10 0,10 10,0 10,0 0))",
+            "MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((5 5,6 5,6 6,5 5)))",
+            "GEOMETRYCOLLECTION(POINT(1 2),POLYGON((0 0,10 0,10 10,0 10,0 0)))",
+        ];
+        for wkt in valid.iter() {
+            let geom = wkt_to_geometry(wkt).expect(wkt);
+            assert!(geometry_is_valid(&geom), "{} should be valid", wkt);
+        }
+
+        let invalid = [
+            "LINESTRING(1 1,1 1)",
+            "MULTILINESTRING((0 0,1 1),(2 2,2 2))",
+            "POLYGON((0 0,10 10,10 0,0 10,0 0))",
+            "GEOMETRYCOLLECTION(POINT(1 2),POLYGON((0 0,10 10,10 0,0 10,0 0)))",
+        ];
+        for wkt in invalid.iter() {
+            let geom = wkt_to_geometry(wkt).expect(wkt);
+            assert!(!geometry_is_valid(&geom), "{} should be invalid", wkt);
+        }
+        assert!(!geometry_is_valid(&Geometry::Point(geo_types::Point::new(
+            f64::NAN,
+            1.
+        ))));
+    }
+
+    #[test]
+    fn reports_the_same_verdict_as_the_validators() {
+        let cases = [
+            ("POLYGON((0 0,10 0,10 10,0 10,0 0),(3 3,3 6,6 6,6 3,3 3))", true),
+            ("POLYGON((0 0,0 10,10 10,10 0,0 0))", true),
+            ("LINESTRING(0 0,0 0,1 1)", true),
+            ("MULTILINESTRING(EMPTY,(0 0,1 1))", true),
+            (
+                "MULTIPOLYGON(((0 0,10 0,10 10,0 10,0 0),(2 2,8 2,8 8,2 8,2 2)),((4 4,6 4,6 6,4 6,4 4)))",
+                true,
+            ),
+            ("MULTIPOLYGON(((0 0,10 0,10 10,0 10,0 0)),((4 4,6 4,6 6,4 6,4 4)))", false),
+            ("POLYGON((0 0,4 0,4 4,0 4,0 0),(0 0,2 1,1 2,0 0))", false),
+            ("POLYGON((0 0,10 0,10 10,0 10,0 0),(1 1,1 8,8 8,8 1,1 1),(2 2,2 3,3 3,3 2,2 2))", false),
+            ("POLYGON((0 0,10 0,10 10,5 0,0 10,0 0))", false),
+            ("POLYGON((0 0,10 0,10 10,0 10,0 0),EMPTY)", false),
+            ("POLYGON((0 0,10 10,10 0,0 10,0 0))", false),
+            ("LINESTRING(1 1,1 1)", false),
+        ];
+        for (wkt, valid) in cases.iter() {
+            let report = wkt_validation_report(wkt).unwrap();
+            assert_eq!(*valid, wkt_is_valid_geom(wkt), "{}", wkt);
+            assert_eq!(*valid, report.valid, "{}", wkt);
+            // An invalid geometry always has a violation to show
+            assert!(*valid || !report.violations.is_empty(), "{}", wkt);
+        }
+    }
+
+    #[test]
+    fn validates_wkt_like_svg() {
+        assert!(wkt_is_valid_geom("POLYGON((0 0,10 0,10 10,0 10,0 0))"));
+        assert!(wkt_is_valid_geom("LINESTRING(0 0,10 10)"));
+        assert!(!wkt_is_valid_geom("LINESTRING(0 0,0 0)"));
+        assert!(!wkt_is_valid_geom("POLYGON((0 0,10 10,10 0,0 10,0 0))"));
+        assert!(!wkt_is_valid_geom("POLYGON((0 0,10 0"));
+
+        assert!(validate_wkt_polygon("POLYGON((0 0,10 0,10 10,0 10,0 0))"));
+        assert!(!validate_wkt_polygon("POLYGON((0 0,10 10,10 0,0 10,0 0))"));
+        assert!(!validate_wkt_polygon(
+            "MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((5 5,6 5,6 6,5 5)))"
+        ));
+
+        assert!(validate_wkt_multi_polygon(
+            "MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((5 5,6 5,6 6,5 5)))"
+        ));
+        assert!(!validate_wkt_multi_polygon(
+            "POLYGON((0 0,10 0,10 10,0 10,0 0))"
+        ));
+    }
+
+    #[test]
+    fn handles_empty_geometries() {
+        for wkt in [
+            "POLYGON EMPTY",
+            "MULTIPOLYGON EMPTY",
+            "GEOMETRYCOLLECTION EMPTY",
+            "POINT EMPTY",
+        ]
+        .iter()
+        {
+            assert!(wkt_is_valid_geom(wkt), "{} should be valid", wkt);
+            assert_eq!(
+                *wkt_validation_report(wkt).unwrap().to_json(),
+                *r#"{"valid":true,"violations":[]}"#
+            );
+        }
+        assert!(validate_wkt_polygon("POLYGON EMPTY"));
+        assert_eq!("POLYGON EMPTY", repair_wkt("POLYGON EMPTY").unwrap());
+        assert_eq!(
+            "GEOMETRYCOLLECTION EMPTY",
+            repair_wkt("GEOMETRYCOLLECTION EMPTY").unwrap()
+        );
+    }
+
+    #[test]
+    fn repairs_collections_member_by_member() {
+        assert_eq!(
+            "GEOMETRYCOLLECTION(POLYGON((0 0,10 0,10 10,0 10,0 0)),MULTIPOLYGON(((20 0,30 0,30 10,20 0))))",
+            repair_wkt("POLYGON((0 0,0 10,10 10,10 0,0 0)) MULTIPOLYGON(EMPTY,((20 0,30 0,30 10,20 0)))")
+                .unwrap()
+        );
+        assert!(repair_wkt("GEOMETRYCOLLECTION(POLYGON((0 0,1 0,1 1,0 0)),POINT(1 1))").is_err());
+    }
+
+    #[test]
+    fn rounds_repaired_geometries() {
+        assert_eq!(
+            "POLYGON((0 0,10 0,10 10,0 10,0 0))",
+            repair_wkt_with_precision(
+                "POLYGON((0 0,0 10,10 10,10 0.2,10.1 0,0 0))",
+                Precision::IntegerGrid
+            )
+            .unwrap()
+        );
+    }
+}
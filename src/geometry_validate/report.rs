@@ -0,0 +1,815 @@
+use crate::geometry_information::polygon_is_empty;
+use geo_types::{Coordinate, Geometry, LineString, Point, Polygon};
+use geo_validator::Validate;
+use serde_json::json;
+
+/// A kind of problem that makes a geometry invalid.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ViolationKind {
+    /// A coordinate is NaN or infinite
+    InvalidCoordinate,
+    /// A ring or line does not have enough distinct points
+    TooFewPoints,
+    /// The first and last points of a ring differ
+    UnclosedRing,
+    /// Two consecutive points are the same
+    DuplicateConsecutivePoints,
+    /// Two segments of the same ring cross, touch, or overlap
+    SelfIntersection,
+    /// A point of a ring lies on a segment of the same or of another ring
+    PointTouchingLine,
+    /// A point appears more than once in a ring
+    RepeatedPoint,
+    /// A hole crosses its shell or lies outside of it
+    HoleOutsideShell,
+    /// Two holes of the same polygon cross, or one lies inside the other
+    OverlappingHoles,
+    /// Two polygons of a multi polygon cross, or one lies inside the other
+    OverlappingPolygons,
+    /// A shell is wound clockwise or a hole counter clockwise
+    WrongRingOrientation,
+}
+
+impl ViolationKind {
+    /// The name of the violation used in the JSON report
+    pub fn name(self) -> &'static str {
+        match self {
+            ViolationKind::InvalidCoordinate => "invalidCoordinate",
+            ViolationKind::TooFewPoints => "tooFewPoints",
+            ViolationKind::UnclosedRing => "unclosedRing",
+            ViolationKind::DuplicateConsecutivePoints => "duplicateConsecutivePoints",
+            ViolationKind::SelfIntersection => "selfIntersection",
+            ViolationKind::PointTouchingLine => "pointTouchingLine",
+            ViolationKind::RepeatedPoint => "repeatedPoint",
+            ViolationKind::HoleOutsideShell => "holeOutsideShell",
+            ViolationKind::OverlappingHoles => "overlappingHoles",
+            ViolationKind::OverlappingPolygons => "overlappingPolygons",
+            ViolationKind::WrongRingOrientation => "wrongRingOrientation",
+        }
+    }
+}
+
+/// A single problem found in a geometry.
+///
+/// `geometry` is the path of indices to the member that the problem was found
+/// in, e.g., `[1, 0]` for the first polygon of a multi polygon that is the second
+/// member of a collection, or `[]` for the geometry itself. `other_geometry` is
+/// the path to the member it overlaps (for overlapping polygons). `rings` lists
+/// the rings involved (0 is the shell, holes follow), and `segments` lists the
+/// segments involved, where segment `i` runs from point `i` to point `i + 1` of
+/// the ring at the same position in `rings` (or of the only ring). Both are
+/// empty when only `geo_validator` found the problem. `coordinates` holds the
+/// spots to highlight.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    pub kind: ViolationKind,
+    pub geometry: Vec<usize>,
+    pub other_geometry: Option<Vec<usize>>,
+    pub rings: Vec<usize>,
+    pub segments: Vec<usize>,
+    pub coordinates: Vec<Coordinate<f64>>,
+}
+
+/// The result of validating a geometry.
+///
+/// `valid` is the verdict of `geometry_is_valid`. A wrong ring orientation, and
+/// duplicate consecutive points of a line string, are listed among the
+/// violations but do not make the geometry invalid, since every operation in
+/// this library accepts them.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationReport {
+    pub valid: bool,
+    pub violations: Vec<Violation>,
+}
+
+impl ValidationReport {
+    pub fn to_json(&self) -> String {
+        json!({
+            "valid": self.valid,
+            "violations": self.violations.iter().map(|v| json!({
+                "kind": v.kind.name(),
+                "geometry": v.geometry,
+                "otherGeometry": v.other_geometry,
+                "rings": v.rings,
+                "segments": v.segments,
+                "coordinates": v.coordinates.iter().map(|c| vec![c.x, c.y]).collect::<Vec<Vec<f64>>>(),
+            })).collect::<Vec<serde_json::Value>>(),
+        })
+        .to_string()
+    }
+}
+
+/// Validates a Geometry and lists every violation that was found.
+///
+/// Besides its own checks, every polygon is checked with `geo_validator`, and
+/// each rule that it finds broken is listed as a violation too.
+///
+pub fn validation_report(geom: &Geometry<f64>) -> ValidationReport {
+    let mut violations = vec![] as Vec<Violation>;
+    check_geometry(geom, &[], &mut violations);
+    ValidationReport {
+        valid: !violations.iter().any(makes_invalid),
+        violations,
+    }
+}
+
+/// Whether a violation makes the geometry invalid, or is only listed as a hint
+fn makes_invalid(violation: &Violation) -> bool {
+    match violation.kind {
+        ViolationKind::WrongRingOrientation => false,
+        // Only a line string has duplicate points outside of any ring
+        ViolationKind::DuplicateConsecutivePoints => !violation.rings.is_empty(),
+        _ => true,
+    }
+}
+
+fn check_geometry(geom: &Geometry<f64>, path: &[usize], found: &mut Vec<Violation>) {
+    match geom {
+        Geometry::Point(point) => check_coordinates(&[point.0], path, None, found),
+        Geometry::Line(line) => {
+            check_line_string(&LineString(vec![line.start, line.end]), path, found)
+        }
+        Geometry::LineString(line) => check_line_string(line, path, found),
+        Geometry::Polygon(poly) => check_polygon(poly, path, found),
+        Geometry::MultiPoint(points) => {
+            for (i, point) in points.0.iter().enumerate() {
+                check_coordinates(&[point.0], &member(path, i), None, found);
+            }
+        }
+        Geometry::MultiLineString(lines) => {
+            for (i, line) in lines.0.iter().enumerate() {
+                check_line_string(line, &member(path, i), found);
+            }
+        }
+        Geometry::MultiPolygon(polys) => {
+            for (i, poly) in polys.0.iter().enumerate() {
+                check_polygon(poly, &member(path, i), found);
+            }
+            for i in 0..polys.0.len() {
+                for j in i + 1..polys.0.len() {
+                    if let Some((rings, spot)) = polygons_overlap(&polys.0[i], &polys.0[j]) {
+                        found.push(Violation {
+                            kind: ViolationKind::OverlappingPolygons,
+                            geometry: member(path, i),
+                            other_geometry: Some(member(path, j)),
+                            rings,
+                            segments: vec![],
+                            coordinates: vec![spot],
+                        });
+                    }
+                }
+            }
+        }
+        Geometry::GeometryCollection(geoms) => {
+            for (i, geom) in geoms.0.iter().enumerate() {
+                check_geometry(geom, &member(path, i), found);
+            }
+        }
+    }
+}
+
+/// The path of a member of the multi geometry or collection at a path
+fn member(path: &[usize], index: usize) -> Vec<usize> {
+    let mut path = path.to_vec();
+    path.push(index);
+    path
+}
+
+fn check_coordinates(
+    coords: &[Coordinate<f64>],
+    path: &[usize],
+    ring: Option<usize>,
+    found: &mut Vec<Violation>,
+) {
+    for coord in coords {
+        if !coord.x.is_finite() || !coord.y.is_finite() {
+            found.push(Violation {
+                kind: ViolationKind::InvalidCoordinate,
+                geometry: path.to_vec(),
+                other_geometry: None,
+                rings: ring.into_iter().collect(),
+                segments: vec![],
+                coordinates: vec![*coord],
+            });
+        }
+    }
+}
+
+fn check_duplicates(
+    line: &LineString<f64>,
+    path: &[usize],
+    ring: Option<usize>,
+    found: &mut Vec<Violation>,
+) {
+    for (i, pair) in line.0.windows(2).enumerate() {
+        if pair[0] == pair[1] {
+            found.push(Violation {
+                kind: ViolationKind::DuplicateConsecutivePoints,
+                geometry: path.to_vec(),
+                other_geometry: None,
+                rings: ring.into_iter().collect(),
+                segments: vec![i],
+                coordinates: vec![pair[0]],
+            });
+        }
+    }
+}
+
+fn check_line_string(line: &LineString<f64>, path: &[usize], found: &mut Vec<Violation>) {
+    if line.0.is_empty() {
+        return;
+    }
+    check_coordinates(&line.0, path, None, found);
+    check_duplicates(line, path, None, found);
+    if distinct_points(line) < 2 {
+        found.push(Violation {
+            kind: ViolationKind::TooFewPoints,
+            geometry: path.to_vec(),
+            other_geometry: None,
+            rings: vec![],
+            segments: vec![],
+            coordinates: line.0.clone(),
+        });
+    }
+}
+
+fn check_polygon(poly: &Polygon<f64>, path: &[usize], found: &mut Vec<Violation>) {
+    if polygon_is_empty(poly) {
+        return;
+    }
+    let mut rings = vec![poly.exterior()];
+    rings.extend(poly.interiors());
+
+    // Problems within each ring
+    let mut usable = vec![] as Vec<bool>;
+    for (r, ring) in rings.iter().enumerate() {
+        check_coordinates(&ring.0, path, Some(r), found);
+        check_duplicates(ring, path, Some(r), found);
+
+        let (first, last) = (ring.0.first(), ring.0.last());
+        if first != last {
+            found.push(Violation {
+                kind: ViolationKind::UnclosedRing,
+                geometry: path.to_vec(),
+                other_geometry: None,
+                rings: vec![r],
+                segments: vec![],
+                coordinates: first.into_iter().chain(last).cloned().collect(),
+            });
+        }
+
+        if distinct_points(ring) < 3 {
+            found.push(Violation {
+                kind: ViolationKind::TooFewPoints,
+                geometry: path.to_vec(),
+                other_geometry: None,
+                rings: vec![r],
+                segments: vec![],
+                coordinates: ring.0.clone(),
+            });
+            usable.push(false);
+            continue;
+        }
+        usable.push(true);
+
+        check_self_intersections(ring, path, r, found);
+
+        let area = signed_area(ring);
+        if (r == 0 && area < 0.) || (r > 0 && area > 0.) {
+            found.push(Violation {
+                kind: ViolationKind::WrongRingOrientation,
+                geometry: path.to_vec(),
+                other_geometry: None,
+                rings: vec![r],
+                segments: vec![],
+                coordinates: vec![],
+            });
+        }
+    }
+
+    // Problems between the rings
+    for i in 0..rings.len() {
+        for j in i + 1..rings.len() {
+            if !usable[i] || !usable[j] {
+                continue;
+            }
+            let kind = if i == 0 {
+                ViolationKind::HoleOutsideShell
+            } else {
+                ViolationKind::OverlappingHoles
+            };
+            let crossings = ring_crossings(rings[i], rings[j]);
+            if !crossings.is_empty() {
+                for (si, sj, spot) in crossings {
+                    found.push(Violation {
+                        kind,
+                        geometry: path.to_vec(),
+                        other_geometry: None,
+                        rings: vec![i, j],
+                        segments: vec![si, sj],
+                        coordinates: vec![spot],
+                    });
+                }
+                continue;
+            }
+
+            let outside = match i {
+                // A hole must lie inside its shell
+                0 => rings[j]
+                    .0
+                    .iter()
+                    .find(|c| !on_ring(rings[0], **c))
+                    .filter(|c| !point_in_ring(rings[0], **c))
+                    .cloned(),
+                // Holes must not lie inside each other
+                _ => rings_overlap(rings[i], rings[j]),
+            };
+            if let Some(spot) = outside {
+                found.push(Violation {
+                    kind,
+                    geometry: path.to_vec(),
+                    other_geometry: None,
+                    rings: vec![i, j],
+                    segments: vec![],
+                    coordinates: vec![spot],
+                });
+            }
+        }
+    }
+
+    check_with_geo_validator(poly, path, found);
+}
+
+/// Lists the rules broken according to `geo_validator` that the checks above
+/// have not reported yet. Spots that are already reported for the same polygon
+/// are left out, and a broken rule without new spots is only listed when the
+/// polygon has not been found invalid otherwise.
+fn check_with_geo_validator(poly: &Polygon<f64>, path: &[usize], found: &mut Vec<Violation>) {
+    // `geo_validator` cannot check empty rings, which are too short anyway
+    if polygon_rings(poly).iter().any(|ring| ring.0.is_empty()) {
+        return;
+    }
+    let errors = poly.validate_detailed();
+    if errors.valid {
+        return;
+    }
+    let first_and_last = |ring: &LineString<f64>| {
+        ring.0
+            .first()
+            .into_iter()
+            .chain(ring.0.last())
+            .cloned()
+            .collect::<Vec<Coordinate<f64>>>()
+    };
+    let broken = vec![
+        (
+            ViolationKind::TooFewPoints,
+            errors.has_less_than_three_points,
+            vec![],
+        ),
+        (
+            ViolationKind::InvalidCoordinate,
+            !errors.unsupported_floating_point_values.is_empty(),
+            vec![],
+        ),
+        (
+            ViolationKind::UnclosedRing,
+            !errors.open_rings.is_empty(),
+            errors.open_rings.iter().flat_map(first_and_last).collect(),
+        ),
+        (
+            ViolationKind::HoleOutsideShell,
+            errors.is_multi_polygon || !errors.ring_intersects_other_ring.is_empty(),
+            errors.ring_intersects_other_ring,
+        ),
+        (
+            ViolationKind::SelfIntersection,
+            !errors.self_intersections.is_empty(),
+            errors.self_intersections,
+        ),
+        (
+            ViolationKind::PointTouchingLine,
+            !errors.point_touching_line.is_empty(),
+            errors.point_touching_line,
+        ),
+        (
+            ViolationKind::RepeatedPoint,
+            !errors.repeated_points.is_empty(),
+            errors.repeated_points,
+        ),
+    ];
+
+    for (kind, is_broken, spots) in broken {
+        if !is_broken {
+            continue;
+        }
+        let mut coordinates = vec![] as Vec<Coordinate<f64>>;
+        for spot in spots {
+            let spot = Coordinate {
+                x: spot.x + 0.,
+                y: spot.y + 0.,
+            };
+            // Parallel segments are reported as meeting at infinity
+            let reported = found
+                .iter()
+                .any(|v| v.geometry == path && v.coordinates.contains(&spot));
+            if spot.x.is_finite() && spot.y.is_finite() && !reported && !coordinates.contains(&spot)
+            {
+                coordinates.push(spot);
+            }
+        }
+        let already_invalid = found.iter().any(|v| v.geometry == path && makes_invalid(v));
+        if coordinates.is_empty() && already_invalid {
+            continue;
+        }
+        found.push(Violation {
+            kind,
+            geometry: path.to_vec(),
+            other_geometry: None,
+            rings: vec![],
+            segments: vec![],
+            coordinates,
+        });
+    }
+}
+
+fn check_self_intersections(
+    ring: &LineString<f64>,
+    path: &[usize],
+    r: usize,
+    found: &mut Vec<Violation>,
+) {
+    // Zero length segments are reported as duplicate points, so they are
+    // skipped here and the segments on either side count as neighbours
+    let segments = ring
+        .0
+        .windows(2)
+        .enumerate()
+        .filter(|(_, s)| s[0] != s[1])
+        .collect::<Vec<(usize, &[Coordinate<f64>])>>();
+    let count = segments.len();
+    for a in 0..count {
+        let (i, s1) = segments[a];
+        for (b, &(j, s2)) in segments.iter().enumerate().skip(a + 1) {
+            let adjacent = b == a + 1 || (a == 0 && b == count - 1);
+            let hit = match segment_intersection(s1[0], s1[1], s2[0], s2[1]) {
+                SegmentIntersection::None => None,
+                // Neighbouring segments always share a point
+                SegmentIntersection::Touch(_) if adjacent => None,
+                SegmentIntersection::Touch(spot)
+                | SegmentIntersection::Cross(spot)
+                | SegmentIntersection::Overlap(spot) => Some(spot),
+            };
+            if let Some(spot) = hit {
+                found.push(Violation {
+                    kind: ViolationKind::SelfIntersection,
+                    geometry: path.to_vec(),
+                    other_geometry: None,
+                    rings: vec![r],
+                    segments: vec![i, j],
+                    coordinates: vec![spot],
+                });
+            }
+        }
+    }
+}
+
+/// Lists the segments at which two rings cross or overlap (touching at a single
+/// point is allowed).
+fn ring_crossings(
+    ring1: &LineString<f64>,
+    ring2: &LineString<f64>,
+) -> Vec<(usize, usize, Coordinate<f64>)> {
+    let mut crossings = vec![];
+    for (i, s1) in ring1.0.windows(2).enumerate() {
+        for (j, s2) in ring2.0.windows(2).enumerate() {
+            match segment_intersection(s1[0], s1[1], s2[0], s2[1]) {
+                SegmentIntersection::Cross(spot) | SegmentIntersection::Overlap(spot) => {
+                    crossings.push((i, j, spot))
+                }
+                _ => {}
+            }
+        }
+    }
+    crossings
+}
+
+/// Returns a spot where the areas enclosed by two rings overlap, if they do.
+fn rings_overlap(ring1: &LineString<f64>, ring2: &LineString<f64>) -> Option<Coordinate<f64>> {
+    if let Some((_, _, spot)) = ring_crossings(ring1, ring2).into_iter().next() {
+        return Some(spot);
+    }
+    if let Some(c) = ring2.0.iter().find(|c| !on_ring(ring1, **c)) {
+        if point_in_ring(ring1, *c) {
+            return Some(*c);
+        }
+    }
+    if let Some(c) = ring1.0.iter().find(|c| !on_ring(ring2, **c)) {
+        if point_in_ring(ring2, *c) {
+            return Some(*c);
+        }
+    }
+    None
+}
+
+/// Returns the rings involved and a spot where the areas of two polygons
+/// overlap, if they do. A polygon may lie in a hole of the other.
+fn polygons_overlap(
+    poly1: &Polygon<f64>,
+    poly2: &Polygon<f64>,
+) -> Option<(Vec<usize>, Coordinate<f64>)> {
+    let rings1 = polygon_rings(poly1);
+    let rings2 = polygon_rings(poly2);
+    for (i, ring1) in rings1.iter().enumerate() {
+        for (j, ring2) in rings2.iter().enumerate() {
+            if let Some((_, _, spot)) = ring_crossings(ring1, ring2).into_iter().next() {
+                return Some((vec![i, j], spot));
+            }
+        }
+    }
+    // Without crossings, a shell lies wholly inside or outside the other polygon
+    let inside = |rings: &[&LineString<f64>], shell: &LineString<f64>| {
+        shell
+            .0
+            .iter()
+            .find(|c| !rings.iter().any(|ring| on_ring(ring, **c)))
+            .filter(|c| point_in_polygon(rings, **c))
+            .cloned()
+    };
+    inside(&rings1, poly2.exterior())
+        .or_else(|| inside(&rings2, poly1.exterior()))
+        .map(|spot| (vec![0, 0], spot))
+}
+
+fn polygon_rings(poly: &Polygon<f64>) -> Vec<&LineString<f64>> {
+    std::iter::once(poly.exterior())
+        .chain(poly.interiors())
+        .collect()
+}
+
+/* Geometric helpers */
+
+enum SegmentIntersection {
+    None,
+    /// The segments meet at a single point that is an end point of one of them
+    Touch(Coordinate<f64>),
+    /// The segments cross at a single point inside both of them
+    Cross(Coordinate<f64>),
+    /// The segments are collinear and share more than a single point
+    Overlap(Coordinate<f64>),
+}
+
+fn cross(a: Coordinate<f64>, b: Coordinate<f64>, c: Coordinate<f64>) -> f64 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+fn on_segment(a: Coordinate<f64>, b: Coordinate<f64>, c: Coordinate<f64>) -> bool {
+    cross(a, b, c) == 0.
+        && c.x >= a.x.min(b.x)
+        && c.x <= a.x.max(b.x)
+        && c.y >= a.y.min(b.y)
+        && c.y <= a.y.max(b.y)
+}
+
+fn segment_intersection(
+    p1: Coordinate<f64>,
+    p2: Coordinate<f64>,
+    q1: Coordinate<f64>,
+    q2: Coordinate<f64>,
+) -> SegmentIntersection {
+    let d1 = cross(q1, q2, p1);
+    let d2 = cross(q1, q2, p2);
+    let d3 = cross(p1, p2, q1);
+    let d4 = cross(p1, p2, q2);
+
+    if d1 * d2 < 0. && d3 * d4 < 0. {
+        let t = d1 / (d1 - d2);
+        return SegmentIntersection::Cross(Coordinate {
+            x: p1.x + t * (p2.x - p1.x),
+            y: p1.y + t * (p2.y - p1.y),
+        });
+    }
+
+    if d1 == 0. && d2 == 0. {
+        // Collinear, so find the shared stretch of the two segments
+        let shared = [p1, p2, q1, q2]
+            .iter()
+            .cloned()
+            .filter(|c| on_segment(p1, p2, *c) && on_segment(q1, q2, *c))
+            .collect::<Vec<Coordinate<f64>>>();
+        return match shared.first() {
+            None => SegmentIntersection::None,
+            Some(first) => match shared.iter().find(|c| *c != first) {
+                Some(_) => SegmentIntersection::Overlap(*first),
+                None => SegmentIntersection::Touch(*first),
+            },
+        };
+    }
+
+    for (c, a, b) in [(p1, q1, q2), (p2, q1, q2), (q1, p1, p2), (q2, p1, p2)].iter() {
+        if on_segment(*a, *b, *c) {
+            return SegmentIntersection::Touch(*c);
+        }
+    }
+    SegmentIntersection::None
+}
+
+fn on_ring(ring: &LineString<f64>, c: Coordinate<f64>) -> bool {
+    ring.0.windows(2).any(|s| on_segment(s[0], s[1], c))
+}
+
+fn point_in_ring(ring: &LineString<f64>, c: Coordinate<f64>) -> bool {
+    let mut inside = false;
+    for s in ring.0.windows(2) {
+        let (a, b) = (s[0], s[1]);
+        if (a.y > c.y) != (b.y > c.y) && c.x < (b.x - a.x) * (c.y - a.y) / (b.y - a.y) + a.x {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Tests whether a point lies inside the shell of a polygon and outside its holes
+fn point_in_polygon(rings: &[&LineString<f64>], c: Coordinate<f64>) -> bool {
+    point_in_ring(rings[0], c) && !rings[1..].iter().any(|hole| point_in_ring(hole, c))
+}
+
+fn signed_area(ring: &LineString<f64>) -> f64 {
+    ring.0
+        .windows(2)
+        .map(|s| s[0].x * s[1].y - s[1].x * s[0].y)
+        .sum::<f64>()
+        / 2.
+}
+
+fn distinct_points(line: &LineString<f64>) -> usize {
+    let mut points = vec![] as Vec<Point<f64>>;
+    for coord in &line.0 {
+        if !points.iter().any(|p| p.0 == *coord) {
+            points.push(Point(*coord));
+        }
+    }
+    points.len()
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry_convert::wkt_to_geometry;
+
+    fn kinds(wkt: &str) -> Vec<&'static str> {
+        validation_report(&wkt_to_geometry(wkt).unwrap())
+            .violations
+            .iter()
+            .map(|v| v.kind.name())
+            .collect()
+    }
+
+    #[test]
+    fn reports_nothing_for_valid_polygon() {
+        let report = validation_report(
+            &wkt_to_geometry("POLYGON((0 0,10 0,10 10,0 10,0 0),(3 3,3 6,6 6,6 3,3 3))").unwrap(),
+        );
+        assert!(report.valid);
+        assert!(report.violations.is_empty());
+    }
+
+    #[test]
+    fn reports_self_intersection_with_segments() {
+        let report =
+            validation_report(&wkt_to_geometry("POLYGON((0 0,10 10,10 0,0 10,0 0))").unwrap());
+        assert!(!report.valid);
+        let violation = &report.violations[0];
+        assert_eq!(ViolationKind::SelfIntersection, violation.kind);
+        assert_eq!(vec![0, 2], violation.segments);
+        assert_eq!(vec![Coordinate { x: 5., y: 5. }], violation.coordinates);
+    }
+
+    #[test]
+    fn reports_hole_problems() {
+        assert_eq!(
+            vec!["holeOutsideShell"],
+            kinds("POLYGON((0 0,10 0,10 10,0 10,0 0),(20 20,20 25,25 25,25 20,20 20))")
+        );
+        assert_eq!(
+            vec!["holeOutsideShell", "holeOutsideShell"],
+            kinds("POLYGON((0 0,10 0,10 10,0 10,0 0),(5 5,5 15,8 15,8 5,5 5))")
+        );
+        assert_eq!(
+            vec!["overlappingHoles"],
+            kinds("POLYGON((0 0,10 0,10 10,0 10,0 0),(1 1,1 8,8 8,8 1,1 1),(2 2,2 3,3 3,3 2,2 2))")
+        );
+    }
+
+    #[test]
+    fn reports_duplicate_points_and_orientation() {
+        assert_eq!(
+            vec!["duplicateConsecutivePoints", "wrongRingOrientation"],
+            kinds("POLYGON((0 0,0 10,0 10,10 10,10 0,0 0))")
+        );
+        let report =
+            validation_report(&wkt_to_geometry("POLYGON((0 0,0 10,10 10,10 0,0 0))").unwrap());
+        assert!(report.valid);
+    }
+
+    #[test]
+    fn reports_too_few_points() {
+        assert_eq!(vec!["tooFewPoints"], kinds("POLYGON((0 0,10 0,0 0))"));
+        assert_eq!(
+            vec!["duplicateConsecutivePoints", "tooFewPoints"],
+            kinds("LINESTRING(1 1,1 1)")
+        );
+    }
+
+    #[test]
+    fn reports_overlapping_polygons() {
+        // An island in the hole of another polygon does not overlap it
+        let report = validation_report(
+            &wkt_to_geometry(
+                "MULTIPOLYGON(((0 0,10 0,10 10,0 10,0 0),(2 2,8 2,8 8,2 8,2 2)),((4 4,6 4,6 6,4 6,4 4)))",
+            )
+            .unwrap(),
+        );
+        assert!(report.valid);
+        assert!(report
+            .violations
+            .iter()
+            .all(|v| v.kind == ViolationKind::WrongRingOrientation));
+        assert_eq!(
+            vec!["overlappingPolygons"],
+            kinds(
+                "MULTIPOLYGON(((0 0,10 0,10 10,0 10,0 0),(2 2,2 8,8 8,8 2,2 2)),((1 4,6 4,6 6,1 6,1 4)))"
+            )
+        );
+        assert_eq!(
+            vec!["overlappingPolygons"],
+            kinds("MULTIPOLYGON(((0 0,10 0,10 10,0 10,0 0)),((4 4,6 4,6 6,4 6,4 4)))")
+        );
+    }
+
+    #[test]
+    fn reports_every_rule_broken_according_to_geo_validator() {
+        // geo_validator does not allow a hole to touch its shell
+        for (wkt, spot) in [
+            ("POLYGON((0 0,4 0,4 4,0 4,0 0),(0 0,1 2,2 1,0 0))", (0., 0.)),
+            ("POLYGON((0 0,4 0,4 4,0 4,0 0),(2 0,1 1,3 1,2 0))", (2., 0.)),
+        ]
+        .iter()
+        {
+            let report = validation_report(&wkt_to_geometry(wkt).unwrap());
+            assert!(!report.valid);
+            assert_eq!(1, report.violations.len());
+            let violation = &report.violations[0];
+            assert_eq!(ViolationKind::HoleOutsideShell, violation.kind);
+            assert_eq!(vec![Coordinate::from(*spot)], violation.coordinates);
+        }
+
+        // A spot that is already reported is not listed again
+        assert_eq!(
+            vec!["selfIntersection", "selfIntersection"],
+            kinds("POLYGON((0 0,10 0,10 10,5 0,0 10,0 0))")
+        );
+    }
+
+    #[test]
+    fn reports_paths_to_members_of_collections() {
+        let report = validation_report(
+            &wkt_to_geometry(
+                "GEOMETRYCOLLECTION(POINT(1 1),MULTIPOLYGON(((0 0,10 0,10 10,0 10,0 0)),\
+                 ((20 0,30 0,30 10,20 10,20 0)),((5 5,25 5,25 8,5 8,5 5))))",
+            )
+            .unwrap(),
+        );
+        let paths = report
+            .violations
+            .iter()
+            .map(|v| (v.kind.name(), v.geometry.clone(), v.other_geometry.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("overlappingPolygons", vec![1, 0], Some(vec![1, 2])),
+                ("overlappingPolygons", vec![1, 1], Some(vec![1, 2])),
+            ],
+            paths
+        );
+        assert_eq!(
+            r#"{"valid":false,"violations":[{"coordinates":[[10.0,5.0]],"geometry":[1,0],"kind":"overlappingPolygons","otherGeometry":[1,2],"rings":[0,0],"segments":[]},{"coordinates":[[20.0,5.0]],"geometry":[1,1],"kind":"overlappingPolygons","otherGeometry":[1,2],"rings":[0,0],"segments":[]}]}"#,
+            report.to_json()
+        );
+    }
+
+    #[test]
+    fn can_serialize_report() {
+        let report =
+            validation_report(&wkt_to_geometry("POLYGON((0 0,10 10,10 0,0 10,0 0))").unwrap());
+        assert_eq!(
+            r#"{"valid":false,"violations":[{"coordinates":[[5.0,5.0]],"geometry":[],"kind":"selfIntersection","otherGeometry":null,"rings":[0],"segments":[0,2]}]}"#,
+            report.to_json()
+        );
+    }
+}
@@ -0,0 +1,576 @@
+use crate::error::GeometryToolsError;
+use crate::geometry_convert::{
+    geometry_to_svg, geometry_to_wkt, svg_to_repaired_geometry, wkt_to_geometry,
+};
+use crate::json_errors;
+use byteorder::{BigEndian, ByteOrder, LittleEndian, ReadBytesExt, WriteBytesExt};
+use geo_types::{
+    Coordinate, Geometry, GeometryCollection, LineString, MultiLineString, MultiPoint,
+    MultiPolygon, Point, Polygon,
+};
+use std::io::Cursor;
+
+const WKB_POINT: u32 = 1;
+const WKB_LINE_STRING: u32 = 2;
+const WKB_POLYGON: u32 = 3;
+const WKB_MULTI_POINT: u32 = 4;
+const WKB_MULTI_LINE_STRING: u32 = 5;
+const WKB_MULTI_POLYGON: u32 = 6;
+const WKB_GEOMETRY_COLLECTION: u32 = 7;
+
+const EWKB_Z_FLAG: u32 = 0x8000_0000;
+const EWKB_M_FLAG: u32 = 0x4000_0000;
+const EWKB_SRID_FLAG: u32 = 0x2000_0000;
+
+/// How deeply multi geometries and collections may be nested inside each other
+const MAX_NESTING_DEPTH: usize = 64;
+/// The fewest bytes any geometry can take (byte order, type and an empty count)
+const MIN_GEOMETRY_SIZE: usize = 9;
+/// The size of an element count
+const COUNT_SIZE: usize = 4;
+
+/// Parses WKB or EWKB into a Geometry together with its SRID (if one is embedded).
+///
+/// Both byte orders are supported, as are the ISO (1000/2000/3000) and EWKB
+/// flag styles for Z and M coordinates. Z and M values are dropped, since all
+/// geometries are two dimensional.
+///
+/// Non-finite coordinates (other than the NaN coordinates of an empty point),
+/// nesting deeper than 64 levels and element counts that the remaining bytes
+/// cannot hold are rejected as parse errors.
+///
+pub fn wkb_to_geometry_with_srid(
+    wkb: &[u8],
+) -> Result<(Geometry<f64>, Option<u32>), GeometryToolsError> {
+    let mut reader = WkbReader {
+        cursor: Cursor::new(wkb),
+        srid: None,
+    };
+    let geom = match reader.read_geometry(0) {
+        Ok(geom) => geom,
+        Err(reason) => {
+            return Err(json_errors::wkb_error::could_not_parse(
+                &bytes_to_hex(wkb),
+                &reason,
+            ))
+        }
+    };
+    if (reader.cursor.position() as usize) < wkb.len() {
+        return Err(json_errors::wkb_error::could_not_parse(
+            &bytes_to_hex(wkb),
+            "The WKB has trailing bytes",
+        ));
+    }
+    Ok((geom, reader.srid))
+}
+
+/// Parses WKB or EWKB into a Geometry, ignoring any embedded SRID.
+///
+pub fn wkb_to_geometry(wkb: &[u8]) -> Result<Geometry<f64>, GeometryToolsError> {
+    Ok(wkb_to_geometry_with_srid(wkb)?.0)
+}
+
+/// Writes a Geometry as little endian WKB.
+///
+/// When an `srid` is given, the output is EWKB with the SRID embedded.
+///
+pub fn geometry_to_wkb(geom: &Geometry<f64>, srid: Option<u32>) -> Vec<u8> {
+    let mut wkb = vec![] as Vec<u8>;
+    write_geometry(&mut wkb, geom, srid);
+    wkb
+}
+
+/// Decodes a hex string (as returned by most spatial databases) into bytes.
+///
+pub fn hex_to_bytes(hex: &str) -> Result<Vec<u8>, GeometryToolsError> {
+    let hex = hex.trim();
+    let hex = hex
+        .strip_prefix("\\x")
+        .or_else(|| hex.strip_prefix("0x"))
+        .unwrap_or(hex);
+    if !hex.len().is_multiple_of(2) {
+        return Err(json_errors::wkb_error::could_not_parse(
+            hex,
+            "The hex string has an odd number of digits",
+        ));
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| match hex.get(i..i + 2) {
+            Some(byte) => u8::from_str_radix(byte, 16).ok(),
+            None => None,
+        })
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(|| {
+            json_errors::wkb_error::could_not_parse(hex, "The hex string has invalid digits")
+        })
+}
+
+/// Encodes bytes as an upper case hex string.
+///
+pub fn bytes_to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02X}", byte)).collect()
+}
+
+/// Converts hex encoded WKB or EWKB into WKT.
+///
+pub fn wkb_to_wkt(wkb: &str) -> Result<String, GeometryToolsError> {
+    wkb_bytes_to_wkt(&hex_to_bytes(wkb)?)
+}
+
+/// Converts WKB or EWKB bytes into WKT.
+///
+pub fn wkb_bytes_to_wkt(wkb: &[u8]) -> Result<String, GeometryToolsError> {
+    Ok(geometry_to_wkt(&wkb_to_geometry(wkb)?))
+}
+
+/// Converts WKT into hex encoded WKB, or EWKB when an `srid` is given.
+///
+pub fn wkt_to_wkb(wkt: &str, srid: Option<u32>) -> Result<String, GeometryToolsError> {
+    Ok(bytes_to_hex(&wkt_to_wkb_bytes(wkt, srid)?))
+}
+
+/// Converts WKT into WKB bytes, or EWKB when an `srid` is given.
+///
+pub fn wkt_to_wkb_bytes(wkt: &str, srid: Option<u32>) -> Result<Vec<u8>, GeometryToolsError> {
+    Ok(geometry_to_wkb(&wkt_to_geometry(wkt)?, srid))
+}
+
+/// Converts hex encoded WKB or EWKB into an SVG element.
+///
+pub fn wkb_to_svg(wkb: &str) -> Result<String, GeometryToolsError> {
+    wkb_bytes_to_svg(&hex_to_bytes(wkb)?)
+}
+
+/// Converts WKB or EWKB bytes into an SVG element.
+///
+pub fn wkb_bytes_to_svg(wkb: &[u8]) -> Result<String, GeometryToolsError> {
+    Ok(geometry_to_svg(&wkb_to_geometry(wkb)?))
+}
+
+/// Converts an SVG element into hex encoded WKB, or EWKB when an `srid` is given.
+/// Polygons and multi polygons are repaired on the way.
+///
+pub fn svg_to_wkb(svg: &str, srid: Option<u32>) -> Result<String, GeometryToolsError> {
+    Ok(bytes_to_hex(&svg_to_wkb_bytes(svg, srid)?))
+}
+
+/// Converts an SVG element into WKB bytes, or EWKB when an `srid` is given.
+/// Polygons and multi polygons are repaired on the way.
+///
+pub fn svg_to_wkb_bytes(svg: &str, srid: Option<u32>) -> Result<Vec<u8>, GeometryToolsError> {
+    Ok(geometry_to_wkb(&svg_to_repaired_geometry(svg)?, srid))
+}
+
+/// Reads the SRID embedded in hex encoded EWKB, if there is one.
+///
+pub fn wkb_srid(wkb: &str) -> Result<Option<u32>, GeometryToolsError> {
+    wkb_bytes_srid(&hex_to_bytes(wkb)?)
+}
+
+/// Reads the SRID embedded in EWKB bytes, if there is one.
+///
+pub fn wkb_bytes_srid(wkb: &[u8]) -> Result<Option<u32>, GeometryToolsError> {
+    Ok(wkb_to_geometry_with_srid(wkb)?.1)
+}
+
+/* WKB Reader */
+
+struct WkbReader<'a> {
+    cursor: Cursor<&'a [u8]>,
+    srid: Option<u32>,
+}
+
+struct WkbHeader {
+    little_endian: bool,
+    geom_type: u32,
+    dimensions: usize,
+}
+
+impl<'a> WkbReader<'a> {
+    fn read_header(&mut self) -> Result<WkbHeader, String> {
+        let little_endian = match self.cursor.read_u8() {
+            Ok(0) => false,
+            Ok(1) => true,
+            Ok(order) => return Err(format!("Unknown byte order {}", order)),
+            Err(_) => return Err("The WKB ended unexpectedly".into()),
+        };
+        let raw_type = self.read_u32(little_endian)?;
+
+        let mut has_z = raw_type & EWKB_Z_FLAG != 0;
+        let mut has_m = raw_type & EWKB_M_FLAG != 0;
+        if raw_type & EWKB_SRID_FLAG != 0 {
+            self.srid = Some(self.read_u32(little_endian)?);
+        }
+
+        let iso_type = raw_type & 0x0fff_ffff;
+        match iso_type / 1000 {
+            0 => {}
+            1 => has_z = true,
+            2 => has_m = true,
+            3 => {
+                has_z = true;
+                has_m = true;
+            }
+            _ => return Err(format!("Unknown geometry type {}", iso_type)),
+        }
+
+        Ok(WkbHeader {
+            little_endian,
+            geom_type: iso_type % 1000,
+            dimensions: 2 + has_z as usize + has_m as usize,
+        })
+    }
+
+    fn read_geometry(&mut self, depth: usize) -> Result<Geometry<f64>, String> {
+        if depth > MAX_NESTING_DEPTH {
+            return Err(format!(
+                "The geometries are nested more than {} levels deep",
+                MAX_NESTING_DEPTH
+            ));
+        }
+        let header = self.read_header()?;
+        let le = header.little_endian;
+        let dims = header.dimensions;
+        Ok(match header.geom_type {
+            WKB_POINT => {
+                let coord = self.read_raw_coordinate(le, dims)?;
+                // An empty point is written with NaN coordinates
+                if coord.x.is_nan() && coord.y.is_nan() {
+                    Geometry::MultiPoint(MultiPoint(vec![]))
+                } else {
+                    Geometry::Point(Point(check_finite(coord)?))
+                }
+            }
+            WKB_LINE_STRING => Geometry::LineString(self.read_line_string(le, dims)?),
+            WKB_POLYGON => Geometry::Polygon(self.read_polygon(le, dims)?),
+            WKB_MULTI_POINT => {
+                let mut points = vec![] as Vec<Point<f64>>;
+                for _ in 0..self.read_count(le, MIN_GEOMETRY_SIZE)? {
+                    match self.read_geometry(depth + 1)? {
+                        Geometry::Point(point) => points.push(point),
+                        Geometry::MultiPoint(ref empty) if empty.0.is_empty() => {}
+                        _ => return Err("A MULTIPOINT may only contain points".into()),
+                    }
+                }
+                Geometry::MultiPoint(MultiPoint(points))
+            }
+            WKB_MULTI_LINE_STRING => {
+                let mut lines = vec![] as Vec<LineString<f64>>;
+                for _ in 0..self.read_count(le, MIN_GEOMETRY_SIZE)? {
+                    match self.read_geometry(depth + 1)? {
+                        Geometry::LineString(line) => lines.push(line),
+                        _ => return Err("A MULTILINESTRING may only contain linestrings".into()),
+                    }
+                }
+                Geometry::MultiLineString(MultiLineString(lines))
+            }
+            WKB_MULTI_POLYGON => {
+                let mut polygons = vec![] as Vec<Polygon<f64>>;
+                for _ in 0..self.read_count(le, MIN_GEOMETRY_SIZE)? {
+                    match self.read_geometry(depth + 1)? {
+                        Geometry::Polygon(poly) => polygons.push(poly),
+                        _ => return Err("A MULTIPOLYGON may only contain polygons".into()),
+                    }
+                }
+                Geometry::MultiPolygon(MultiPolygon(polygons))
+            }
+            WKB_GEOMETRY_COLLECTION => {
+                let mut geoms = vec![] as Vec<Geometry<f64>>;
+                for _ in 0..self.read_count(le, MIN_GEOMETRY_SIZE)? {
+                    geoms.push(self.read_geometry(depth + 1)?);
+                }
+                Geometry::GeometryCollection(GeometryCollection(geoms))
+            }
+            geom_type => return Err(format!("Unsupported geometry type {}", geom_type)),
+        })
+    }
+
+    fn read_polygon(&mut self, le: bool, dims: usize) -> Result<Polygon<f64>, String> {
+        let ring_count = self.read_count(le, COUNT_SIZE)?;
+        if ring_count == 0 {
+            return Ok(Polygon::new(LineString(vec![]), vec![]));
+        }
+        let exterior = self.read_line_string(le, dims)?;
+        let mut interiors = vec![] as Vec<LineString<f64>>;
+        for _ in 1..ring_count {
+            interiors.push(self.read_line_string(le, dims)?);
+        }
+        Ok(Polygon::new(exterior, interiors))
+    }
+
+    fn read_line_string(&mut self, le: bool, dims: usize) -> Result<LineString<f64>, String> {
+        let mut coords = vec![] as Vec<Coordinate<f64>>;
+        for _ in 0..self.read_count(le, dims * 8)? {
+            coords.push(check_finite(self.read_raw_coordinate(le, dims)?)?);
+        }
+        Ok(LineString(coords))
+    }
+
+    fn read_raw_coordinate(&mut self, le: bool, dims: usize) -> Result<Coordinate<f64>, String> {
+        let x = self.read_f64(le)?;
+        let y = self.read_f64(le)?;
+        // Skip the Z and M values
+        for _ in 2..dims {
+            self.read_f64(le)?;
+        }
+        Ok(Coordinate { x, y })
+    }
+
+    /// Reads an element count, rejecting counts of elements of at least
+    /// `element_size` bytes that could not fit in the remaining bytes.
+    fn read_count(&mut self, le: bool, element_size: usize) -> Result<u32, String> {
+        let count = self.read_u32(le)?;
+        let remaining = self.cursor.get_ref().len() - self.cursor.position() as usize;
+        if (count as usize).saturating_mul(element_size) > remaining {
+            return Err(format!("The WKB is too short for its {} elements", count));
+        }
+        Ok(count)
+    }
+
+    fn read_u32(&mut self, le: bool) -> Result<u32, String> {
+        let value = if le {
+            self.cursor.read_u32::<LittleEndian>()
+        } else {
+            self.cursor.read_u32::<BigEndian>()
+        };
+        value.map_err(|_| "The WKB ended unexpectedly".into())
+    }
+
+    fn read_f64(&mut self, le: bool) -> Result<f64, String> {
+        let value = if le {
+            self.cursor.read_f64::<LittleEndian>()
+        } else {
+            self.cursor.read_f64::<BigEndian>()
+        };
+        value.map_err(|_| "The WKB ended unexpectedly".into())
+    }
+}
+
+fn check_finite(coord: Coordinate<f64>) -> Result<Coordinate<f64>, String> {
+    if coord.x.is_finite() && coord.y.is_finite() {
+        Ok(coord)
+    } else {
+        Err("The WKB has a non-finite coordinate".into())
+    }
+}
+
+/* WKB Writer */
+
+fn write_geometry(wkb: &mut Vec<u8>, geom: &Geometry<f64>, srid: Option<u32>) {
+    let geom_type = match geom {
+        Geometry::Point(_) => WKB_POINT,
+        Geometry::Line(_) | Geometry::LineString(_) => WKB_LINE_STRING,
+        Geometry::Polygon(_) => WKB_POLYGON,
+        Geometry::MultiPoint(_) => WKB_MULTI_POINT,
+        Geometry::MultiLineString(_) => WKB_MULTI_LINE_STRING,
+        Geometry::MultiPolygon(_) => WKB_MULTI_POLYGON,
+        Geometry::GeometryCollection(_) => WKB_GEOMETRY_COLLECTION,
+    };
+    wkb.push(1);
+    match srid {
+        Some(srid) => {
+            write_u32(wkb, geom_type | EWKB_SRID_FLAG);
+            write_u32(wkb, srid);
+        }
+        None => write_u32(wkb, geom_type),
+    }
+
+    match geom {
+        Geometry::Point(point) => write_coordinate(wkb, &point.0),
+        Geometry::Line(line) => write_line_string(wkb, &LineString(vec![line.start, line.end])),
+        Geometry::LineString(line) => write_line_string(wkb, line),
+        Geometry::Polygon(poly) => write_polygon(wkb, poly),
+        Geometry::MultiPoint(points) => {
+            write_u32(wkb, points.0.len() as u32);
+            for point in &points.0 {
+                write_geometry(wkb, &Geometry::Point(*point), None);
+            }
+        }
+        Geometry::MultiLineString(lines) => {
+            write_u32(wkb, lines.0.len() as u32);
+            for line in &lines.0 {
+                write_geometry(wkb, &Geometry::LineString(line.clone()), None);
+            }
+        }
+        Geometry::MultiPolygon(polys) => {
+            write_u32(wkb, polys.0.len() as u32);
+            for poly in &polys.0 {
+                write_geometry(wkb, &Geometry::Polygon(poly.clone()), None);
+            }
+        }
+        Geometry::GeometryCollection(geoms) => {
+            write_u32(wkb, geoms.0.len() as u32);
+            for geom in &geoms.0 {
+                write_geometry(wkb, geom, None);
+            }
+        }
+    }
+}
+
+fn write_polygon(wkb: &mut Vec<u8>, poly: &Polygon<f64>) {
+    if poly.exterior().0.is_empty() {
+        write_u32(wkb, 0);
+        return;
+    }
+    write_u32(wkb, 1 + poly.interiors().len() as u32);
+    write_line_string(wkb, poly.exterior());
+    for interior in poly.interiors() {
+        write_line_string(wkb, interior);
+    }
+}
+
+fn write_line_string(wkb: &mut Vec<u8>, line: &LineString<f64>) {
+    write_u32(wkb, line.0.len() as u32);
+    for coord in &line.0 {
+        write_coordinate(wkb, coord);
+    }
+}
+
+fn write_coordinate(wkb: &mut Vec<u8>, coord: &Coordinate<f64>) {
+    let mut buf = [0; 8];
+    LittleEndian::write_f64(&mut buf, coord.x);
+    wkb.extend_from_slice(&buf);
+    LittleEndian::write_f64(&mut buf, coord.y);
+    wkb.extend_from_slice(&buf);
+}
+
+fn write_u32(wkb: &mut Vec<u8>, value: u32) {
+    // Writing into a Vec cannot fail
+    wkb.write_u32::<LittleEndian>(value).unwrap();
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_read_little_endian_wkb() {
+        let wkt = wkb_to_wkt("0101000000000000000000F03F0000000000000040");
+        assert_eq!("POINT(1 2)", wkt.unwrap());
+    }
+
+    #[test]
+    fn can_read_big_endian_wkb() {
+        let wkt = wkb_to_wkt("00000000013FF00000000000004000000000000000");
+        assert_eq!("POINT(1 2)", wkt.unwrap());
+    }
+
+    #[test]
+    fn can_read_ewkb_with_srid_and_z() {
+        // SRID=4326;POINT Z(1 2 3)
+        let wkb = "01010000A0E6100000000000000000F03F00000000000000400000000000000840";
+        assert_eq!("POINT(1 2)", wkb_to_wkt(wkb).unwrap());
+        assert_eq!(Some(4326), wkb_srid(wkb).unwrap());
+    }
+
+    #[test]
+    fn can_read_iso_wkb_with_zm() {
+        // POINT ZM(1 2 3 4)
+        let wkt = wkb_to_wkt(
+            "01B90B0000000000000000F03F000000000000004000000000000008400000000000001040",
+        );
+        assert_eq!("POINT(1 2)", wkt.unwrap());
+    }
+
+    #[test]
+    fn round_trips_polygon_with_srid() {
+        let wkt = "POLYGON((0 0,10 0,10 10,0 10,0 0),(3 3,3 6,6 6,6 3,3 3))";
+        let wkb = wkt_to_wkb(wkt, Some(4326)).unwrap();
+        assert_eq!(Some(4326), wkb_srid(&wkb).unwrap());
+        assert_eq!(wkt, wkb_to_wkt(&wkb).unwrap());
+        let wkb = wkt_to_wkb(wkt, None).unwrap();
+        assert!(wkb.starts_with("0103000000"));
+        assert_eq!(None, wkb_srid(&wkb).unwrap());
+    }
+
+    #[test]
+    fn converts_wkb_bytes() {
+        let wkt = "POLYGON((0 0,10 0,10 10,0 10,0 0))";
+        let wkb = wkt_to_wkb_bytes(wkt, Some(4326)).unwrap();
+        assert_eq!(bytes_to_hex(&wkb), wkt_to_wkb(wkt, Some(4326)).unwrap());
+        assert_eq!(wkt, wkb_bytes_to_wkt(&wkb).unwrap());
+        assert_eq!(Some(4326), wkb_bytes_srid(&wkb).unwrap());
+        assert_eq!(
+            "<path d=\"M0 0L10 0L10 10L0 10L0 0\"/>",
+            wkb_bytes_to_svg(&wkb).unwrap()
+        );
+
+        let svg = "<path d=\"M0 0L10 0L10 10L0 10L0 0\"/>";
+        let wkb = svg_to_wkb_bytes(svg, None).unwrap();
+        assert_eq!(bytes_to_hex(&wkb), svg_to_wkb(svg, None).unwrap());
+        assert_eq!(None, wkb_bytes_srid(&wkb).unwrap());
+        assert_eq!(
+            "WKB_PARSE_ERROR",
+            wkb_bytes_to_wkt(&wkb[..9]).unwrap_err().code()
+        );
+    }
+
+    #[test]
+    fn errors_on_non_finite_coordinates() {
+        let wkb = wkt_to_wkb_bytes("POLYGON((0 0,10 0,10 10,0 10,0 0))", None).unwrap();
+        let mut nan = wkb.clone();
+        for offset in &[13, 29, 45] {
+            LittleEndian::write_f64(&mut nan[*offset..*offset + 8], f64::NAN);
+        }
+        let err = wkb_bytes_to_wkt(&nan).unwrap_err();
+        assert_eq!("WKB_PARSE_ERROR", err.code());
+        assert!(err.to_string().contains("non-finite"));
+
+        let point = wkt_to_wkb_bytes("POINT(1 2)", None).unwrap();
+        let mut inf = point.clone();
+        LittleEndian::write_f64(&mut inf[5..13], f64::INFINITY);
+        assert_eq!(
+            "WKB_PARSE_ERROR",
+            wkb_bytes_to_wkt(&inf).unwrap_err().code()
+        );
+
+        // Only an all NaN point is read as an empty point
+        let mut empty = point.clone();
+        LittleEndian::write_f64(&mut empty[5..13], f64::NAN);
+        LittleEndian::write_f64(&mut empty[13..21], f64::NAN);
+        assert_eq!("MULTIPOINT EMPTY", wkb_bytes_to_wkt(&empty).unwrap());
+    }
+
+    #[test]
+    fn errors_on_deeply_nested_collections() {
+        let mut wkb = vec![] as Vec<u8>;
+        for _ in 0..200_000 {
+            wkb.extend_from_slice(&[1, 7, 0, 0, 0, 1, 0, 0, 0]);
+        }
+        wkb.extend_from_slice(&[1, 7, 0, 0, 0, 0, 0, 0, 0]);
+        let err = wkb_bytes_to_wkt(&wkb).unwrap_err();
+        assert_eq!("WKB_PARSE_ERROR", err.code());
+        assert!(err.to_string().contains("nested"));
+
+        let mut wkb = vec![] as Vec<u8>;
+        for _ in 0..MAX_NESTING_DEPTH {
+            wkb.extend_from_slice(&[1, 7, 0, 0, 0, 1, 0, 0, 0]);
+        }
+        wkb.extend_from_slice(&[1, 7, 0, 0, 0, 0, 0, 0, 0]);
+        assert!(wkb_bytes_to_wkt(&wkb).is_ok());
+    }
+
+    #[test]
+    fn errors_on_counts_larger_than_the_wkb() {
+        // A GEOMETRYCOLLECTION claiming 4 billion members
+        let wkb = [1, 7, 0, 0, 0, 0xff, 0xff, 0xff, 0xff];
+        let err = wkb_bytes_to_wkt(&wkb).unwrap_err();
+        assert!(err.to_string().contains("too short"));
+        // A LINESTRING claiming 2 points but holding 1
+        let mut wkb = wkt_to_wkb_bytes("LINESTRING(0 0,1 1)", None).unwrap();
+        wkb.truncate(wkb.len() - 16);
+        let err = wkb_bytes_to_wkt(&wkb).unwrap_err();
+        assert!(err.to_string().contains("too short"));
+    }
+
+    #[test]
+    fn errors_on_truncated_wkb() {
+        let wkt = wkb_to_wkt("0101000000000000000000F03F");
+        assert_eq!("WKB_PARSE_ERROR", wkt.unwrap_err().code());
+        let wkt = wkb_to_wkt("01010000ZZ");
+        assert_eq!("WKB_PARSE_ERROR", wkt.unwrap_err().code());
+    }
+}
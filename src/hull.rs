@@ -0,0 +1,58 @@
+use crate::geometry_hull::{self, ConcaveHullOptions};
+use wasm_bindgen::prelude::*;
+
+/* Convex Hulls */
+
+/// Returns the smallest convex POLYGON that contains every coordinate of a WKT
+/// geometry, which can be of any type, including GEOMETRYCOLLECTION and MULTIPOINT.
+/// The result is POLYGON EMPTY when the coordinates do not enclose any area.
+///
+#[wasm_bindgen(js_name = wktConvexHull)]
+pub fn wkt_convex_hull(wkt: String) -> Result<String, JsValue> {
+    Ok(geometry_hull::wkt_convex_hull(&wkt)?)
+}
+
+/// Returns the convex hull of an SVG element as an SVG path (see `wktConvexHull`).
+///
+#[wasm_bindgen(js_name = svgConvexHull)]
+pub fn svg_convex_hull(svg: String) -> Result<String, JsValue> {
+    Ok(geometry_hull::svg_convex_hull(&svg)?)
+}
+
+/* Concave Hulls */
+
+/// Returns a POLYGON that wraps every coordinate of a WKT geometry more tightly
+/// than its convex hull.
+///
+/// `concavity` (2 by default) sets how far the outline may cut in between the
+/// coordinates: 1 gives a detailed outline, larger values a smoother one, and
+/// Infinity gives the convex hull. Edges shorter than `lengthThreshold`
+/// (0 by default) are not cut into any further.
+///
+/// It throws an error if the options are invalid.
+///
+#[wasm_bindgen(js_name = wktConcaveHull)]
+pub fn wkt_concave_hull(
+    wkt: String,
+    concavity: Option<f64>,
+    length_threshold: Option<f64>,
+) -> Result<String, JsValue> {
+    Ok(geometry_hull::wkt_concave_hull(
+        &wkt,
+        &ConcaveHullOptions::parse(concavity, length_threshold)?,
+    )?)
+}
+
+/// Returns the concave hull of an SVG element as an SVG path (see `wktConcaveHull`).
+///
+#[wasm_bindgen(js_name = svgConcaveHull)]
+pub fn svg_concave_hull(
+    svg: String,
+    concavity: Option<f64>,
+    length_threshold: Option<f64>,
+) -> Result<String, JsValue> {
+    Ok(geometry_hull::svg_concave_hull(
+        &svg,
+        &ConcaveHullOptions::parse(concavity, length_threshold)?,
+    )?)
+}
@@ -1,6 +1,5 @@
-use crate::json_errors;
-use geo_svg_io::geo_svg_reader::svg_to_geometry_collection;
-use geo_types::Geometry;
+use crate::geometry_information;
+use geo_types::Rect;
 use wasm_bindgen::prelude::*;
 
 /// Returns the Geometry type recognized for the submitted SVG element.
@@ -9,68 +8,150 @@ use wasm_bindgen::prelude::*;
 ///
 #[wasm_bindgen(js_name = svgGeomType)]
 pub fn svg_geom_type(svg: String) -> String {
-    let geom = match svg_to_geometry_collection(&svg) {
-        Ok(geom) => geom,
-        Err(_) => return "None".into(),
-    };
-
-    if geom.0.len() > 1 {
-        return "GEOMETRYCOLLECTION".into();
-    }
-
-    match geom.0[0] {
-        Geometry::MultiPolygon { .. } => "MULTIPOLYGON".into(),
-        Geometry::Polygon { .. } => "POLYGON".into(),
-        Geometry::MultiLineString { .. } => "MULTILINESTRING".into(),
-        Geometry::LineString { .. } => "LINESTRING".into(),
-        Geometry::Line { .. } => "LINESTRING".into(),
-        Geometry::MultiPoint { .. } => "MULTIPOINT".into(),
-        Geometry::Point { .. } => "POINT".into(),
-        _ => "INVALIDGEOMETRY".into(),
-    }
+    geometry_information::svg_geom_type(&svg)
 }
 
 /// Returns the Geometry type recognized for the submitted SVG path d-string.
 ///
 #[wasm_bindgen(js_name = svgPathGeomType)]
 pub fn svg_path_geom_type(d_string: String) -> String {
-    svg_geom_type(format!("<path d=\"{}\"/>", d_string))
+    geometry_information::svg_path_geom_type(&d_string)
+}
+
+/// Returns the geometry type declared at the start of a WKT string, e.g., "MULTIPOLYGON".
+///
+/// The type is read case insensitively, and an EWKT `SRID=...;` prefix, a Z/M/ZM
+/// dimension, and EMPTY are understood. It throws an error if no known type is found.
+///
+#[wasm_bindgen(js_name = wktGeomType)]
+pub fn wkt_geom_type(wkt: String) -> Result<String, JsValue> {
+    Ok(geometry_information::wkt_geometry_type(&wkt)?
+        .kind
+        .name()
+        .into())
+}
+
+/// Returns the full geometry type declared at the start of a WKT string as JSON,
+/// e.g., `{"type":"POINT","dimension":"XYZ","empty":false,"srid":4326}`.
+///
+#[wasm_bindgen(js_name = wktGeomTypeDetails)]
+pub fn wkt_geom_type_details(wkt: String) -> Result<String, JsValue> {
+    Ok(geometry_information::wkt_geometry_type(&wkt)?.to_json())
+}
+
+/* Measurements */
+
+/// Returns the area of a WKT geometry.
+///
+/// By default the area is unsigned and holes are subtracted from their shells.
+/// When `signed` is true, each ring adds its signed area instead: counter clockwise
+/// rings (in y-up coordinates, which look clockwise in SVG's y-down coordinates)
+/// are positive and clockwise rings are negative.
+/// When `pixelsPerMm` is given, the result is converted from pixels into mm².
+///
+#[wasm_bindgen(js_name = wktArea)]
+pub fn wkt_area(
+    wkt: String,
+    pixels_per_mm: Option<f64>,
+    signed: Option<bool>,
+) -> Result<f64, JsValue> {
+    Ok(geometry_information::wkt_area(
+        &wkt,
+        pixels_per_mm,
+        signed.unwrap_or(false),
+    )?)
+}
+
+/// Returns the perimeter of a WKT geometry, including the rings of any holes.
+/// When `pixelsPerMm` is given, the result is converted from pixels into mm.
+///
+#[wasm_bindgen(js_name = wktPerimeter)]
+pub fn wkt_perimeter(wkt: String, pixels_per_mm: Option<f64>) -> Result<f64, JsValue> {
+    Ok(geometry_information::wkt_perimeter(&wkt, pixels_per_mm)?)
+}
+
+/// Returns the centroid of a WKT geometry as [x, y], or undefined for an empty geometry.
+/// When `pixelsPerMm` is given, the result is converted from pixels into mm.
+///
+#[wasm_bindgen(js_name = wktCentroid)]
+pub fn wkt_centroid(wkt: String, pixels_per_mm: Option<f64>) -> Result<Option<Vec<f64>>, JsValue> {
+    Ok(geometry_information::wkt_centroid(&wkt, pixels_per_mm)?.map(|c| vec![c.x, c.y]))
+}
+
+/// Returns the area of an SVG element. See `wktArea` for the meaning of `signed`.
+/// When `pixelsPerMm` is given, the result is converted from pixels into mm².
+///
+#[wasm_bindgen(js_name = svgArea)]
+pub fn svg_area(
+    svg: String,
+    pixels_per_mm: Option<f64>,
+    signed: Option<bool>,
+) -> Result<f64, JsValue> {
+    Ok(geometry_information::svg_area(
+        &svg,
+        pixels_per_mm,
+        signed.unwrap_or(false),
+    )?)
+}
+
+/// Returns the perimeter of an SVG element, including the rings of any holes.
+/// When `pixelsPerMm` is given, the result is converted from pixels into mm.
+///
+#[wasm_bindgen(js_name = svgPerimeter)]
+pub fn svg_perimeter(svg: String, pixels_per_mm: Option<f64>) -> Result<f64, JsValue> {
+    Ok(geometry_information::svg_perimeter(&svg, pixels_per_mm)?)
 }
 
-/// This function reads a submitted string and makes a very quick decision
-/// about the WKT geometry type it must contain. This function does not
-/// actually check if the string really contains the shape it claims to
-/// contain, nor does it do any processing or validation. All validation should
-/// be down downstream.
-///
-pub fn get_geometry_type(geom: &str) -> Result<String, JsValue> {
-    match geom.get(..1) {
-        Some("G") => Ok(String::from("GeometryCollection")),
-        Some("L") => Ok(String::from("LineString")),
-        Some("M") => {
-            if geom.starts_with("MultiL") {
-                Ok(String::from("MultiLineString"))
-            } else if geom.starts_with("MultiPoi") {
-                Ok(String::from("MultiPoint"))
-            } else if geom.starts_with("MultiP") {
-                Ok(String::from("MultiPolygon"))
-            } else {
-                Err(json_errors::wkt_errors::wkt_cannot_be_parsed(geom))
-            }
-        }
-        Some("P") => {
-            if geom.starts_with("Poi") {
-                Ok(String::from("Point"))
-            } else {
-                Ok(String::from("Polygon"))
-            }
-        }
-        // Return immediately on empty string
-        Some(&_) => Err(json_errors::wkt_errors::wkt_cannot_be_parsed(geom)),
-        None => Err(json_errors::wkt_errors::wkt_cannot_be_parsed(geom)),
-    }
+/// Returns the centroid of an SVG element as [x, y], or undefined for an empty element.
+/// When `pixelsPerMm` is given, the result is converted from pixels into mm.
+///
+#[wasm_bindgen(js_name = svgCentroid)]
+pub fn svg_centroid(svg: String, pixels_per_mm: Option<f64>) -> Result<Option<Vec<f64>>, JsValue> {
+    Ok(geometry_information::svg_centroid(&svg, pixels_per_mm)?.map(|c| vec![c.x, c.y]))
+}
+
+/* Bounding Boxes */
+
+/// Returns the bounding box of a WKT geometry as a Float64Array of
+/// [minX, minY, maxX, maxY], or undefined for an empty geometry.
+///
+#[wasm_bindgen(js_name = wktBoundingBox)]
+pub fn wkt_bounding_box(wkt: String) -> Result<Option<Vec<f64>>, JsValue> {
+    Ok(geometry_information::wkt_bounding_box(&wkt)?.map(rect_to_vec))
+}
+
+/// Returns the bounding box of an SVG element as a Float64Array of
+/// [minX, minY, maxX, maxY], or undefined for an empty element.
+///
+#[wasm_bindgen(js_name = svgBoundingBox)]
+pub fn svg_bounding_box(svg: String) -> Result<Option<Vec<f64>>, JsValue> {
+    Ok(geometry_information::svg_bounding_box(&svg)?.map(rect_to_vec))
+}
+
+/// Returns the bounding box of a WKT geometry as a WKT POLYGON, or as an SVG
+/// <rect> when `format` is "svg". An empty geometry gives "POLYGON EMPTY" or "".
+///
+#[wasm_bindgen(js_name = wktEnvelope)]
+pub fn wkt_envelope(wkt: String, format: Option<String>) -> Result<String, JsValue> {
+    let format = match format {
+        Some(format) => geometry_information::EnvelopeFormat::parse(&format)?,
+        None => geometry_information::EnvelopeFormat::Wkt,
+    };
+    Ok(geometry_information::wkt_envelope(&wkt, format)?)
+}
+
+/// Returns the bounding box of an SVG element as an SVG <rect>, or as a WKT
+/// POLYGON when `format` is "wkt". An empty element gives "" or "POLYGON EMPTY".
+///
+#[wasm_bindgen(js_name = svgEnvelope)]
+pub fn svg_envelope(svg: String, format: Option<String>) -> Result<String, JsValue> {
+    let format = match format {
+        Some(format) => geometry_information::EnvelopeFormat::parse(&format)?,
+        None => geometry_information::EnvelopeFormat::Svg,
+    };
+    Ok(geometry_information::svg_envelope(&svg, format)?)
 }
 
-pub fn type_of<T>(_: T) -> String {
-    std::any::type_name::<T>().into()
+fn rect_to_vec(rect: Rect<f64>) -> Vec<f64> {
+    vec![rect.min.x, rect.min.y, rect.max.x, rect.max.y]
 }
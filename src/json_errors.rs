@@ -1,50 +1,309 @@
-pub mod wkt_errors {
-    use wasm_bindgen::JsValue;
-    pub fn invalid_wkt_type(wkt: &str) -> JsValue {
-        JsValue::from_str(
-            format!("The wkt geometry type is invalid or unsupported: {}", wkt).as_ref(),
-        )
+use crate::error::GeometryToolsError;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::JsValue;
+
+/// An error that is handed to JS as a JSON string.
+///
+/// Every error has a stable `code` that callers can match on, a human readable
+/// `message`, the offending `input` (when there is one), and a `details` object
+/// with any extra information about the failure.
+///
+pub struct JsonError {
+    code: &'static str,
+    message: String,
+    input: Option<String>,
+    details: Vec<(&'static str, String)>,
+}
+
+impl JsonError {
+    pub fn new(code: &'static str, message: &str) -> Self {
+        JsonError {
+            code,
+            message: message.into(),
+            input: None,
+            details: vec![],
+        }
     }
 
-    pub fn wkt_cannot_be_parsed(wkt: &str) -> JsValue {
-        JsValue::from_str(
-            format!("The wkt geometry could not be successfully parsed: {}", wkt).as_ref(),
-        )
+    pub fn with_input(mut self, input: &str) -> Self {
+        self.input = Some(input.into());
+        self
+    }
+
+    pub fn with_detail(mut self, key: &'static str, value: &str) -> Self {
+        self.details.push((key, json_string(value)));
+        self
     }
 
-    pub fn invalid_geometry(wkt: &str) -> JsValue {
-        JsValue::from_str(
+    pub fn with_number_detail(mut self, key: &'static str, value: usize) -> Self {
+        self.details.push((key, value.to_string()));
+        self
+    }
+
+    pub fn with_list_detail(mut self, key: &'static str, values: &[String]) -> Self {
+        self.details.push((
+            key,
             format!(
-                "The submitted shape resulted in an invalid geometry: {}",
-                wkt
-            )
-            .as_ref(),
+                "[{}]",
+                values
+                    .iter()
+                    .map(|v| json_string(v))
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
+        ));
+        self
+    }
+
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"code\":{},\"message\":{},\"input\":{},\"details\":{{{}}}}}",
+            json_string(self.code),
+            json_string(&self.message),
+            match &self.input {
+                Some(input) => json_string(input),
+                None => "null".into(),
+            },
+            self.details
+                .iter()
+                .map(|(key, value)| format!("{}:{}", json_string(key), value))
+                .collect::<Vec<String>>()
+                .join(",")
         )
     }
 }
 
+impl From<&GeometryToolsError> for JsonError {
+    fn from(err: &GeometryToolsError) -> Self {
+        let json_error = JsonError::new(err.code(), &err.message());
+        let json_error = match err.input() {
+            Some(input) => json_error.with_input(input),
+            None => json_error,
+        };
+        match err {
+            GeometryToolsError::WktParse { reason, .. }
+            | GeometryToolsError::SvgParse { reason, .. }
+            | GeometryToolsError::GeoJsonParse { reason, .. }
+            | GeometryToolsError::WkbParse { reason, .. } => {
+                json_error.with_detail("reason", reason)
+            }
+            GeometryToolsError::InvalidGeometry { failed_rules, .. } => {
+                json_error.with_list_detail("failedRules", failed_rules)
+            }
+            GeometryToolsError::InvalidBooleanGeometryPair {
+                geometry1,
+                geometry2,
+            } => json_error
+                .with_detail("geometry1", geometry1)
+                .with_detail("geometry2", geometry2),
+            GeometryToolsError::InvalidArgument { argument, reason } => json_error
+                .with_detail("argument", argument)
+                .with_detail("reason", reason),
+            GeometryToolsError::InvalidUnionInput { index, reason, .. } => json_error
+                .with_number_detail("index", *index)
+                .with_detail("reason", reason),
+            GeometryToolsError::InvalidWktType { .. }
+            | GeometryToolsError::IrreparableGeometry { .. } => json_error,
+        }
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl From<JsonError> for JsValue {
+    fn from(err: JsonError) -> Self {
+        JsValue::from_str(&err.to_json())
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl From<GeometryToolsError> for JsValue {
+    fn from(err: GeometryToolsError) -> Self {
+        JsonError::from(&err).into()
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+pub mod wkt_errors {
+    use crate::error::GeometryToolsError;
+    use geo_validator::ValidationErrors;
+
+    pub fn invalid_wkt_type(wkt: &str) -> GeometryToolsError {
+        GeometryToolsError::InvalidWktType { input: wkt.into() }
+    }
+
+    pub fn wkt_cannot_be_parsed(wkt: &str, reason: &str) -> GeometryToolsError {
+        GeometryToolsError::WktParse {
+            input: wkt.into(),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn invalid_geometry(wkt: &str, validation: &ValidationErrors<f64>) -> GeometryToolsError {
+        GeometryToolsError::InvalidGeometry {
+            input: wkt.into(),
+            failed_rules: failed_validation_rules(validation),
+        }
+    }
+
+    /// Lists the names of the validation rules that a geometry has broken
+    pub fn failed_validation_rules(validation: &ValidationErrors<f64>) -> Vec<String> {
+        let mut rules = vec![] as Vec<String>;
+        if validation.has_less_than_three_points {
+            rules.push("hasLessThanThreePoints".into());
+        }
+        if validation.is_multi_polygon {
+            rules.push("isMultiPolygon".into());
+        }
+        if !validation.unsupported_floating_point_values.is_empty() {
+            rules.push("unsupportedFloatingPointValues".into());
+        }
+        if !validation.open_rings.is_empty() {
+            rules.push("openRings".into());
+        }
+        if !validation.ring_intersects_other_ring.is_empty() {
+            rules.push("ringIntersectsOtherRing".into());
+        }
+        if !validation.self_intersections.is_empty() {
+            rules.push("selfIntersections".into());
+        }
+        if !validation.point_touching_line.is_empty() {
+            rules.push("pointTouchingLine".into());
+        }
+        if !validation.repeated_points.is_empty() {
+            rules.push("repeatedPoints".into());
+        }
+        rules
+    }
+}
+
 pub mod geometry_processing_error {
-    use wasm_bindgen::JsValue;
-    pub fn invalid_boolean_geom_pair(g1: &str, g2: &str) -> JsValue {
-        JsValue::from_str(
-            format!(
-                "Cannot perform a boolean operation on geometries {} and {}",
-                g1, g2
-            )
-            .as_ref(),
-        )
+    use crate::error::GeometryToolsError;
+
+    pub fn invalid_boolean_geom_pair(g1: &str, g2: &str) -> GeometryToolsError {
+        GeometryToolsError::InvalidBooleanGeometryPair {
+            geometry1: g1.into(),
+            geometry2: g2.into(),
+        }
+    }
+    pub fn invalid_union_input(index: usize, input: &str, reason: &str) -> GeometryToolsError {
+        GeometryToolsError::InvalidUnionInput {
+            index,
+            input: input.into(),
+            reason: reason.into(),
+        }
     }
-    pub fn invalid_geom(reason: &str) -> JsValue {
-        JsValue::from_str(format!("The geometry is bad. {}", reason).as_ref())
+    pub fn irreparable_geom(input: &str) -> GeometryToolsError {
+        GeometryToolsError::IrreparableGeometry {
+            input: input.into(),
+        }
     }
-    pub fn irreparable_geom() -> JsValue {
-        JsValue::from_str("The attempt to repair the geometry failed")
+}
+
+pub mod argument_error {
+    use crate::error::GeometryToolsError;
+
+    pub fn invalid_argument(argument: &str, reason: &str) -> GeometryToolsError {
+        GeometryToolsError::InvalidArgument {
+            argument: argument.into(),
+            reason: reason.into(),
+        }
     }
 }
 
 pub mod svg_error {
-    use wasm_bindgen::JsValue;
-    pub fn could_not_parse() -> JsValue {
-        JsValue::from_str("The submitted SVG element/d-string could not be parsed")
+    use crate::error::GeometryToolsError;
+    use geo_svg_io::geo_svg_reader::SvgError;
+
+    pub fn could_not_parse(svg: &str, reason: &str) -> GeometryToolsError {
+        GeometryToolsError::SvgParse {
+            input: svg.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Describes why the SVG reader rejected an input
+    pub fn describe(err: &SvgError) -> String {
+        match err {
+            SvgError::ParseError(e) => format!("A number could not be parsed: {}", e),
+            SvgError::SvgInvalidType(e) => e.to_string(),
+            SvgError::SvgGeomCollectionForGeometry(e) => e.to_string(),
+            SvgError::InvalidSvgError(e) => e.to_string(),
+        }
+    }
+}
+
+pub mod geojson_error {
+    use crate::error::GeometryToolsError;
+
+    pub fn could_not_parse(geojson: &str, reason: &str) -> GeometryToolsError {
+        GeometryToolsError::GeoJsonParse {
+            input: geojson.into(),
+            reason: reason.into(),
+        }
+    }
+}
+
+pub mod wkb_error {
+    use crate::error::GeometryToolsError;
+
+    pub fn could_not_parse(wkb: &str, reason: &str) -> GeometryToolsError {
+        GeometryToolsError::WkbParse {
+            input: wkb.into(),
+            reason: reason.into(),
+        }
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_serialize_error_to_json() {
+        let err = JsonError::new("INVALID_UNION_INPUT", "Bad \"input\"")
+            .with_input("POLYGON((0 0,1 0))")
+            .with_number_detail("index", 2)
+            .with_list_detail("failedRules", &["openRings".into()]);
+        assert_eq!(
+            r#"{"code":"INVALID_UNION_INPUT","message":"Bad \"input\"","input":"POLYGON((0 0,1 0))","details":{"index":2,"failedRules":["openRings"]}}"#,
+            err.to_json()
+        );
+    }
+
+    #[test]
+    fn can_serialize_geometry_tools_error() {
+        let err = svg_error::could_not_parse("<circle/>", "Unsupported element");
+        assert_eq!(
+            r#"{"code":"SVG_PARSE_ERROR","message":"The submitted SVG element/d-string could not be parsed","input":"<circle/>","details":{"reason":"Unsupported element"}}"#,
+            JsonError::from(&err).to_json()
+        );
+    }
+
+    #[test]
+    fn serializes_missing_input_as_null() {
+        let err = JsonError::new("IRREPARABLE_GEOMETRY", "Failed\n");
+        assert_eq!(
+            r#"{"code":"IRREPARABLE_GEOMETRY","message":"Failed\n","input":null,"details":{}}"#,
+            err.to_json()
+        );
     }
 }
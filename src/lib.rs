@@ -4,45 +4,84 @@
 //! SVG and WellKnown geometry types. It offers a set of validation functions
 //! along with the standard geometric boolean operations.
 //!
+//! The core of the library works on `geo_types::Geometry<f64>` and reports
+//! failures with [`GeometryToolsError`], so it can be used from native Rust.
+//! The JS bindings are thin wrappers around that core and are only compiled
+//! with the `wasm` cargo feature (enabled by default).
+//!
 
+#[cfg(feature = "wasm")]
 mod boolean;
+#[cfg(feature = "wasm")]
+mod buffer;
+#[cfg(feature = "wasm")]
 mod convertors;
-mod geometry_boolean;
+pub mod error;
+pub mod geometry_boolean;
+pub mod geometry_buffer;
+pub mod geometry_convert;
+pub mod geometry_geojson;
+#[cfg(feature = "wasm")]
+mod geometry_handle;
+pub mod geometry_hull;
+pub mod geometry_information;
+pub mod geometry_precision;
+pub mod geometry_predicate;
+pub mod geometry_simplify;
+pub mod geometry_transform;
+pub mod geometry_validate;
+pub mod geometry_wkb;
+#[cfg(feature = "wasm")]
+mod hull;
+#[cfg(feature = "wasm")]
 mod information;
-mod json_errors;
+pub mod json_errors;
+#[cfg(feature = "wasm")]
+mod predicates;
+#[cfg(feature = "wasm")]
+mod simplify;
+#[cfg(feature = "wasm")]
+mod transform;
 mod utils;
+#[cfg(feature = "wasm")]
 mod validators;
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
+#[cfg(feature = "wasm")]
 use crate::convertors::svg_to_wkt;
 
+pub use crate::error::GeometryToolsError;
+
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
 // allocator.
 #[cfg(feature = "wee_alloc")]
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
+#[cfg(feature = "wasm")]
 #[wasm_bindgen]
 extern "C" {
     fn alert(s: &str);
 }
 
 /// Shows a greeting in an alert (remove for production).
+#[cfg(feature = "wasm")]
 #[wasm_bindgen]
 pub fn greet() {
     alert("Hello, geometry-tools!");
 }
 
+#[cfg(feature = "wasm")]
 #[wasm_bindgen]
 pub fn exp_svg_to_wkt(svg: String) -> Result<String, JsValue> {
-    svg_to_wkt(svg)
+    svg_to_wkt(svg, JsValue::UNDEFINED)
 }
 
-/** Tests */
+/* Tests */
 
-#[cfg(test)]
+#[cfg(all(test, feature = "wasm"))]
 mod tests {
-    use super::*;
     use crate::boolean::wkt_polygon_union;
 
     #[test]
@@ -50,6 +89,8 @@ mod tests {
         let res = wkt_polygon_union(
             "POLYGON((0 0,10 0,10 10,0 10,0 0),(3 3,6 3,6 6,3 6,3 3))".into(),
             "POLYGON((2 2,4 2,4 4,2 4,2 2))".into(),
+            None,
+            None,
         );
         assert_eq!(res, res);
     }
@@ -0,0 +1,209 @@
+use crate::geometry_predicate;
+use wasm_bindgen::prelude::*;
+
+/* Point Containment */
+
+/// Tests whether the point (`x`, `y`) lies in a WKT geometry.
+///
+/// A point inside a polygon, on a line, or equal to a point is contained. A point
+/// on the rings of a polygon or at an end of a line lies on the boundary, and is
+/// only contained when `includeBoundary` is true (the default).
+///
+/// It throws an error if the geometry cannot be read or the coordinates are not
+/// finite numbers.
+///
+#[wasm_bindgen(js_name = wktContainsPoint)]
+pub fn wkt_contains_point(
+    wkt: String,
+    x: f64,
+    y: f64,
+    include_boundary: Option<bool>,
+) -> Result<bool, JsValue> {
+    Ok(geometry_predicate::wkt_contains_point(
+        &wkt,
+        x,
+        y,
+        include_boundary.unwrap_or(true),
+    )?)
+}
+
+/// Tests whether the point (`x`, `y`) lies in an SVG element (see `wktContainsPoint`).
+///
+#[wasm_bindgen(js_name = svgContainsPoint)]
+pub fn svg_contains_point(
+    svg: String,
+    x: f64,
+    y: f64,
+    include_boundary: Option<bool>,
+) -> Result<bool, JsValue> {
+    Ok(geometry_predicate::svg_contains_point(
+        &svg,
+        x,
+        y,
+        include_boundary.unwrap_or(true),
+    )?)
+}
+
+/* Hit Testing */
+
+/// Returns the indices of the WKT geometries in `shapes` that contain the point
+/// (`x`, `y`), see `wktContainsPoint`.
+///
+/// The indices are ordered by the area of their geometry, smallest first, so the
+/// innermost of nested shapes comes first. Shapes with the same area keep their
+/// order.
+///
+/// It throws an error naming the index of the first shape that cannot be read.
+///
+#[wasm_bindgen(js_name = wktHitTest)]
+pub fn wkt_hit_test(
+    shapes: Vec<String>,
+    x: f64,
+    y: f64,
+    include_boundary: Option<bool>,
+) -> Result<Vec<u32>, JsValue> {
+    Ok(
+        geometry_predicate::wkt_hit_test(&shapes, x, y, include_boundary.unwrap_or(true))?
+            .into_iter()
+            .map(|index| index as u32)
+            .collect(),
+    )
+}
+
+/// Returns the indices of the SVG elements in `shapes` that contain the point
+/// (`x`, `y`), innermost first (see `wktHitTest`).
+///
+#[wasm_bindgen(js_name = svgHitTest)]
+pub fn svg_hit_test(
+    shapes: Vec<String>,
+    x: f64,
+    y: f64,
+    include_boundary: Option<bool>,
+) -> Result<Vec<u32>, JsValue> {
+    Ok(
+        geometry_predicate::svg_hit_test(&shapes, x, y, include_boundary.unwrap_or(true))?
+            .into_iter()
+            .map(|index| index as u32)
+            .collect(),
+    )
+}
+
+/* Spatial Predicates */
+
+/// Tests whether two WKT geometries have at least one point in common.
+///
+/// The geometries are expected to be valid. It throws an error if either of
+/// them cannot be read.
+///
+#[wasm_bindgen(js_name = wktIntersects)]
+pub fn wkt_intersects(wkt1: String, wkt2: String) -> Result<bool, JsValue> {
+    Ok(geometry_predicate::wkt_relate(&wkt1, &wkt2)?.intersects())
+}
+
+/// Tests whether two WKT geometries have no point in common (see `wktIntersects`).
+///
+#[wasm_bindgen(js_name = wktDisjoint)]
+pub fn wkt_disjoint(wkt1: String, wkt2: String) -> Result<bool, JsValue> {
+    Ok(geometry_predicate::wkt_relate(&wkt1, &wkt2)?.disjoint())
+}
+
+/// Tests whether no point of the second WKT geometry lies outside the first,
+/// and their interiors meet (see `wktIntersects`).
+///
+#[wasm_bindgen(js_name = wktContains)]
+pub fn wkt_contains(wkt1: String, wkt2: String) -> Result<bool, JsValue> {
+    Ok(geometry_predicate::wkt_relate(&wkt1, &wkt2)?.contains())
+}
+
+/// Tests whether no point of the first WKT geometry lies outside the second,
+/// and their interiors meet (see `wktIntersects`).
+///
+#[wasm_bindgen(js_name = wktWithin)]
+pub fn wkt_within(wkt1: String, wkt2: String) -> Result<bool, JsValue> {
+    Ok(geometry_predicate::wkt_relate(&wkt1, &wkt2)?.within())
+}
+
+/// Tests whether two WKT geometries meet, but only on their boundaries
+/// (see `wktIntersects`).
+///
+#[wasm_bindgen(js_name = wktTouches)]
+pub fn wkt_touches(wkt1: String, wkt2: String) -> Result<bool, JsValue> {
+    Ok(geometry_predicate::wkt_relate(&wkt1, &wkt2)?.touches())
+}
+
+/// Tests whether two WKT geometries of the same dimension share part of their
+/// interiors while each has points outside the other (see `wktIntersects`).
+///
+#[wasm_bindgen(js_name = wktOverlaps)]
+pub fn wkt_overlaps(wkt1: String, wkt2: String) -> Result<bool, JsValue> {
+    Ok(geometry_predicate::wkt_relate(&wkt1, &wkt2)?.overlaps())
+}
+
+/// Tests whether two SVG elements have at least one point in common (see `wktIntersects`).
+///
+#[wasm_bindgen(js_name = svgIntersects)]
+pub fn svg_intersects(svg1: String, svg2: String) -> Result<bool, JsValue> {
+    Ok(geometry_predicate::svg_relate(&svg1, &svg2)?.intersects())
+}
+
+/// Tests whether two SVG elements have no point in common (see `wktDisjoint`).
+///
+#[wasm_bindgen(js_name = svgDisjoint)]
+pub fn svg_disjoint(svg1: String, svg2: String) -> Result<bool, JsValue> {
+    Ok(geometry_predicate::svg_relate(&svg1, &svg2)?.disjoint())
+}
+
+/// Tests whether the first SVG element contains the second (see `wktContains`).
+///
+#[wasm_bindgen(js_name = svgContains)]
+pub fn svg_contains(svg1: String, svg2: String) -> Result<bool, JsValue> {
+    Ok(geometry_predicate::svg_relate(&svg1, &svg2)?.contains())
+}
+
+/// Tests whether the first SVG element lies within the second (see `wktWithin`).
+///
+#[wasm_bindgen(js_name = svgWithin)]
+pub fn svg_within(svg1: String, svg2: String) -> Result<bool, JsValue> {
+    Ok(geometry_predicate::svg_relate(&svg1, &svg2)?.within())
+}
+
+/// Tests whether two SVG elements only meet on their boundaries (see `wktTouches`).
+///
+#[wasm_bindgen(js_name = svgTouches)]
+pub fn svg_touches(svg1: String, svg2: String) -> Result<bool, JsValue> {
+    Ok(geometry_predicate::svg_relate(&svg1, &svg2)?.touches())
+}
+
+/// Tests whether two SVG elements partly overlap (see `wktOverlaps`).
+///
+#[wasm_bindgen(js_name = svgOverlaps)]
+pub fn svg_overlaps(svg1: String, svg2: String) -> Result<bool, JsValue> {
+    Ok(geometry_predicate::svg_relate(&svg1, &svg2)?.overlaps())
+}
+
+/* Relate */
+
+/// Returns the DE-9IM matrix of two WKT geometries as a string of nine
+/// characters.
+///
+/// Row by row, it gives the dimension of the intersection of the interior,
+/// boundary and exterior of the first geometry with the interior, boundary and
+/// exterior of the second: `F` when they do not intersect, or `0`, `1` or `2`.
+/// For example, `212101212` for two polygons that partly overlap, or
+/// `2FF1FF212` when the first polygon lies inside the second without touching
+/// its boundary.
+///
+/// The geometries are expected to be valid. It throws an error if either of
+/// them cannot be read.
+///
+#[wasm_bindgen(js_name = wktRelate)]
+pub fn wkt_relate(wkt1: String, wkt2: String) -> Result<String, JsValue> {
+    Ok(geometry_predicate::wkt_relate(&wkt1, &wkt2)?.to_string())
+}
+
+/// Returns the DE-9IM matrix of two SVG elements (see `wktRelate`).
+///
+#[wasm_bindgen(js_name = svgRelate)]
+pub fn svg_relate(svg1: String, svg2: String) -> Result<String, JsValue> {
+    Ok(geometry_predicate::svg_relate(&svg1, &svg2)?.to_string())
+}
@@ -0,0 +1,53 @@
+use crate::geometry_simplify::{self, SimplifyOptions};
+use wasm_bindgen::prelude::*;
+
+/* Simplification */
+
+/// Removes the vertices of a WKT geometry that hardly change its shape.
+///
+/// `algorithm` is "douglas-peucker" (the default), for which `tolerance` is the
+/// largest distance between a removed vertex and the simplified line, or
+/// "visvalingam", for which it is the largest area of the triangle that a removed
+/// vertex forms with its neighbours. When `preserveTopology` is true, no line or
+/// ring is allowed to cross or touch another, so the result stays valid.
+///
+/// The result is JSON with the simplified WKT and the number of coordinates
+/// before and after, e.g., `{"geometry":"POLYGON(...)","verticesAfter":5,"verticesBefore":6}`.
+///
+/// It throws an error if the options are invalid, or if `preserveTopology` is
+/// true and the geometry is not valid.
+///
+#[wasm_bindgen(js_name = wktSimplify)]
+pub fn wkt_simplify(
+    wkt: String,
+    tolerance: f64,
+    algorithm: Option<String>,
+    preserve_topology: Option<bool>,
+) -> Result<String, JsValue> {
+    Ok(geometry_simplify::wkt_simplify(
+        &wkt,
+        tolerance,
+        &SimplifyOptions::parse(algorithm.as_deref(), preserve_topology)?,
+    )?
+    .to_json())
+}
+
+/// Removes the vertices of an SVG element that hardly change its shape (see `wktSimplify`).
+///
+/// The result is JSON with the simplified SVG element and the number of
+/// coordinates before and after.
+///
+#[wasm_bindgen(js_name = svgSimplify)]
+pub fn svg_simplify(
+    svg: String,
+    tolerance: f64,
+    algorithm: Option<String>,
+    preserve_topology: Option<bool>,
+) -> Result<String, JsValue> {
+    Ok(geometry_simplify::svg_simplify(
+        &svg,
+        tolerance,
+        &SimplifyOptions::parse(algorithm.as_deref(), preserve_topology)?,
+    )?
+    .to_json())
+}
@@ -0,0 +1,152 @@
+use crate::geometry_transform::{self, AffineTransform, MirrorDirection, TransformParameters};
+use geo_types::Coordinate;
+use wasm_bindgen::prelude::*;
+
+/* Matrix Transforms */
+
+/// Transforms a WKT geometry with a 2x3 affine matrix, given in the same order
+/// as an SVG `matrix(a b c d e f)`: [a, b, c, d, e, f].
+/// A point (x, y) is moved to (a * x + c * y + e, b * x + d * y + f).
+///
+/// It throws an error unless the matrix has six finite numbers.
+///
+#[wasm_bindgen(js_name = wktTransform)]
+pub fn wkt_transform(wkt: String, matrix: Vec<f64>) -> Result<String, JsValue> {
+    Ok(geometry_transform::wkt_transform(
+        &wkt,
+        &AffineTransform::from_matrix(&matrix)?,
+    )?)
+}
+
+/// Transforms an SVG element with a 2x3 affine matrix. See `wktTransform`.
+///
+#[wasm_bindgen(js_name = svgTransform)]
+pub fn svg_transform(svg: String, matrix: Vec<f64>) -> Result<String, JsValue> {
+    Ok(geometry_transform::svg_transform(
+        &svg,
+        &AffineTransform::from_matrix(&matrix)?,
+    )?)
+}
+
+/* Parameter Transforms */
+
+/// Transforms a WKT geometry by scaling it and rotating it about an origin, and then translating it.
+///
+/// The rotation is given in degrees, and positive angles turn the x axis towards the
+/// y axis (clockwise on screen in SVG). The origin defaults to (0, 0), the scale to 1,
+/// and everything else to 0. `scaleY` defaults to `scaleX`.
+///
+#[allow(clippy::too_many_arguments)]
+#[wasm_bindgen(js_name = wktTransformWith)]
+pub fn wkt_transform_with(
+    wkt: String,
+    translate_x: Option<f64>,
+    translate_y: Option<f64>,
+    rotate: Option<f64>,
+    origin_x: Option<f64>,
+    origin_y: Option<f64>,
+    scale_x: Option<f64>,
+    scale_y: Option<f64>,
+) -> Result<String, JsValue> {
+    let transform = parameters(
+        translate_x,
+        translate_y,
+        rotate,
+        origin_x,
+        origin_y,
+        scale_x,
+        scale_y,
+    )?;
+    Ok(geometry_transform::wkt_transform(&wkt, &transform)?)
+}
+
+/// Transforms an SVG element by scaling it and rotating it about an origin, and then translating it.
+/// See `wktTransformWith`.
+///
+#[allow(clippy::too_many_arguments)]
+#[wasm_bindgen(js_name = svgTransformWith)]
+pub fn svg_transform_with(
+    svg: String,
+    translate_x: Option<f64>,
+    translate_y: Option<f64>,
+    rotate: Option<f64>,
+    origin_x: Option<f64>,
+    origin_y: Option<f64>,
+    scale_x: Option<f64>,
+    scale_y: Option<f64>,
+) -> Result<String, JsValue> {
+    let transform = parameters(
+        translate_x,
+        translate_y,
+        rotate,
+        origin_x,
+        origin_y,
+        scale_x,
+        scale_y,
+    )?;
+    Ok(geometry_transform::svg_transform(&svg, &transform)?)
+}
+
+/* Convenience Transforms */
+
+/// Mirrors a WKT geometry across a line through its centroid.
+/// The `direction` is "horizontal" (swap left and right) or "vertical" (swap top and bottom).
+///
+#[wasm_bindgen(js_name = wktMirror)]
+pub fn wkt_mirror(wkt: String, direction: String) -> Result<String, JsValue> {
+    Ok(geometry_transform::wkt_mirror(
+        &wkt,
+        MirrorDirection::parse(&direction)?,
+    )?)
+}
+
+/// Mirrors an SVG element across a line through its centroid. See `wktMirror`.
+///
+#[wasm_bindgen(js_name = svgMirror)]
+pub fn svg_mirror(svg: String, direction: String) -> Result<String, JsValue> {
+    Ok(geometry_transform::svg_mirror(
+        &svg,
+        MirrorDirection::parse(&direction)?,
+    )?)
+}
+
+/// Rotates a WKT geometry about its centroid by an angle in degrees.
+/// It throws an error if `degrees` is not a finite number.
+///
+#[wasm_bindgen(js_name = wktRotateAboutCentroid)]
+pub fn wkt_rotate_about_centroid(wkt: String, degrees: f64) -> Result<String, JsValue> {
+    Ok(geometry_transform::wkt_rotate_about_centroid(
+        &wkt, degrees,
+    )?)
+}
+
+/// Rotates an SVG element about its centroid by an angle in degrees.
+///
+#[wasm_bindgen(js_name = svgRotateAboutCentroid)]
+pub fn svg_rotate_about_centroid(svg: String, degrees: f64) -> Result<String, JsValue> {
+    Ok(geometry_transform::svg_rotate_about_centroid(
+        &svg, degrees,
+    )?)
+}
+
+fn parameters(
+    translate_x: Option<f64>,
+    translate_y: Option<f64>,
+    rotate: Option<f64>,
+    origin_x: Option<f64>,
+    origin_y: Option<f64>,
+    scale_x: Option<f64>,
+    scale_y: Option<f64>,
+) -> Result<AffineTransform, JsValue> {
+    let scale_x = scale_x.unwrap_or(1.);
+    Ok(TransformParameters {
+        translate: (translate_x.unwrap_or(0.), translate_y.unwrap_or(0.)),
+        rotate: rotate.unwrap_or(0.),
+        scale: (scale_x, scale_y.unwrap_or(scale_x)),
+        origin: Coordinate {
+            x: origin_x.unwrap_or(0.),
+            y: origin_y.unwrap_or(0.),
+        },
+    }
+    .to_transform()?)
+}
@@ -1,62 +1,26 @@
-use geo_repair_polygon::repair::Repair;
-use geo_svg_io::geo_svg_reader::svg_to_geometry_collection;
-use geo_types::Geometry;
-use geo_validator::Validate;
-use geo_wkt_writer::ToWkt;
+use crate::geometry_precision::Precision;
+use crate::geometry_validate;
 use wasm_bindgen::prelude::*;
-use wkt::Wkt;
 
-/** Validators */
+/* Validators */
 
 /// Tests whether an SVG element can represent a valid Geometry.
 /// This function can read a <path>, <polygon>. <polyline>,
 /// <rect>, and <line>, all other SVG elements will fail
-/// immediately.
+/// immediately. Every shape element of the SVG is read, so a
+/// <g> with several shapes is checked as a GeometryCollection,
+/// and every geometry read must pass the checks of `geometry_is_valid`.
 ///
 #[wasm_bindgen(js_name = svgIsValidGeom)]
 pub fn svg_is_valid_geom(svg: String) -> bool {
-    let geom = match svg_to_geometry_collection(&svg) {
-        Ok(geom) => geom,
-        Err(_) => return false,
-    };
-    for shape in geom.0 {
-        match shape {
-            Geometry::MultiPolygon { .. } => {
-                if !shape.into_multi_polygon().unwrap().validate() {
-                    return false;
-                }
-            }
-            Geometry::Polygon { .. } => {
-                if !shape.into_polygon().unwrap().validate() {
-                    return false;
-                }
-            }
-            Geometry::MultiLineString { .. } => {
-                if shape.into_multi_line_string().is_none() {
-                    return false;
-                }
-            }
-            Geometry::LineString { .. } => {
-                if shape.into_line_string().is_none() {
-                    return false;
-                }
-            }
-            Geometry::Line { .. } => {
-                if shape.into_line().is_none() {
-                    return false;
-                }
-            }
-            _ => return false,
-        }
-    }
-    false
+    geometry_validate::svg_is_valid_geom(&svg)
 }
 
 /// Tests whether an SVG <path> d-string can represent a valid Geometry.
 ///
 #[wasm_bindgen(js_name = svgPathStringIsValidGeom)]
 pub fn svg_path_string_is_valid_geom(d_string: String) -> bool {
-    svg_is_valid_geom(format!("<path d=\"{}\"/>", d_string))
+    geometry_validate::svg_path_string_is_valid_geom(&d_string)
 }
 
 /// Tests whether an SVG element is a valid polygon.
@@ -66,25 +30,14 @@ pub fn svg_path_string_is_valid_geom(d_string: String) -> bool {
 ///
 #[wasm_bindgen(js_name = validateSvgPolygon)]
 pub fn validate_svg_polygon(svg: String) -> bool {
-    let geom = match svg_to_geometry_collection(&svg) {
-        Ok(geom) => geom,
-        Err(_) => return false,
-    };
-    if geom.0.len() != 1 {
-        return false;
-    }
-    let poly = match geom.0[0].clone().into_polygon() {
-        Some(p) => p,
-        None => return false,
-    };
-    poly.validate()
+    geometry_validate::validate_svg_polygon(&svg)
 }
 
 /// Tests whether an SVG <path> d-string is a valid polygon.
 ///
 #[wasm_bindgen(js_name = validateSvgPathStringAsPolygon)]
 pub fn validate_svg_path_string_as_polygon(d_string: String) -> bool {
-    validate_svg_polygon(format!("<path d=\"{}\"/>", d_string))
+    geometry_validate::validate_svg_path_string_as_polygon(&d_string)
 }
 
 /// Tests whether an SVG element represents a valid multi polygon geometry.
@@ -94,43 +47,86 @@ pub fn validate_svg_path_string_as_polygon(d_string: String) -> bool {
 ///
 #[wasm_bindgen(js_name = validateSvgMultiPolygon)]
 pub fn validate_svg_multi_polygon(svg: String) -> bool {
-    let geom = match svg_to_geometry_collection(&svg) {
-        Ok(geom) => geom,
-        Err(_) => return false,
-    };
-    if geom.0.len() != 1 {
-        return false;
-    }
-    let poly = match geom.0[0].clone().into_multi_polygon() {
-        Some(p) => p,
-        None => return false,
-    };
-    poly.validate()
+    geometry_validate::validate_svg_multi_polygon(&svg)
 }
 
 /// Tests whether an SVG <path> d-string represents a valid multi polygon geometry.
 ///
 #[wasm_bindgen(js_name = validateSvgPathStringAsMultiPolygon)]
 pub fn validate_svg_path_string_as_multi_polygon(d_string: String) -> bool {
-    validate_svg_multi_polygon(format!("<path d=\"{}\"/>", d_string))
+    geometry_validate::validate_svg_path_string_as_multi_polygon(&d_string)
 }
 
-/// Repairs a WKT geometry.
+/// Tests whether a WKT string can represent a valid Geometry.
+/// The same rules are applied as for `svgIsValidGeom`.
 ///
-#[wasm_bindgen(js_name = repairWkt)]
-pub fn repair_wkt(wkt: String) -> String {
-    let wkt_geom: Wkt<f64> = match Wkt::from_str(&wkt) {
-        Ok(g1) => g1,
-        Err(_) => return "INVALIDGEOMETRY".into(),
-    };
+#[wasm_bindgen(js_name = wktIsValidGeom)]
+pub fn wkt_is_valid_geom(wkt: String) -> bool {
+    geometry_validate::wkt_is_valid_geom(&wkt)
+}
+
+/// Tests whether a WKT string is a valid POLYGON.
+///
+#[wasm_bindgen(js_name = validateWktPolygon)]
+pub fn validate_wkt_polygon(wkt: String) -> bool {
+    geometry_validate::validate_wkt_polygon(&wkt)
+}
 
-    let geo = match wkt::conversion::try_into_geometry(&wkt_geom.items[0]) {
-        Ok(g1) => g1,
-        Err(_) => return "INVALIDGEOMETRY".into(),
-    };
+/// Tests whether a WKT string is a valid MULTIPOLYGON.
+///
+#[wasm_bindgen(js_name = validateWktMultiPolygon)]
+pub fn validate_wkt_multi_polygon(wkt: String) -> bool {
+    geometry_validate::validate_wkt_multi_polygon(&wkt)
+}
 
-    match geo.repair() {
-        Some(g) => g.to_wkt(),
-        None => "INVALIDGEOMETRY".into(),
-    }
+/// Validates a WKT geometry and returns a JSON report of every violation found.
+///
+/// The report looks like `{"valid": false, "violations": [...]}`, where each
+/// violation has a `kind` (e.g., "selfIntersection" or "holeOutsideShell"), the
+/// `rings` and `segments` indices involved, and the `coordinates` of the spots
+/// to highlight. `geometry` is the path of member indices to the geometry that
+/// the problem was found in, e.g., `[1, 0]` for the first polygon of a
+/// MULTIPOLYGON that is the second member of a GEOMETRYCOLLECTION, and `[]`
+/// for the geometry itself. `otherGeometry` is the path to the polygon that it
+/// overlaps, or null. `valid` is the same verdict as `wktIsValidGeom` gives,
+/// and an invalid geometry always has at least one violation.
+///
+#[wasm_bindgen(js_name = wktValidationReport)]
+pub fn wkt_validation_report(wkt: String) -> Result<String, JsValue> {
+    Ok(geometry_validate::wkt_validation_report(&wkt)?.to_json())
+}
+
+/// Validates an SVG element and returns a JSON report of every violation found.
+/// See `wktValidationReport` for the format of the report.
+///
+#[wasm_bindgen(js_name = svgValidationReport)]
+pub fn svg_validation_report(svg: String) -> Result<String, JsValue> {
+    Ok(geometry_validate::svg_validation_report(&svg)?.to_json())
+}
+
+/// Validates an SVG <path> d-string and returns a JSON report of every violation found.
+/// See `wktValidationReport` for the format of the report.
+///
+#[wasm_bindgen(js_name = svgPathStringValidationReport)]
+pub fn svg_path_string_validation_report(d_string: String) -> Result<String, JsValue> {
+    Ok(geometry_validate::svg_path_string_validation_report(&d_string)?.to_json())
+}
+
+/// Repairs a WKT geometry.
+/// It throws an error if the WKT cannot be parsed or the geometry cannot be repaired.
+///
+/// See `wktToSvg` for `precision` and `precisionMode`, which round the
+/// coordinates of the repaired geometry. An invalid `precision` or
+/// `precisionMode` throws an INVALID_ARGUMENT error.
+///
+#[wasm_bindgen(js_name = repairWkt)]
+pub fn repair_wkt(
+    wkt: String,
+    precision: Option<u32>,
+    precision_mode: Option<String>,
+) -> Result<String, JsValue> {
+    let precision = Precision::parse(precision, precision_mode.as_deref())?;
+    Ok(geometry_validate::repair_wkt_with_precision(
+        &wkt, precision,
+    )?)
 }